@@ -0,0 +1,63 @@
+//! Build script generating static word arrays for the built-in wordlists.
+//!
+//! Each built-in wordlist in `res` is turned into a `&'static [&'static str]` so a
+//! `StaticWordList` can be used without parsing or allocating at runtime. Wordlists behind a
+//! disabled cargo feature are skipped.
+
+use std::{env, fs, path::Path};
+
+/// The built-in wordlists to generate static word arrays for, by constant name, file path and
+/// the cargo feature required to include it, if any.
+const WORDLISTS: &[(&str, &str, Option<&str>)] = &[
+    (
+        "EFF_LARGE_WORDS",
+        "res/eff/large.txt",
+        Some("wordlist-eff-large"),
+    ),
+    (
+        "EFF_SHORT_WORDS",
+        "res/eff/short.txt",
+        Some("wordlist-eff-short"),
+    ),
+    (
+        "EFF_GENERAL_SHORT_WORDS",
+        "res/eff/general_short.txt",
+        Some("wordlist-eff-general-short"),
+    ),
+    ("BIP39_ENGLISH_WORDS", "res/bip39/english.txt", None),
+    ("PGP_EVEN_WORDS", "res/pgp/even.txt", None),
+    ("PGP_ODD_WORDS", "res/pgp/odd.txt", None),
+    ("THEME_ADJECTIVE_WORDS", "res/theme/adjective.txt", None),
+    ("THEME_ANIMAL_WORDS", "res/theme/animal.txt", None),
+    ("THEME_COLOR_WORDS", "res/theme/color.txt", None),
+    ("THEME_VERB_WORDS", "res/theme/verb.txt", None),
+];
+
+/// Check whether the given cargo feature is enabled.
+fn feature_enabled(feature: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+    env::var_os(var).is_some()
+}
+
+fn main() {
+    let mut code = String::new();
+
+    for (name, path, feature) in WORDLISTS {
+        if let Some(feature) = feature {
+            if !feature_enabled(feature) {
+                continue;
+            }
+        }
+        println!("cargo:rerun-if-changed={}", path);
+
+        let words = fs::read_to_string(path).expect("failed to read built-in wordlist");
+        code += &format!("pub(crate) static {}: &[&str] = &[\n", name);
+        for word in words.lines().filter(|w| !w.is_empty()) {
+            code += &format!("    {:?},\n", word);
+        }
+        code += "];\n";
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("builtin.rs");
+    fs::write(out, code).expect("failed to write generated built-in wordlists");
+}
@@ -0,0 +1,98 @@
+//! Curate wordlists from the command line
+//!
+//! A minimal tool wrapping the wordlist cleaning, statistics and dice numbering APIs of this
+//! crate, so lists can be curated before using them for generation:
+//!
+//! ```txt
+//! cargo run --example wordlist -- clean <input> <output>
+//! cargo run --example wordlist -- stats <input>
+//! cargo run --example wordlist -- dice <input> <output>
+//! ```
+//!
+//! Input wordlists may be plain or diced, see [`WordList::load_auto`](word::WordList::load_auto).
+
+use std::env;
+use std::process;
+
+use chbs::{
+    prelude::*,
+    word::{
+        tidy::{TidyConfig, TidyReason},
+        WordList, WordListFormat,
+    },
+};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["clean", input, output] => clean(input, output),
+        ["stats", input] => stats(input),
+        ["dice", input, output] => dice(input, output),
+        _ => {
+            eprintln!(
+                "usage: wordlist (clean <input> <output> | stats <input> | dice <input> <output>)"
+            );
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+/// Clean the wordlist at `input` with the default tidy configuration, and save it to `output`.
+fn clean(input: &str, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (list, report) = TidyConfig::default().tidy(&WordList::load_auto(input)?)?;
+    list.save(output, WordListFormat::Plain)?;
+
+    println!("Words: {} -> {}", report.words_before, report.words_after());
+    for reason in [
+        TidyReason::NonAlphabetic,
+        TidyReason::Duplicate,
+        TidyReason::Prefix,
+        TidyReason::EditDistance,
+        TidyReason::UniquePrefix,
+    ]
+    .iter()
+    {
+        println!("Removed ({:?}): {}", reason, report.count(*reason));
+    }
+    println!(
+        "Entropy change per word: {:.2} bits",
+        report.entropy_change().bits()
+    );
+    Ok(())
+}
+
+/// Print statistics of the wordlist at `input`.
+fn stats(input: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let list = WordList::load_auto(input)?;
+    let lengths: Vec<usize> = list.words().iter().map(|w| w.chars().count()).collect();
+
+    println!("Words: {}", lengths.len());
+    println!("Entropy per word: {:.2} bits", list.entropy().bits());
+    println!("Dice per word: {}", list.dice_count());
+    println!(
+        "Word length: {} to {}, {:.1} on average",
+        lengths.iter().min().unwrap(),
+        lengths.iter().max().unwrap(),
+        lengths.iter().sum::<usize>() as f64 / lengths.len() as f64,
+    );
+    Ok(())
+}
+
+/// Save the wordlist at `input` to `output`, prefixing each word with its dice number.
+fn dice(input: &str, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let list = WordList::load_auto(input)?;
+    list.save(output, WordListFormat::Diced)?;
+    println!(
+        "Saved {} words for {} dice",
+        list.words().len(),
+        list.dice_count()
+    );
+    Ok(())
+}
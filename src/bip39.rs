@@ -0,0 +1,244 @@
+//! BIP39 mnemonic seed phrase generation
+//!
+//! This module generates [`Mnemonic`](Mnemonic) seed phrases as specified by
+//! [BIP39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki), as used by
+//! cryptocurrency wallets. Unlike free-form passphrases, the last word of a mnemonic includes
+//! checksum bits derived from the entropy using SHA-256. Words are taken from the
+//! [`BUILTIN_BIP39_ENGLISH`](crate::word::BUILTIN_BIP39_ENGLISH) wordlist.
+//!
+//! Only available with the `bip39` feature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chbs::bip39::{Mnemonic, MnemonicLength};
+//!
+//! let mnemonic = Mnemonic::generate(MnemonicLength::Words24);
+//! assert_eq!(24, mnemonic.words().len());
+//! assert_eq!(256.0, mnemonic.entropy().bits());
+//!
+//! // Mnemonics can be parsed and verified again
+//! let parsed = Mnemonic::parse(&mnemonic.phrase()).unwrap();
+//! assert_eq!(mnemonic, parsed);
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::entropy::Entropy;
+use crate::prelude::*;
+use crate::rng::default_rng;
+use crate::word::StaticWordList;
+
+/// The number of bits each mnemonic word encodes.
+const BITS_PER_WORD: usize = 11;
+
+/// The number of words in a BIP39 mnemonic.
+///
+/// Each length encodes a different amount of entropy, with one checksum bit per 32 bits of
+/// entropy.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MnemonicLength {
+    /// 12 words, encoding 128 bits of entropy.
+    Words12,
+
+    /// 15 words, encoding 160 bits of entropy.
+    Words15,
+
+    /// 18 words, encoding 192 bits of entropy.
+    Words18,
+
+    /// 21 words, encoding 224 bits of entropy.
+    Words21,
+
+    /// 24 words, encoding 256 bits of entropy.
+    Words24,
+}
+
+impl MnemonicLength {
+    /// Get the mnemonic length for the given number of words, if valid.
+    pub fn from_words(words: usize) -> Option<Self> {
+        match words {
+            12 => Some(MnemonicLength::Words12),
+            15 => Some(MnemonicLength::Words15),
+            18 => Some(MnemonicLength::Words18),
+            21 => Some(MnemonicLength::Words21),
+            24 => Some(MnemonicLength::Words24),
+            _ => None,
+        }
+    }
+
+    /// Get the number of words.
+    pub fn words(self) -> usize {
+        match self {
+            MnemonicLength::Words12 => 12,
+            MnemonicLength::Words15 => 15,
+            MnemonicLength::Words18 => 18,
+            MnemonicLength::Words21 => 21,
+            MnemonicLength::Words24 => 24,
+        }
+    }
+
+    /// Get the number of entropy bytes encoded.
+    pub fn entropy_bytes(self) -> usize {
+        self.words() * BITS_PER_WORD * 32 / 33 / 8
+    }
+}
+
+/// A BIP39 mnemonic seed phrase.
+///
+/// Construct one by [generating](Mnemonic::generate) it, from existing
+/// [entropy](Mnemonic::from_entropy), or by [parsing](Mnemonic::parse) a phrase.
+///
+/// # Security
+///
+/// The mnemonic holds its entropy, which is the wallet seed material. Treat it as a secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mnemonic {
+    /// The encoded entropy.
+    entropy: Vec<u8>,
+
+    /// The mnemonic words, including the checksum.
+    words: Vec<&'static str>,
+}
+
+impl Mnemonic {
+    /// Generate a random mnemonic of the given length.
+    pub fn generate(length: MnemonicLength) -> Self {
+        Self::generate_with_rng(length, &mut default_rng())
+    }
+
+    /// Generate a random mnemonic of the given length, using the given randomness source.
+    pub fn generate_with_rng(length: MnemonicLength, rng: &mut dyn SecureRng) -> Self {
+        let mut entropy = vec![0; length.entropy_bytes()];
+        rng.fill_bytes(&mut entropy);
+        Self::from_entropy(&entropy).expect("generated entropy has a valid length")
+    }
+
+    /// Construct the mnemonic encoding the given entropy.
+    ///
+    /// The entropy must be 16, 20, 24, 28 or 32 bytes.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        if ![16, 20, 24, 28, 32].contains(&entropy.len()) {
+            return Err(MnemonicError::EntropyLength(entropy.len()));
+        }
+
+        // Append the checksum, the first bit of the hash for each 32 bits of entropy
+        let checksum = Sha256::digest(entropy);
+        let bits = entropy.len() * 8 + entropy.len() / 4;
+        let bit = |i: usize| {
+            let byte = entropy.get(i / 8).unwrap_or(&checksum[0]);
+            byte >> (7 - i % 8) & 1
+        };
+
+        let wordlist = StaticWordList::builtin_bip39_english().words();
+        let words = (0..bits / BITS_PER_WORD)
+            .map(|word| {
+                let index = (0..BITS_PER_WORD).fold(0, |index, i| {
+                    index << 1 | bit(word * BITS_PER_WORD + i) as usize
+                });
+                wordlist[index]
+            })
+            .collect();
+
+        Ok(Self {
+            entropy: entropy.to_vec(),
+            words,
+        })
+    }
+
+    /// Parse and verify a mnemonic phrase.
+    ///
+    /// Words may be separated by any whitespace, and are matched case-insensitively. An error is
+    /// returned if the number of words is invalid, a word is unknown, or the checksum doesn't
+    /// match.
+    pub fn parse(phrase: &str) -> Result<Self, MnemonicError> {
+        let wordlist = StaticWordList::builtin_bip39_english().words();
+        let indices = phrase
+            .split_whitespace()
+            .map(|word| {
+                let lowercase = word.to_lowercase();
+                wordlist
+                    .binary_search(&lowercase.as_str())
+                    .map_err(|_| MnemonicError::UnknownWord(word.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let length = MnemonicLength::from_words(indices.len())
+            .ok_or(MnemonicError::WordCount(indices.len()))?;
+
+        let mut entropy = vec![0u8; length.entropy_bytes()];
+        for (i, index) in indices.iter().enumerate() {
+            for b in 0..BITS_PER_WORD {
+                let position = i * BITS_PER_WORD + b;
+                if position >= entropy.len() * 8 {
+                    break;
+                }
+                let value = (index >> (BITS_PER_WORD - 1 - b) & 1) as u8;
+                entropy[position / 8] |= value << (7 - position % 8);
+            }
+        }
+
+        let mnemonic = Self::from_entropy(&entropy)?;
+        if mnemonic
+            .words
+            .iter()
+            .zip(&indices)
+            .any(|(w, &i)| *w != wordlist[i])
+        {
+            return Err(MnemonicError::Checksum);
+        }
+        Ok(mnemonic)
+    }
+
+    /// Get the mnemonic phrase, with words separated by spaces.
+    pub fn phrase(&self) -> String {
+        self.words.join(" ")
+    }
+
+    /// Get the mnemonic words.
+    pub fn words(&self) -> &[&'static str] {
+        &self.words
+    }
+
+    /// Get the entropy encoded in this mnemonic.
+    ///
+    /// This is the seed material, excluding the checksum.
+    pub fn entropy_bytes(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Get the entropy of this mnemonic.
+    ///
+    /// The checksum bits are derived from the entropy, and don't add to it.
+    pub fn entropy(&self) -> Entropy {
+        Entropy::from_bits((self.entropy.len() * 8) as f64)
+    }
+}
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.phrase())
+    }
+}
+
+/// A [`Mnemonic`](Mnemonic) error.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MnemonicError {
+    /// The entropy has an invalid number of bytes.
+    #[error("invalid mnemonic entropy length of {0} bytes, must be 16, 20, 24, 28 or 32")]
+    EntropyLength(usize),
+
+    /// The phrase has an invalid number of words.
+    #[error("invalid mnemonic of {0} words, must be 12, 15, 18, 21 or 24")]
+    WordCount(usize),
+
+    /// The phrase contains a word not in the BIP39 English wordlist.
+    #[error("unknown mnemonic word '{0}'")]
+    UnknownWord(String),
+
+    /// The checksum of the phrase doesn't match.
+    #[error("invalid mnemonic checksum")]
+    Checksum,
+}
@@ -0,0 +1,100 @@
+//! Named character sets passphrases may be restricted to
+//!
+//! Passphrases are sometimes embedded in places that only allow specific characters, such as
+//! shell commands, links or QR payloads. The [`Charset`](Charset) type defines such a set of
+//! allowed characters.
+//!
+//! Use a [`CharsetPhraseBuilder`](crate::component::phrase::CharsetPhraseBuilder) to build
+//! passphrases restricted to a character set. [`Scheme::validate`](crate::scheme::Scheme::validate)
+//! reports components producing characters outside it.
+
+use std::fmt::{self, Display, Formatter};
+
+/// Symbols that never require quoting or escaping in common shells.
+///
+/// Together with ASCII letters and digits, these characters may be used in a shell command
+/// without quoting, see [`Charset::ShellSafe`](Charset::ShellSafe).
+pub const SHELL_SAFE_SYMBOLS: &str = "-_.,:+=@%/";
+
+/// Symbols in the unreserved set of RFC 3986.
+///
+/// Together with ASCII letters and digits, these characters may be used anywhere in a URL
+/// without percent-encoding, see [`Charset::UrlSafe`](Charset::UrlSafe).
+pub const URL_SAFE_SYMBOLS: &str = "-._~";
+
+/// Separators picked from for shell-safe passphrases.
+///
+/// This contains 8 (2<sup>3</sup>) symbols, providing 3 bits of entropy when uniformly sampled.
+/// The `-` and `/` symbols are excluded, as the first occurs inside words of the built-in
+/// wordlists and the second makes passphrases look like paths.
+const SHELL_SAFE_SEPARATORS: [&str; 8] = ["_", ".", ",", ":", "+", "=", "@", "%"];
+
+/// Separators picked from for URL-safe passphrases.
+///
+/// The `-` symbol is excluded, as it occurs inside words of the built-in wordlists.
+const URL_SAFE_SEPARATORS: [&str; 3] = ["_", ".", "~"];
+
+/// A named set of allowed characters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Charset {
+    /// Characters that never require quoting or escaping in common shells.
+    ///
+    /// This allows ASCII letters, digits and [`SHELL_SAFE_SYMBOLS`](SHELL_SAFE_SYMBOLS). Spaces,
+    /// quotes, `$`, backticks and all other characters are excluded. Useful for passphrases
+    /// destined to be pasted into scripts and terminals.
+    ShellSafe,
+
+    /// The unreserved characters of RFC 3986, which never require percent-encoding in URLs.
+    ///
+    /// This allows ASCII letters, digits and [`URL_SAFE_SYMBOLS`](URL_SAFE_SYMBOLS). Useful for
+    /// tokens embedded in links and QR payloads.
+    UrlSafe,
+}
+
+impl Charset {
+    /// Check whether the given character is part of this character set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::charset::Charset;
+    ///
+    /// assert!(Charset::UrlSafe.contains('~'));
+    /// assert!(!Charset::UrlSafe.contains(' '));
+    /// assert!(!Charset::ShellSafe.contains('$'));
+    /// ```
+    pub fn contains(self, c: char) -> bool {
+        c.is_ascii_alphanumeric() || self.symbols().contains(c)
+    }
+
+    /// Check whether all characters of the given string are part of this character set.
+    pub fn contains_all(self, s: &str) -> bool {
+        s.chars().all(|c| self.contains(c))
+    }
+
+    /// Get the symbols allowed in this character set, besides ASCII letters and digits.
+    pub fn symbols(self) -> &'static str {
+        match self {
+            Charset::ShellSafe => SHELL_SAFE_SYMBOLS,
+            Charset::UrlSafe => URL_SAFE_SYMBOLS,
+        }
+    }
+
+    /// Get the restricted pool of word separators for this character set.
+    pub fn separators(self) -> &'static [&'static str] {
+        match self {
+            Charset::ShellSafe => &SHELL_SAFE_SEPARATORS,
+            Charset::UrlSafe => &URL_SAFE_SEPARATORS,
+        }
+    }
+}
+
+impl Display for Charset {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Charset::ShellSafe => write!(f, "shell-safe"),
+            Charset::UrlSafe => write!(f, "URL-safe"),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Grapheme-safe helpers for styling components
+//!
+//! Stylers operating on characters may split a user-perceived character, such as `é` written as
+//! `e` followed by a combining accent, or a flag emoji made of two code points. These helpers
+//! operate on extended grapheme clusters instead, so styled words stay valid. They are used by
+//! the built-in stylers, and may be used to implement custom components.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chbs::component::grapheme::{grapheme_count, insert_at_grapheme, uppercase_first_grapheme};
+//!
+//! let word = "e\u{301}clair";
+//! assert_eq!(6, grapheme_count(word));
+//! assert_eq!("E\u{301}clair", uppercase_first_grapheme(word));
+//! assert_eq!("e\u{301}-clair", insert_at_grapheme(word, 1, "-"));
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::rng::{gen_index, SecureRng};
+
+/// Count the graphemes in the given string.
+pub fn grapheme_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// Get the byte offset of the grapheme boundary before the grapheme at `index`.
+///
+/// An `index` equal to the number of graphemes gives the length of the string.
+///
+/// # Panics
+///
+/// Panics if `index` is larger than the number of graphemes.
+pub fn grapheme_offset(s: &str, index: usize) -> usize {
+    s.grapheme_indices(true)
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(s.len()))
+        .nth(index)
+        .expect("grapheme index out of bounds")
+}
+
+/// Uppercase the first grapheme of the given string, leaving the rest unchanged.
+///
+/// All characters of the first grapheme are uppercased, so combining marks are kept with the
+/// character they belong to.
+pub fn uppercase_first_grapheme(s: &str) -> String {
+    let split = s.graphemes(true).next().map_or(0, str::len);
+    let (first, rest) = s.split_at(split);
+    first.to_uppercase() + rest
+}
+
+/// Insert `insert` into the given string at the grapheme boundary before grapheme `index`.
+///
+/// # Panics
+///
+/// Panics if `index` is larger than the number of graphemes.
+pub fn insert_at_grapheme(s: &str, index: usize, insert: &str) -> String {
+    let offset = grapheme_offset(s, index);
+    let mut result = String::with_capacity(s.len() + insert.len());
+    result.push_str(&s[..offset]);
+    result.push_str(insert);
+    result.push_str(&s[offset..]);
+    result
+}
+
+/// Pick a uniformly random grapheme boundary in the given string, using the given randomness
+/// source.
+///
+/// This returns a grapheme index from `0` up to and including the number of graphemes, for use
+/// with [`insert_at_grapheme`](insert_at_grapheme). Inserting at the returned index never splits
+/// a grapheme.
+pub fn random_grapheme_boundary(s: &str, rng: &mut dyn SecureRng) -> usize {
+    gen_index(rng, grapheme_count(s) + 1)
+}
@@ -10,15 +10,60 @@
 //! are listed below:
 //!
 //! - [`WordSetProvider`](self::traits::WordSetProvider)
+//! - [`WordFilter`](self::traits::WordFilter)
 //! - [`WordStyler`](self::traits::WordStyler)
 //! - [`PhraseBuilder`](self::traits::PhraseBuilder)
 //! - [`PhraseStyler`](self::traits::PhraseStyler)
+//! - [`PhraseValidator`](self::traits::PhraseValidator)
 //!
 //! The modules [`word`](self::word) and [`phrase`](self::phrase) contains various included
 //! components to use. For example, the [`WordCapitalizer`](self::word::WordCapitalizer) component
-//! may be used to capitalize passphrase words as configured.
+//! may be used to capitalize passphrase words as configured. The [`grapheme`](self::grapheme)
+//! module provides Unicode-safe helpers for implementing stylers.
+
+use std::fmt::{self, Display, Formatter};
 
 // Re-export the modules
+pub mod grapheme;
 pub mod phrase;
 pub mod traits;
 pub mod word;
+
+/// A component kind.
+///
+/// Identifies one of the component kinds a [`Scheme`](::scheme::Scheme) is built from, used in
+/// reports and warnings to refer to a specific component.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ComponentKind {
+    /// A [`WordSetProvider`](self::traits::WordSetProvider).
+    WordSetProvider,
+
+    /// A [`WordFilter`](self::traits::WordFilter).
+    WordFilter,
+
+    /// A [`WordStyler`](self::traits::WordStyler).
+    WordStyler,
+
+    /// A [`PhraseBuilder`](self::traits::PhraseBuilder).
+    PhraseBuilder,
+
+    /// A [`PhraseStyler`](self::traits::PhraseStyler).
+    PhraseStyler,
+
+    /// A [`PhraseValidator`](self::traits::PhraseValidator).
+    PhraseValidator,
+}
+
+impl Display for ComponentKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ComponentKind::WordSetProvider => write!(f, "word set provider"),
+            ComponentKind::WordFilter => write!(f, "word filter"),
+            ComponentKind::WordStyler => write!(f, "word styler"),
+            ComponentKind::PhraseBuilder => write!(f, "phrase builder"),
+            ComponentKind::PhraseStyler => write!(f, "phrase styler"),
+            ComponentKind::PhraseValidator => write!(f, "phrase validator"),
+        }
+    }
+}
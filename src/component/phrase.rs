@@ -5,13 +5,26 @@
 //!
 //! - [`PhraseBuilder`](super::traits::PhraseBuilder)
 //! - [`PhraseStyler`](super::traits::PhraseStyler)
+//! - [`PhraseValidator`](super::traits::PhraseValidator)
 //!
 //! Most of these components are used by configuration strucutres provided by this crate, see
 //! the [`config`](::config) module. You may of course implement these components in your own
 //! configuration structures and [`Scheme`](::scheme::Scheme) definitions.
 
+use std::borrow::Cow;
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "emoji")]
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::charset::Charset;
+use crate::component::grapheme::insert_at_grapheme;
 use crate::entropy::Entropy;
+use crate::locale::Locale;
 use crate::prelude::*;
+use crate::rng::{default_rng, gen_index};
+use crate::validate::{check_separator, SeparatorError};
 
 /// A passphrase builder with as constant word separator.
 ///
@@ -25,9 +38,51 @@ pub struct BasicPhraseBuilder {
 }
 
 impl BasicPhraseBuilder {
+    /// Construct a phrase builder using the given separator.
+    ///
+    /// To ensure the separator doesn't occur inside any word, use
+    /// [`new_checked`](BasicPhraseBuilder::new_checked) instead.
     pub fn new(separator: String) -> Self {
         Self { separator }
     }
+
+    /// Construct a phrase builder using the given separator, checked against the given `words`.
+    ///
+    /// If the separator occurs inside any of the words a passphrase can't be split into its words
+    /// reliably. An error is returned in that case, with a list of suggested safe separators.
+    /// See [`check_separator`](crate::validate::check_separator).
+    pub fn new_checked<S: AsRef<str>>(
+        separator: String,
+        words: &[S],
+    ) -> Result<Self, SeparatorError> {
+        check_separator(&separator, words)?;
+        Ok(Self::new(separator))
+    }
+
+    /// Construct a phrase builder joining words naturally for the given locale.
+    ///
+    /// This uses the [natural separator](Locale::separator) of the locale, which is empty for
+    /// languages written without spaces such as Chinese and Japanese. Words joined without
+    /// separator may be ambiguous, as different sets of words may form the same passphrase. Use
+    /// [`new`](BasicPhraseBuilder::new) with one of the locale's full-width
+    /// [separators](Locale::separators) to prevent this.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::{component::phrase::BasicPhraseBuilder, locale::Locale, prelude::*};
+    ///
+    /// let builder = BasicPhraseBuilder::for_locale(Locale::from_tag("ja"));
+    /// assert_eq!("馬電池", builder.build_phrase(vec!["馬".into(), "電池".into()]));
+    /// ```
+    pub fn for_locale(locale: Locale) -> Self {
+        Self::new(locale.separator().into())
+    }
+
+    /// Get the separator that is used.
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
 }
 
 impl HasEntropy for BasicPhraseBuilder {
@@ -40,4 +95,716 @@ impl PhraseBuilder for BasicPhraseBuilder {
     fn build_phrase(&self, words: Vec<String>) -> String {
         words.join(&self.separator)
     }
+
+    fn build_phrase_ref(&self, words: &[Cow<str>]) -> String {
+        words.join(&self.separator)
+    }
+
+    fn separators(&self) -> Vec<&str> {
+        vec![&self.separator]
+    }
+}
+
+/// A passphrase builder restricting passphrases to a character set.
+///
+/// This builds passphrases that only contain characters of the given [`Charset`](Charset), for
+/// example to never require quoting in a shell or percent-encoding in a URL.
+///
+/// For each passphrase, the separator is uniformly picked from the restricted
+/// [separator pool](Charset::separators) of the character set, which adds `log2(separators)`
+/// bits of entropy. Any character in a word outside the character set is replaced by the
+/// separator as well. Word stylers adding such characters should not be used with this builder,
+/// as the replacement may make different passphrases equal, and lower the entropy below what is
+/// reported. Phrase stylers are applied after this builder, and must only add characters of the
+/// character set. [`Scheme::validate`](crate::scheme::Scheme::validate) reports such components.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{charset::Charset, component::phrase::CharsetPhraseBuilder, prelude::*};
+///
+/// let builder = CharsetPhraseBuilder::new(Charset::ShellSafe);
+/// let phrase = builder.build_phrase(vec!["it's".into(), "$HOME".into()]);
+/// assert!(Charset::ShellSafe.contains_all(&phrase));
+/// assert_eq!(3.0, builder.entropy().bits());
+/// ```
+#[derive(Debug)]
+pub struct CharsetPhraseBuilder {
+    /// The character set passphrases are restricted to.
+    charset: Charset,
+}
+
+impl CharsetPhraseBuilder {
+    /// Construct a phrase builder restricting passphrases to the given `charset`.
+    pub fn new(charset: Charset) -> Self {
+        Self { charset }
+    }
+}
+
+impl HasEntropy for CharsetPhraseBuilder {
+    fn entropy(&self) -> Entropy {
+        Entropy::from_real(self.charset.separators().len() as f64)
+    }
+}
+
+impl PhraseBuilder for CharsetPhraseBuilder {
+    fn build_phrase(&self, words: Vec<String>) -> String {
+        self.build_phrase_with_rng(words, &mut default_rng())
+    }
+
+    fn build_phrase_with_rng(&self, words: Vec<String>, rng: &mut dyn SecureRng) -> String {
+        let separators = self.charset.separators();
+        let separator = separators[gen_index(rng, separators.len())];
+        words
+            .iter()
+            .map(|word| {
+                word.chars()
+                    .map(|c| {
+                        if self.charset.contains(c) {
+                            c.to_string()
+                        } else {
+                            separator.to_owned()
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn separators(&self) -> Vec<&str> {
+        self.charset.separators().to_vec()
+    }
+
+    fn charset(&self) -> Option<Charset> {
+        Some(self.charset)
+    }
+}
+
+/// A passphrase builder placing words between fixed literal text.
+///
+/// This is used for schemes built from a [`Template`](crate::template::Template). The builder
+/// is given the literal text before, between and after the words, so one more literal than
+/// there are words. For example, the literals `["", "-", "!"]` build `correct-horse!` from the
+/// words `correct` and `horse`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::TemplatePhraseBuilder, prelude::*};
+///
+/// let builder = TemplatePhraseBuilder::new(vec!["<".into(), "-".into(), ">".into()]);
+/// let phrase = builder.build_phrase(vec!["correct".into(), "horse".into()]);
+/// assert_eq!("<correct-horse>", phrase);
+/// assert_eq!(Some(vec!["correct".into(), "horse".into()]), builder.split_phrase(&phrase, None));
+/// ```
+#[derive(Debug)]
+pub struct TemplatePhraseBuilder {
+    /// The literal text before, between and after words.
+    literals: Vec<String>,
+}
+
+impl TemplatePhraseBuilder {
+    /// Construct a phrase builder using the given literals.
+    ///
+    /// # Panic
+    ///
+    /// `literals` must contain at least 2 items, being the text before and after the first word.
+    pub fn new(literals: Vec<String>) -> Self {
+        if literals.len() < 2 {
+            panic!("cannot construct TemplatePhraseBuilder with less than 2 literals");
+        }
+
+        Self { literals }
+    }
+}
+
+impl HasEntropy for TemplatePhraseBuilder {
+    fn entropy(&self) -> Entropy {
+        Entropy::zero()
+    }
+}
+
+impl PhraseBuilder for TemplatePhraseBuilder {
+    fn build_phrase(&self, words: Vec<String>) -> String {
+        let mut phrase = self.literals[0].clone();
+        for (i, word) in words.iter().enumerate() {
+            phrase.push_str(word);
+            if let Some(literal) = self.literals.get(i + 1) {
+                phrase.push_str(literal);
+            }
+        }
+        for literal in self.literals.iter().skip(words.len() + 1) {
+            phrase.push_str(literal);
+        }
+        phrase
+    }
+
+    fn separators(&self) -> Vec<&str> {
+        let inner = &self.literals[1..self.literals.len() - 1];
+        inner
+            .iter()
+            .map(String::as_str)
+            .filter(|literal| !literal.is_empty())
+            .collect()
+    }
+
+    /// Split a passphrase built by this builder back into its words.
+    ///
+    /// Words are delimited by the literals. Returns `None` if the phrase doesn't match the
+    /// literals, or if two words are adjacent without literal text in between, as their boundary
+    /// is unknown.
+    fn split_phrase(&self, phrase: &str, count: Option<usize>) -> Option<Vec<String>> {
+        let slots = self.literals.len() - 1;
+        if matches!(count, Some(count) if count != slots) {
+            return None;
+        }
+
+        let mut rest = phrase.strip_prefix(self.literals[0].as_str())?;
+        let mut words = Vec::with_capacity(slots);
+        for (i, literal) in self.literals[1..].iter().enumerate() {
+            let end = if i + 1 == slots {
+                rest.strip_suffix(literal.as_str())?.len()
+            } else if literal.is_empty() {
+                return None;
+            } else {
+                rest.find(literal.as_str())?
+            };
+            words.push(rest[..end].to_owned());
+            rest = &rest[end + literal.len()..];
+        }
+        Some(words)
+    }
+}
+
+/// A phrase styler appending a random year or date.
+///
+/// This appends a uniformly random year from a configurable range to the passphrase, such as
+/// `correct horse battery staple 1987`. The separator placed before the year is configurable.
+/// With the [`Date`](DateFormat::Date) format a uniformly random day within those years is
+/// appended instead, such as `correct horse battery staple 1987-06-21`.
+///
+/// The entropy this styler adds is `log2(years)`, where `years` is the number of years in the
+/// range. The default range of 1950 to 2049 adds about 6.6 bits. For dates it is `log2(days)`,
+/// adding about 15.2 bits for the default range.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::{DateFormat, YearAppender}, prelude::*};
+///
+/// let styler = YearAppender::new(1950..=2049, " ".into());
+/// let phrase = styler.style_phrase("correct horse".into());
+/// assert!(phrase.starts_with("correct horse 19") || phrase.starts_with("correct horse 20"));
+///
+/// let styler = YearAppender::new(1987..=1987, " ".into()).with_format(DateFormat::Date);
+/// let phrase = styler.style_phrase("correct horse".into());
+/// assert!(phrase.starts_with("correct horse 1987-"));
+/// assert_eq!("correct horse 1987-06-21".len(), phrase.len());
+/// ```
+#[derive(Debug)]
+pub struct YearAppender {
+    /// The range of years to pick from.
+    years: RangeInclusive<u32>,
+
+    /// The separator placed before the year.
+    separator: String,
+
+    /// The format of the appended token.
+    format: DateFormat,
+}
+
+/// The format of the token appended by a [`YearAppender`](YearAppender).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DateFormat {
+    /// A year, such as `1987`.
+    Year,
+
+    /// A date formatted as `YYYY-MM-DD`, such as `1987-06-21`.
+    Date,
+}
+
+impl YearAppender {
+    /// Construct a year appender picking years from the given range.
+    ///
+    /// # Panics
+    ///
+    /// The range of `years` must not be empty.
+    pub fn new(years: RangeInclusive<u32>, separator: String) -> Self {
+        if years.is_empty() {
+            panic!("cannot construct YearAppender with an empty range of years");
+        }
+
+        Self {
+            years,
+            separator,
+            format: DateFormat::Year,
+        }
+    }
+
+    /// Set the format of the appended token, defaults to [`Year`](DateFormat::Year).
+    pub fn with_format(mut self, format: DateFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Get the number of days in the range of years.
+    fn days(&self) -> u64 {
+        let years = u64::from(self.years.end() - self.years.start()) + 1;
+        years * 365 + leap_years_before(u64::from(*self.years.end()) + 1)
+            - leap_years_before(u64::from(*self.years.start()))
+    }
+}
+
+/// Count the leap years in the proleptic Gregorian calendar from year zero, up to but excluding
+/// `year`.
+fn leap_years_before(year: u64) -> u64 {
+    match year {
+        0 => 0,
+        year => (year - 1) / 4 - (year - 1) / 100 + (year - 1) / 400 + 1,
+    }
+}
+
+/// Check whether the given year is a leap year.
+fn is_leap_year(year: u32) -> bool {
+    let year = u64::from(year);
+    leap_years_before(year + 1) > leap_years_before(year)
+}
+
+impl Default for YearAppender {
+    /// Construct a year appender picking years from 1950 to 2049, separated by a space.
+    fn default() -> Self {
+        Self::new(1950..=2049, " ".into())
+    }
+}
+
+impl HasEntropy for YearAppender {
+    fn entropy(&self) -> Entropy {
+        match self.format {
+            DateFormat::Year => {
+                Entropy::from_real((self.years.end() - self.years.start()) as f64 + 1.0)
+            }
+            DateFormat::Date => Entropy::from_real(self.days() as f64),
+        }
+    }
+}
+
+impl PhraseStyler for YearAppender {
+    fn style_phrase(&self, phrase: String) -> String {
+        self.style_phrase_with_rng(phrase, &mut default_rng())
+    }
+
+    fn style_phrase_with_rng(&self, mut phrase: String, rng: &mut dyn SecureRng) -> String {
+        phrase.push_str(&self.separator);
+        match self.format {
+            DateFormat::Year => phrase.push_str(&rng.gen_range(self.years.clone()).to_string()),
+            DateFormat::Date => {
+                // Pick a uniformly random day, and find the year and month it falls in
+                let mut day = rng.gen_range(0..self.days());
+                let mut year = *self.years.start();
+                let days_in_year = |year| if is_leap_year(year) { 366 } else { 365 };
+                while day >= days_in_year(year) {
+                    day -= days_in_year(year);
+                    year += 1;
+                }
+                let february = if is_leap_year(year) { 29 } else { 28 };
+                let months = [31, february, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+                let mut month = 0;
+                while day >= months[month] {
+                    day -= months[month];
+                    month += 1;
+                }
+                phrase.push_str(&format!("{:04}-{:02}-{:02}", year, month + 1, day + 1));
+            }
+        }
+        phrase
+    }
+}
+
+/// A class of characters a passphrase may be required to start with or contain.
+///
+/// Used by [`LeadingCharStyler`](LeadingCharStyler), [`CharClassAppender`](CharClassAppender) and
+/// [`CharClassValidator`](CharClassValidator).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CharClass {
+    /// Any alphabetic character, `a` to `z` in either case, or non-ASCII letters.
+    Letter,
+
+    /// A lowercase letter.
+    Lowercase,
+
+    /// An uppercase letter.
+    Uppercase,
+
+    /// Any alphabetic character or digit.
+    Alphanumeric,
+
+    /// A digit, `0` to `9`.
+    Digit,
+
+    /// Any character that is not alphanumeric or whitespace, such as `!` or `#`.
+    Symbol,
+}
+
+impl CharClass {
+    /// Check whether the given character is part of this class.
+    pub fn matches(self, c: char) -> bool {
+        match self {
+            CharClass::Letter => c.is_alphabetic(),
+            CharClass::Lowercase => c.is_lowercase(),
+            CharClass::Uppercase => c.is_uppercase(),
+            CharClass::Alphanumeric => c.is_alphanumeric(),
+            CharClass::Digit => c.is_ascii_digit(),
+            CharClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+
+    /// The ASCII characters of this class a character is picked from when one must be injected.
+    fn pool(self) -> &'static str {
+        match self {
+            CharClass::Uppercase => "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            CharClass::Digit => "0123456789",
+            CharClass::Symbol => Charset::ShellSafe.symbols(),
+            _ => "abcdefghijklmnopqrstuvwxyz",
+        }
+    }
+}
+
+/// A phrase styler guaranteeing the passphrase starts with a character of a specific class.
+///
+/// Some systems reject passwords starting with a digit or symbol. If the passphrase doesn't start
+/// with a character of the configured [`CharClass`](CharClass), a random character of that class
+/// is injected at the start. Passphrases that already match are left unchanged.
+///
+/// # Entropy
+///
+/// This styler never rejects or reorders passphrases, so it doesn't lower the entropy of the
+/// components before it. A character is only injected for some passphrases, so its entropy is
+/// not counted, and the entropy of this styler is zero. Place it after any phrase styler that
+/// changes the start of the passphrase.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::{CharClass, LeadingCharStyler}, prelude::*};
+///
+/// let styler = LeadingCharStyler::new(CharClass::Letter);
+/// assert_eq!("horse", styler.style_phrase("horse".into()));
+/// let phrase = styler.style_phrase("7 horses".into());
+/// assert!(phrase.starts_with(char::is_alphabetic) && phrase.ends_with("7 horses"));
+/// ```
+#[derive(Debug)]
+pub struct LeadingCharStyler {
+    /// The class of character the passphrase must start with.
+    class: CharClass,
+}
+
+impl LeadingCharStyler {
+    /// Construct a styler guaranteeing passphrases start with a character of the given `class`.
+    pub fn new(class: CharClass) -> Self {
+        Self { class }
+    }
+}
+
+impl HasEntropy for LeadingCharStyler {
+    fn entropy(&self) -> Entropy {
+        Entropy::zero()
+    }
+}
+
+impl PhraseStyler for LeadingCharStyler {
+    fn style_phrase(&self, phrase: String) -> String {
+        self.style_phrase_with_rng(phrase, &mut default_rng())
+    }
+
+    fn style_phrase_with_rng(&self, phrase: String, rng: &mut dyn SecureRng) -> String {
+        if phrase.starts_with(|c| self.class.matches(c)) {
+            return phrase;
+        }
+
+        let pool = self.class.pool();
+        let i = gen_index(rng, pool.len());
+        insert_at_grapheme(&phrase, 0, &pool[i..=i])
+    }
+}
+
+/// A phrase styler appending a random character of each configured class.
+///
+/// Many systems require passwords to contain characters such as a digit or symbol. This appends
+/// a random ASCII character of each configured [`CharClass`](CharClass) to every passphrase, in
+/// order, so such requirements are always met.
+///
+/// # Entropy
+///
+/// A character is appended to every passphrase, so the entropy of each is counted. Digits add
+/// about 3.3 bits, letters about 4.7 bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::{CharClass, CharClassAppender}, prelude::*};
+///
+/// let styler = CharClassAppender::new(vec![CharClass::Digit, CharClass::Symbol]);
+/// let phrase = styler.style_phrase("horse".into());
+/// assert!(phrase.starts_with("horse") && phrase.chars().count() == 7);
+/// assert_eq!(10f64.log2(), CharClassAppender::new(vec![CharClass::Digit]).entropy().bits());
+/// ```
+#[derive(Debug)]
+pub struct CharClassAppender {
+    /// The classes to append a character of.
+    classes: Vec<CharClass>,
+}
+
+impl CharClassAppender {
+    /// Construct a styler appending a random character of each of the given `classes`.
+    pub fn new(classes: Vec<CharClass>) -> Self {
+        Self { classes }
+    }
+}
+
+impl HasEntropy for CharClassAppender {
+    fn entropy(&self) -> Entropy {
+        self.classes
+            .iter()
+            .map(|class| Entropy::from_real(class.pool().len() as f64))
+            .sum()
+    }
+}
+
+impl PhraseStyler for CharClassAppender {
+    fn style_phrase(&self, phrase: String) -> String {
+        self.style_phrase_with_rng(phrase, &mut default_rng())
+    }
+
+    fn style_phrase_with_rng(&self, mut phrase: String, rng: &mut dyn SecureRng) -> String {
+        for class in &self.classes {
+            let pool = class.pool();
+            let i = gen_index(rng, pool.len());
+            phrase.push_str(&pool[i..=i]);
+        }
+        phrase
+    }
+}
+
+/// A phrase validator rejecting passphrases longer than a maximum number of characters.
+///
+/// Length is counted in characters, not bytes. Prefer limiting the number of words over
+/// rejecting long passphrases where possible, as every rejection lowers entropy.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::MaxLengthValidator, prelude::*};
+///
+/// let validator = MaxLengthValidator::new(8);
+/// assert!(validator.validate_phrase("horse").is_ok());
+/// assert!(validator.validate_phrase("correct horse").is_err());
+/// ```
+#[derive(Debug)]
+pub struct MaxLengthValidator {
+    /// The maximum number of characters.
+    max_length: usize,
+}
+
+impl MaxLengthValidator {
+    /// Construct a validator rejecting passphrases longer than `max_length` characters.
+    pub fn new(max_length: usize) -> Self {
+        Self { max_length }
+    }
+}
+
+impl PhraseValidator for MaxLengthValidator {
+    fn validate_phrase(&self, phrase: &str) -> Result<(), String> {
+        (0..=self.max_length).validate_phrase(phrase)
+    }
+}
+
+/// Reject passphrases with a number of characters outside this range.
+///
+/// This is also used by [`LengthGuard`](crate::guard::LengthGuard).
+impl PhraseValidator for RangeInclusive<usize> {
+    fn validate_phrase(&self, phrase: &str) -> Result<(), String> {
+        let length = phrase.chars().count();
+        if length > *self.end() {
+            return Err(format!(
+                "passphrase has {} characters, exceeding the maximum of {}",
+                length,
+                self.end()
+            ));
+        }
+        if length < *self.start() {
+            return Err(format!(
+                "passphrase has {} characters, below the minimum of {}",
+                length,
+                self.start()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A phrase validator requiring passphrases to contain a character of each configured class.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::{CharClass, CharClassValidator}, prelude::*};
+///
+/// let validator = CharClassValidator::new(vec![CharClass::Uppercase, CharClass::Digit]);
+/// assert!(validator.validate_phrase("Horse 7").is_ok());
+/// assert!(validator.validate_phrase("horse 7").is_err());
+/// ```
+#[derive(Debug)]
+pub struct CharClassValidator {
+    /// The classes passphrases must contain a character of.
+    classes: Vec<CharClass>,
+}
+
+impl CharClassValidator {
+    /// Construct a validator requiring a character of each of the given `classes`.
+    pub fn new(classes: Vec<CharClass>) -> Self {
+        Self { classes }
+    }
+}
+
+impl PhraseValidator for CharClassValidator {
+    fn validate_phrase(&self, phrase: &str) -> Result<(), String> {
+        match self
+            .classes
+            .iter()
+            .find(|class| !phrase.chars().any(|c| class.matches(c)))
+        {
+            Some(class) => Err(format!("passphrase has no {:?} character", class)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A phrase validator rejecting passphrases containing any banned substring.
+///
+/// Substrings are matched case-insensitively. To reject specific whole passphrases, see
+/// [`BanList`](crate::guard::BanList) instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::BannedSubstringValidator, prelude::*};
+///
+/// let validator = BannedSubstringValidator::new(vec!["acme".into()]);
+/// assert!(validator.validate_phrase("correct horse").is_ok());
+/// assert!(validator.validate_phrase("ACME horse").is_err());
+/// ```
+#[derive(Debug)]
+pub struct BannedSubstringValidator {
+    /// The lowercase banned substrings.
+    banned: Vec<String>,
+}
+
+impl BannedSubstringValidator {
+    /// Construct a validator rejecting passphrases containing any of the `banned` substrings.
+    pub fn new(banned: Vec<String>) -> Self {
+        Self {
+            banned: banned
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_lowercase())
+                .collect(),
+        }
+    }
+}
+
+impl PhraseValidator for BannedSubstringValidator {
+    fn validate_phrase(&self, phrase: &str) -> Result<(), String> {
+        let phrase = phrase.to_lowercase();
+        match self
+            .banned
+            .iter()
+            .find(|banned| phrase.contains(banned.as_str()))
+        {
+            Some(banned) => Err(format!("passphrase contains banned '{}'", banned)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The curated set of emoji used by [`EmojiAppender`](EmojiAppender).
+///
+/// This contains 64 (2<sup>6</sup>) emoji, providing 6 bits of entropy per uniformly sampled
+/// emoji. Only emoji consisting of a single code point from Unicode 6.0 are included, without
+/// variation selectors, skin tones or joined sequences, for the best compatibility.
+///
+/// Only available with the `emoji` feature.
+#[cfg(feature = "emoji")]
+pub const EMOJI: [char; 64] = [
+    '🐶', '🐱', '🐭', '🐹', '🐰', '🐻', '🐼', '🐨', '🐯', '🐮', '🐷', '🐸', '🐵', '🐔', '🐧', '🐦',
+    '🐤', '🐺', '🐗', '🐴', '🐝', '🐛', '🐌', '🐞', '🐢', '🐍', '🐙', '🐠', '🐬', '🐳', '🌵', '🌲',
+    '🌴', '🍀', '🌻', '🌹', '🌷', '🍄', '🌙', '🌟', '🔥', '🌈', '🍎', '🍊', '🍋', '🍌', '🍉', '🍇',
+    '🍓', '🍒', '🍑', '🍍', '🍅', '🍆', '🌽', '🍞', '🍕', '🍔', '🍟', '🍩', '🍪', '🎂', '🍰', '🎈',
+];
+
+/// A phrase styler appending random emoji.
+///
+/// This appends a fixed number of random emoji from the curated [`EMOJI`](EMOJI) set to the
+/// passphrase. Each appended emoji adds 6 bits of entropy.
+///
+/// # Compatibility
+///
+/// Many systems don't handle emoji in passwords well. Some reject non-ASCII characters, some
+/// normalize or mangle them, and typing them is hard on devices without an emoji keyboard. Emoji
+/// are encoded as four bytes in UTF-8, which matters for systems limiting the password length in
+/// bytes. Make sure the target system supports them before using this styler.
+///
+/// Only available with the `emoji` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::phrase::EmojiAppender, prelude::*};
+///
+/// let styler = EmojiAppender::new(2, " ".into());
+/// let phrase = styler.style_phrase("correct horse".into());
+/// assert_eq!(16, phrase.chars().count());
+/// ```
+#[cfg(feature = "emoji")]
+#[derive(Debug)]
+pub struct EmojiAppender {
+    /// The number of emoji to append.
+    count: usize,
+
+    /// The separator placed before the emoji.
+    separator: String,
+}
+
+#[cfg(feature = "emoji")]
+impl EmojiAppender {
+    /// Construct an emoji appender appending `count` emoji, after the given separator.
+    pub fn new(count: usize, separator: String) -> Self {
+        Self { count, separator }
+    }
+}
+
+#[cfg(feature = "emoji")]
+impl HasEntropy for EmojiAppender {
+    fn entropy(&self) -> Entropy {
+        Entropy::from_real(EMOJI.len() as f64) * self.count as f64
+    }
+}
+
+#[cfg(feature = "emoji")]
+impl PhraseStyler for EmojiAppender {
+    fn style_phrase(&self, phrase: String) -> String {
+        self.style_phrase_with_rng(phrase, &mut default_rng())
+    }
+
+    fn style_phrase_with_rng(&self, mut phrase: String, rng: &mut dyn SecureRng) -> String {
+        if self.count == 0 {
+            return phrase;
+        }
+
+        phrase.push_str(&self.separator);
+        phrase.extend((0..self.count).map(|_| EMOJI.choose(rng).unwrap()));
+        phrase
+    }
 }
@@ -2,9 +2,15 @@
 //!
 //! The function of this trait is defined in the [`component`](super) module.
 
+use std::borrow::Cow;
 use std::fmt::Debug;
 
+use crate::charset::Charset;
+use crate::component::word::WordFilterError;
+use crate::entropy::Entropy;
+use crate::limits::LimitExceeded;
 use crate::prelude::*;
+use crate::word::provider::{Filter, Map};
 
 /// Something that provides random words.
 ///
@@ -20,12 +26,215 @@ use crate::prelude::*;
 /// useful to implement on types that support this functionallity. In addition to that, the
 /// [`WordSetProvider`](WordSetProvider) should be easy to implement on types that implement this
 /// trait.
-pub trait WordProvider: HasEntropy + Debug + Clone + IntoIterator<Item = String> + Send + Sync {
+///
+/// This trait is implemented for simple collections of words such as `Vec<String>`, `&[&str]`
+/// and arrays, see the [`provider`](crate::word::provider) module.
+///
+/// # Breaking change
+///
+/// Word providers used to require `IntoIterator<Item = String>`, which collections of borrowed
+/// words can't implement. This bound was removed, so code that iterates over a generic
+/// `P: WordProvider` must now add the `IntoIterator<Item = String>` bound itself. The built-in
+/// [`WordSampler`](crate::word::WordSampler) and
+/// [`StaticWordSampler`](crate::word::StaticWordSampler) still implement it.
+pub trait WordProvider: HasEntropy + Debug + Clone + Send + Sync {
     /// Obtain a random word.
     ///
     /// This method should obtain and return a random word from the provider.
     /// The randomization must be cryptographically secure as it's used for generating passphrases.
     fn word(&self) -> String;
+
+    /// Obtain a random word using the given randomness source.
+    ///
+    /// This is used by [`Scheme::generate_with_rng`](crate::scheme::Scheme::generate_with_rng).
+    /// Providers should implement this to obtain all randomness from `rng`, which makes
+    /// generation deterministic for a deterministic source. By default this ignores `rng` and
+    /// uses [`word`](WordProvider::word).
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        let _ = rng;
+        self.word()
+    }
+
+    /// Obtain a random word by reference.
+    ///
+    /// Providers that hold their words in memory may implement this to provide a word without
+    /// allocating, which allows a [`Scheme`](crate::scheme::Scheme) to skip allocating each
+    /// passphrase word. The randomization must be cryptographically secure.
+    ///
+    /// Returns `None` if this provider doesn't support providing words by reference, which is the
+    /// default.
+    fn sample_ref(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get all words this provider may provide.
+    ///
+    /// Providers sampling a known wordlist may implement this to expose the list, which is used
+    /// for validation such as checking whether a separator occurs inside words.
+    ///
+    /// Returns `None` if the provided words are not known upfront, which is the default.
+    fn known_words(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    /// Derive a provider that only provides words accepted by the given `predicate`.
+    ///
+    /// The entropy of the derived provider is based on the number of accepted known words, or
+    /// estimated by measuring the acceptance rate of the predicate, see
+    /// [`Filter`](crate::word::provider::Filter).
+    ///
+    /// Returns [`WordFilterError::Empty`](WordFilterError::Empty) if the predicate accepts no
+    /// words.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{prelude::*, word::WordList};
+    ///
+    /// let provider = WordList::default().sampler().filter(|w| w.len() <= 5).unwrap();
+    /// assert!(provider.word().len() <= 5);
+    /// # }
+    /// ```
+    fn filter<F>(self, predicate: F) -> Result<Filter<Self, F>, WordFilterError>
+    where
+        Self: Sized,
+        F: Fn(&str) -> bool + Clone + Send + Sync,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// Derive a provider that maps each provided word using the given function.
+    ///
+    /// The entropy of the derived provider is equal to this provider, the mapping must therefore
+    /// be injective. See [`Map`](crate::word::provider::Map).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{prelude::*, word::WordList};
+    ///
+    /// let provider = WordList::default().sampler().map(|w| w.to_uppercase());
+    /// println!("Word: {}", provider.word());
+    /// # }
+    /// ```
+    fn map<F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: Fn(String) -> String + Clone + Send + Sync,
+    {
+        Map::new(self, f)
+    }
+}
+
+/// An object-safe variant of [`WordProvider`](WordProvider).
+///
+/// The [`WordProvider`](WordProvider) trait requires `Clone`, which prevents using it as trait
+/// object. This trait is implemented for all word providers, and allows a provider chosen at
+/// runtime to be boxed as `Box<dyn DynWordProvider>`. Such a box implements
+/// [`WordProvider`](WordProvider) itself, so it may be used anywhere a provider is expected, for
+/// example in a [`DynFixedWordSetProvider`](crate::component::word::DynFixedWordSetProvider).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(all(feature = "wordlist-eff-large", feature = "wordlist-eff-short"))]
+/// # {
+/// use chbs::{component::word::DynFixedWordSetProvider, prelude::*, word::WordList};
+///
+/// let short = true;
+/// let provider: Box<dyn DynWordProvider> = if short {
+///     Box::new(WordList::builtin_eff_short().sampler())
+/// } else {
+///     Box::new(WordList::builtin_eff_large().sampler())
+/// };
+///
+/// let set_provider = DynFixedWordSetProvider::new(provider, 5);
+/// assert_eq!(5, set_provider.words().len());
+/// # }
+/// ```
+pub trait DynWordProvider: HasEntropy + Debug + Send + Sync {
+    /// Obtain a random word.
+    ///
+    /// See [`WordProvider::word`](WordProvider::word), this is named differently to prevent
+    /// ambiguity with it.
+    fn dyn_word(&self) -> String;
+
+    /// Obtain a random word using the given randomness source.
+    ///
+    /// See [`WordProvider::word_with_rng`](WordProvider::word_with_rng).
+    fn dyn_word_with_rng(&self, rng: &mut dyn SecureRng) -> String;
+
+    /// Obtain a random word by reference.
+    ///
+    /// See [`WordProvider::sample_ref`](WordProvider::sample_ref).
+    fn dyn_sample_ref(&self) -> Option<&str>;
+
+    /// Get all words this provider may provide.
+    ///
+    /// See [`WordProvider::known_words`](WordProvider::known_words).
+    fn dyn_known_words(&self) -> Option<Vec<&str>>;
+
+    /// Clone this provider into a new box.
+    fn box_clone(&self) -> Box<dyn DynWordProvider>;
+}
+
+impl<P> DynWordProvider for P
+where
+    P: WordProvider + 'static,
+{
+    fn dyn_word(&self) -> String {
+        self.word()
+    }
+
+    fn dyn_word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        self.word_with_rng(rng)
+    }
+
+    fn dyn_sample_ref(&self) -> Option<&str> {
+        self.sample_ref()
+    }
+
+    fn dyn_known_words(&self) -> Option<Vec<&str>> {
+        self.known_words()
+    }
+
+    fn box_clone(&self) -> Box<dyn DynWordProvider> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn DynWordProvider> {
+    fn clone(&self) -> Self {
+        (**self).box_clone()
+    }
+}
+
+impl HasEntropy for Box<dyn DynWordProvider> {
+    fn entropy(&self) -> Entropy {
+        (**self).entropy()
+    }
+}
+
+impl WordProvider for Box<dyn DynWordProvider> {
+    fn word(&self) -> String {
+        (**self).dyn_word()
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        (**self).dyn_word_with_rng(rng)
+    }
+
+    fn sample_ref(&self) -> Option<&str> {
+        (**self).dyn_sample_ref()
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        (**self).dyn_known_words()
+    }
 }
 
 /// Something that provides sets of random words.
@@ -40,19 +249,215 @@ pub trait WordProvider: HasEntropy + Debug + Clone + IntoIterator<Item = String>
 pub trait WordSetProvider: HasEntropy + Debug + Send + Sync {
     /// Source a set of random passphrase words to use in a passphrase.
     fn words(&self) -> Vec<String>;
+
+    /// Source a set of random passphrase words using the given randomness source.
+    ///
+    /// See [`WordProvider::word_with_rng`](WordProvider::word_with_rng). By default this ignores
+    /// `rng` and uses [`words`](WordSetProvider::words).
+    fn words_with_rng(&self, rng: &mut dyn SecureRng) -> Vec<String> {
+        let _ = rng;
+        self.words()
+    }
+
+    /// Source a set of random passphrase words by reference.
+    ///
+    /// Providers that are able to provide words without allocating may implement this, allowing
+    /// a [`Scheme`](crate::scheme::Scheme) to generate passphrases with fewer allocations.
+    ///
+    /// Returns `None` if this provider doesn't support providing words by reference, which is the
+    /// default. [`words`](WordSetProvider::words) is used instead in that case.
+    fn word_refs(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    /// Source a set of random passphrase words, returning an error if a limit is exceeded.
+    ///
+    /// This is used by [`Scheme::try_generate`](crate::scheme::Scheme::try_generate). Providers
+    /// that retry obtaining words within [limits](crate::limits::GenerationLimits), such as one
+    /// sampling unique words, should implement this to return the exceeded limit instead of
+    /// panicking. By default this uses [`words`](WordSetProvider::words).
+    fn try_words(&self) -> Result<Vec<String>, LimitExceeded> {
+        Ok(self.words())
+    }
+
+    /// Source a set of random passphrase words by reference, returning an error if a limit is
+    /// exceeded.
+    ///
+    /// See [`try_words`](WordSetProvider::try_words). By default this uses
+    /// [`word_refs`](WordSetProvider::word_refs).
+    fn try_word_refs(&self) -> Result<Option<Vec<&str>>, LimitExceeded> {
+        Ok(self.word_refs())
+    }
+
+    /// Get all words this provider may use in a passphrase.
+    ///
+    /// See [`WordProvider::known_words`](WordProvider::known_words). Returns `None` if the words
+    /// are not known upfront, which is the default.
+    fn known_words(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    /// Get the number of words this provider sources for each passphrase.
+    ///
+    /// This is used to accumulate the entropy of word stylers, which make a random decision for
+    /// each word. Returns `None` if the number of words is not fixed, which is the default, in
+    /// which case word styler entropy is only accumulated once.
+    fn word_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Source a set of random passphrase words, with mutable access to this provider.
+    ///
+    /// This is used by [`Scheme::generate_mut`](crate::scheme::Scheme::generate_mut). Stateful
+    /// providers, such as one dealing words without replacement, may implement this to update
+    /// their state without interior mutability. By default this uses
+    /// [`words`](WordSetProvider::words).
+    fn words_mut(&mut self) -> Vec<String> {
+        self.words()
+    }
+
+    /// Derive a provider sourcing words from the underlying wordlist filtered by `filter`.
+    ///
+    /// Providers sampling a known wordlist may implement this, allowing a
+    /// [`Scheme`](crate::scheme::Scheme) to apply [`WordFilter`](WordFilter)s. Words should be
+    /// uniformly sampled from the filtered list, and the entropy should reflect its size. See
+    /// [`WordSampler::apply_filter`](crate::word::WordSampler::apply_filter).
+    ///
+    /// Returns [`WordFilterError::Unsupported`](WordFilterError::Unsupported) by default.
+    fn filter_words(
+        &self,
+        filter: &dyn WordFilter,
+    ) -> Result<Box<dyn WordSetProvider>, WordFilterError> {
+        let _ = filter;
+        Err(WordFilterError::Unsupported)
+    }
 }
 
 /// Something that provides logic to _style_ each passphrase word.
 /// This could be used to build a styler for word capitalization.
+///
+/// The [entropy](HasEntropy::entropy) of a word styler is the entropy it adds to a single word.
+/// A [`Scheme`](::scheme::Scheme) accumulates it for each word in the passphrase.
 pub trait WordStyler: HasEntropy + Debug + Send + Sync {
     /// Style the given `word`.
     fn style_word(&self, word: String) -> String;
+
+    /// Style the given `word` using the given randomness source.
+    ///
+    /// See [`WordProvider::word_with_rng`](WordProvider::word_with_rng). By default this ignores
+    /// `rng` and uses [`style_word`](WordStyler::style_word).
+    fn style_word_with_rng(&self, word: String, rng: &mut dyn SecureRng) -> String {
+        let _ = rng;
+        self.style_word(word)
+    }
+
+    /// Style the given, possibly borrowed, `word`.
+    ///
+    /// Stylers that don't always modify the word may implement this to only allocate when the
+    /// word is actually changed. By default this takes ownership of the word and uses
+    /// [`style_word`](WordStyler::style_word).
+    fn style_word_cow<'a>(&self, word: Cow<'a, str>) -> Cow<'a, str> {
+        Cow::Owned(self.style_word(word.into_owned()))
+    }
+
+    /// Style the given `word`, with mutable access to this styler.
+    ///
+    /// See [`WordSetProvider::words_mut`](WordSetProvider::words_mut). By default this uses
+    /// [`style_word`](WordStyler::style_word).
+    fn style_word_mut(&mut self, word: String) -> String {
+        self.style_word(word)
+    }
+
+    /// The entropy this styler adds to the given `word`.
+    ///
+    /// Stylers adding entropy depending on the word, such as on its length, may implement this.
+    /// [`Scheme::validate`](crate::scheme::Scheme::validate) uses it to detect stylers crediting
+    /// more entropy than some known words allow. By default this is the
+    /// [entropy](HasEntropy::entropy) of this styler, regardless of the word.
+    fn word_entropy(&self, word: &str) -> Entropy {
+        let _ = word;
+        self.entropy()
+    }
 }
 
 /// Something that provides logic to combine a list of passphrase words into a passphrase.
 pub trait PhraseBuilder: HasEntropy + Debug + Send + Sync {
     /// Build the passphrase from the given words, and combine them in one final passphrase.
     fn build_phrase(&self, words: Vec<String>) -> String;
+
+    /// Build the passphrase from the given words using the given randomness source.
+    ///
+    /// See [`WordProvider::word_with_rng`](WordProvider::word_with_rng). By default this ignores
+    /// `rng` and uses [`build_phrase`](PhraseBuilder::build_phrase).
+    fn build_phrase_with_rng(&self, words: Vec<String>, rng: &mut dyn SecureRng) -> String {
+        let _ = rng;
+        self.build_phrase(words)
+    }
+
+    /// Build the passphrase from the given, possibly borrowed, words.
+    ///
+    /// Builders may implement this to build a phrase without taking ownership of each word.
+    /// By default this clones all words and uses [`build_phrase`](PhraseBuilder::build_phrase).
+    fn build_phrase_ref(&self, words: &[Cow<str>]) -> String {
+        self.build_phrase(words.iter().map(|w| w.clone().into_owned()).collect())
+    }
+
+    /// Get the separators this builder may place between words.
+    ///
+    /// This is used for validation, such as checking whether a separator occurs inside words.
+    /// Returns an empty list by default.
+    fn separators(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Split a passphrase built by this builder back into its words.
+    ///
+    /// `count` is the expected number of words, if known. By default this splits on the first of
+    /// the [separators](PhraseBuilder::separators) yielding `count` words, or yielding multiple
+    /// words if `count` is unknown. Returns `None` if the phrase can't be split.
+    ///
+    /// Splitting is ambiguous if a separator occurs inside words, see
+    /// [`check_separator`](crate::validate::check_separator).
+    fn split_phrase(&self, phrase: &str, count: Option<usize>) -> Option<Vec<String>> {
+        if count == Some(1) {
+            return Some(vec![phrase.to_owned()]);
+        }
+
+        let words = self
+            .separators()
+            .into_iter()
+            .filter(|separator| !separator.is_empty())
+            .map(|separator| {
+                phrase
+                    .split(separator)
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .find(|words| match count {
+                Some(count) => words.len() == count,
+                None => words.len() > 1,
+            });
+        match count {
+            Some(_) => words,
+            None => Some(words.unwrap_or_else(|| vec![phrase.to_owned()])),
+        }
+    }
+
+    /// Get the character set this builder restricts passphrases to.
+    ///
+    /// This is used for validation, such as checking whether stylers produce characters outside
+    /// it. Returns `None` by default, meaning passphrases are not restricted.
+    fn charset(&self) -> Option<Charset> {
+        None
+    }
+
+    /// Build the passphrase from the given words, with mutable access to this builder.
+    ///
+    /// See [`WordSetProvider::words_mut`](WordSetProvider::words_mut). By default this uses
+    /// [`build_phrase`](PhraseBuilder::build_phrase).
+    fn build_phrase_mut(&mut self, words: Vec<String>) -> String {
+        self.build_phrase(words)
+    }
 }
 
 /// Something that provides logic to _style_ a passphrase as a whole.
@@ -60,4 +465,75 @@ pub trait PhraseStyler: HasEntropy + Debug + Send + Sync {
     /// Style the given `phrase` as a whole.
     /// The styled passphrase is returned.
     fn style_phrase(&self, phrase: String) -> String;
+
+    /// Style the given `phrase` as a whole using the given randomness source.
+    ///
+    /// See [`WordProvider::word_with_rng`](WordProvider::word_with_rng). By default this ignores
+    /// `rng` and uses [`style_phrase`](PhraseStyler::style_phrase).
+    fn style_phrase_with_rng(&self, phrase: String, rng: &mut dyn SecureRng) -> String {
+        let _ = rng;
+        self.style_phrase(phrase)
+    }
+
+    /// Style the given `phrase` as a whole, with mutable access to this styler.
+    ///
+    /// See [`WordSetProvider::words_mut`](WordSetProvider::words_mut). By default this uses
+    /// [`style_phrase`](PhraseStyler::style_phrase).
+    fn style_phrase_mut(&mut self, phrase: String) -> String {
+        self.style_phrase(phrase)
+    }
+}
+
+/// Something that provides logic to validate a passphrase, rejecting those not meeting
+/// requirements.
+///
+/// A [`Scheme`](::scheme::Scheme) regenerates passphrases rejected by any of its validators, up
+/// to a [configured](::scheme::SchemeBuilder::limits) number of attempts. Rejecting
+/// passphrases lowers their entropy, as rejected passphrases are never issued, see
+/// [`SchemeBuilder::acceptance_samples`](::scheme::SchemeBuilder::acceptance_samples).
+pub trait PhraseValidator: Debug + Send + Sync {
+    /// Validate the given `phrase`.
+    ///
+    /// Returns `Err` with a human readable reason if the phrase is rejected.
+    fn validate_phrase(&self, phrase: &str) -> Result<(), String>;
+}
+
+/// Something that provides logic to veto or replace individual words of a wordlist.
+///
+/// Word filters run between the [`WordSetProvider`](WordSetProvider) and the
+/// [`WordStyler`](WordStyler)s of a [`Scheme`](::scheme::Scheme), for example to exclude
+/// profanity or short words. Rather than rejecting generated passphrases, a filter is applied to
+/// the underlying wordlist once when the scheme is built, see
+/// [`WordSetProvider::filter_words`](WordSetProvider::filter_words). Words are then uniformly
+/// sampled from the filtered list, which keeps the entropy accounting correct.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use chbs::prelude::*;
+///
+/// /// A filter spelling out the digit words may contain.
+/// #[derive(Debug)]
+/// struct SpellDigits;
+///
+/// impl WordFilter for SpellDigits {
+///     fn filter_word<'a>(&self, word: &'a str) -> Option<Cow<'a, str>> {
+///         match word {
+///             "1" => Some("one".into()),
+///             w if w.chars().any(|c| c.is_ascii_digit()) => None,
+///             w => Some(w.into()),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Some("one".into()), SpellDigits.filter_word("1"));
+/// assert_eq!(None, SpellDigits.filter_word("2"));
+/// ```
+pub trait WordFilter: Debug + Send + Sync {
+    /// Filter the given `word`.
+    ///
+    /// Returns `None` to veto the word, or the word to use in its place, which may be the word
+    /// itself.
+    fn filter_word<'a>(&self, word: &'a str) -> Option<Cow<'a, str>>;
 }
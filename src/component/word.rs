@@ -5,21 +5,34 @@
 //!
 //! - [`WordSetProvider`](super::traits::WordSetProvider)
 //! - [`WordStyler`](super::traits::WordStyler)
+//! - [`WordFilter`](super::traits::WordFilter)
 //!
 //! Most of these components are used by configuration strucutres provided by this crate, see
 //! the [`config`](::config) module. You may of course implement these components in your own
 //! configuration structures and [`Scheme`](::scheme::Scheme) definitions.
 
-use rand::thread_rng;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
 
+use rand::seq::SliceRandom;
+use thiserror::Error;
+
+use crate::component::grapheme::{grapheme_count, grapheme_offset, uppercase_first_grapheme};
 use crate::entropy::Entropy;
+use crate::limits::{GenerationLimits, LimitExceeded};
 use crate::prelude::*;
 use crate::probability::Probability;
+use crate::rng::{default_rng, gen_index};
+use crate::word::{apply_word_filter, WordSampler};
 
 /// A generator providing a fixed number of passphrase words.
 ///
 /// This generator provides a set of passphrase words for passphrase generation with a fixed number
 /// of words based on the configuration.
+///
+/// Words are sampled independently by default, so a passphrase may repeat a word. Use
+/// [`with_unique`](FixedWordSetProvider::with_unique) to never repeat words within a passphrase.
 #[derive(Debug)]
 pub struct FixedWordSetProvider<P>
 where
@@ -30,6 +43,12 @@ where
 
     /// The number of passphrase words to obtain.
     words: usize,
+
+    /// Whether words must be unique within a set.
+    unique: bool,
+
+    /// The limits on attempts to obtain each unique word.
+    limits: GenerationLimits,
 }
 
 impl<P> FixedWordSetProvider<P>
@@ -51,7 +70,92 @@ where
             panic!("cannot construct FixedWordSetProvider that obtains zero words");
         }
 
-        Self { provider, words }
+        Self {
+            provider,
+            words,
+            unique: false,
+            limits: GenerationLimits::default(),
+        }
+    }
+
+    /// Set whether words must be unique within a set.
+    ///
+    /// Duplicate words are rejected and sampled again. This lowers the entropy, as fewer words
+    /// remain for each next word: `log2(n * (n - 1) * ...)` instead of `log2(n^k)` for `n` words.
+    /// For providers not sampling uniformly, `n` is estimated from the entropy per word.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provider knows its words, and has fewer distinct words than the number of
+    /// words to obtain. Obtaining words panics if no unique word is obtained within the
+    /// [limits](FixedWordSetProvider::with_limits), such as for a provider repeating the same
+    /// word.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::{component::word::FixedWordSetProvider, entropy::Entropy, prelude::*};
+    ///
+    /// let provider = FixedWordSetProvider::new(["correct", "horse", "battery"], 3).with_unique(true);
+    /// assert_eq!(Entropy::from_permutations(3, 3), provider.entropy());
+    ///
+    /// let mut words = provider.words();
+    /// words.sort();
+    /// assert_eq!(words, vec!["battery", "correct", "horse"]);
+    /// ```
+    pub fn with_unique(mut self, unique: bool) -> Self {
+        if unique {
+            if let Some(known) = self.provider.known_words() {
+                let distinct = known.into_iter().collect::<HashSet<_>>().len();
+                if distinct < self.words {
+                    panic!(
+                        "cannot sample {} unique words from {} distinct words",
+                        self.words, distinct,
+                    );
+                }
+            }
+        }
+
+        self.unique = unique;
+        self
+    }
+
+    /// Set the limits on attempts to obtain each word, if words must be unique.
+    ///
+    /// Defaults to [`GenerationLimits::default`](GenerationLimits::default).
+    pub fn with_limits(mut self, limits: GenerationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Obtain a set of words using the given function to obtain a single word.
+    ///
+    /// Duplicate words are obtained again if words must be unique, within the limits. `None` is
+    /// returned as soon as `word` returns `None`.
+    ///
+    /// An error is returned if no unique word is obtained within the limits.
+    fn collect<T, F>(&self, mut word: F) -> Result<Option<Vec<T>>, LimitExceeded>
+    where
+        T: PartialEq,
+        F: FnMut() -> Option<T>,
+    {
+        let mut words = Vec::with_capacity(self.words);
+        while words.len() < self.words {
+            let mut attempts = self.limits.start();
+            loop {
+                attempts.next()?;
+
+                let next = match word() {
+                    Some(next) => next,
+                    None => return Ok(None),
+                };
+                if !self.unique || !words.contains(&next) {
+                    words.push(next);
+                    break;
+                }
+            }
+        }
+        Ok(Some(words))
     }
 }
 
@@ -60,23 +164,516 @@ where
     P: WordProvider,
 {
     fn entropy(&self) -> Entropy {
-        self.provider.entropy() * self.words as f64
+        if !self.unique {
+            return self.provider.entropy() * self.words as f64;
+        }
+
+        // Each next unique word is chosen from one word less
+        let n = 2f64.powf(self.provider.entropy().bits());
+        Entropy::from_bits(
+            (0..self.words)
+                .map(|i| (n - i as f64).max(1.0).log2())
+                .sum::<f64>(),
+        )
     }
 }
 
 impl<P> WordSetProvider for FixedWordSetProvider<P>
 where
     P: WordProvider,
+{
+    /// # Panics
+    ///
+    /// Panics if words must be unique, and no unique word is obtained within the limits. Use
+    /// [`try_words`](WordSetProvider::try_words) to handle this instead.
+    fn words(&self) -> Vec<String> {
+        self.try_words()
+            .unwrap_or_else(|limit| unique_word_exhausted(limit))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if words must be unique, and no unique word is obtained within the limits.
+    fn words_with_rng(&self, rng: &mut dyn SecureRng) -> Vec<String> {
+        self.collect(|| Some(self.provider.word_with_rng(rng)))
+            .unwrap_or_else(|limit| unique_word_exhausted(limit))
+            .expect("word is always obtained")
+    }
+
+    /// # Panics
+    ///
+    /// Panics if words must be unique, and no unique word is obtained within the limits. Use
+    /// [`try_word_refs`](WordSetProvider::try_word_refs) to handle this instead.
+    fn word_refs(&self) -> Option<Vec<&str>> {
+        self.try_word_refs()
+            .unwrap_or_else(|limit| unique_word_exhausted(limit))
+    }
+
+    fn try_words(&self) -> Result<Vec<String>, LimitExceeded> {
+        Ok(self
+            .collect(|| Some(self.provider.word()))?
+            .expect("word is always obtained"))
+    }
+
+    fn try_word_refs(&self) -> Result<Option<Vec<&str>>, LimitExceeded> {
+        self.collect(|| self.provider.sample_ref())
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        self.provider.known_words()
+    }
+
+    fn word_count(&self) -> Option<usize> {
+        Some(self.words)
+    }
+
+    /// Words are uniformly sampled from the filtered known words of the provider.
+    fn filter_words(
+        &self,
+        filter: &dyn WordFilter,
+    ) -> Result<Box<dyn WordSetProvider>, WordFilterError> {
+        let known = self
+            .provider
+            .known_words()
+            .ok_or(WordFilterError::Unsupported)?;
+        let words = apply_word_filter(known, filter);
+        if words.is_empty() {
+            return Err(WordFilterError::Empty);
+        }
+        Ok(Box::new(
+            FixedWordSetProvider::new(WordSampler::new(words), self.words)
+                .with_unique(self.unique)
+                .with_limits(self.limits),
+        ))
+    }
+}
+
+/// Panic as no unique passphrase word was obtained within the given limit.
+fn unique_word_exhausted(limit: LimitExceeded) -> ! {
+    panic!("cannot obtain unique passphrase word {}", limit)
+}
+
+/// A generator providing a fixed number of passphrase words, with batched randomness.
+///
+/// This is like a [`FixedWordSetProvider`](FixedWordSetProvider) over a
+/// [`WordSampler`](crate::word::WordSampler), but draws the randomness for all words at once, see
+/// [`WordSampler::sample_batch`](crate::word::WordSampler::sample_batch). This minimizes the
+/// number of draws from the randomness source where obtaining randomness is expensive, while
+/// keeping the same uniform distribution and entropy.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{
+///     component::word::BatchedWordSetProvider,
+///     prelude::*,
+///     word::{SamplerRng, WordList},
+/// };
+///
+/// let sampler = WordList::default()
+///     .sampler_builder()
+///     .rng(SamplerRng::Os)
+///     .build()
+///     .unwrap();
+/// let provider = BatchedWordSetProvider::new(sampler, 5);
+/// assert_eq!(5, provider.words().len());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BatchedWordSetProvider {
+    /// The sampler to obtain words from.
+    sampler: WordSampler,
+
+    /// The number of words to obtain.
+    words: usize,
+}
+
+impl BatchedWordSetProvider {
+    /// Construct a word set provider obtaining `words` words from the given sampler.
+    ///
+    /// # Panic
+    ///
+    /// `words` must be at least 1.
+    pub fn new(sampler: WordSampler, words: usize) -> Self {
+        if words == 0 {
+            panic!("cannot construct BatchedWordSetProvider that obtains zero words");
+        }
+
+        Self { sampler, words }
+    }
+}
+
+impl HasEntropy for BatchedWordSetProvider {
+    fn entropy(&self) -> Entropy {
+        self.sampler.entropy() * self.words as f64
+    }
+}
+
+impl WordSetProvider for BatchedWordSetProvider {
+    fn words(&self) -> Vec<String> {
+        self.sampler
+            .sample_batch(self.words)
+            .into_iter()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn words_with_rng(&self, rng: &mut dyn SecureRng) -> Vec<String> {
+        self.sampler
+            .sample_batch_with_rng(self.words, rng)
+            .into_iter()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn word_refs(&self) -> Option<Vec<&str>> {
+        Some(self.sampler.sample_batch(self.words))
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        self.sampler.known_words()
+    }
+
+    fn word_count(&self) -> Option<usize> {
+        Some(self.words)
+    }
+
+    fn filter_words(
+        &self,
+        filter: &dyn WordFilter,
+    ) -> Result<Box<dyn WordSetProvider>, WordFilterError> {
+        let sampler = self
+            .sampler
+            .apply_filter(filter)
+            .ok_or(WordFilterError::Empty)?;
+        Ok(Box::new(BatchedWordSetProvider::new(sampler, self.words)))
+    }
+}
+
+/// A [`FixedWordSetProvider`](FixedWordSetProvider) using a word provider chosen at runtime.
+///
+/// This allows switching between wordlists or providers based on user settings without using
+/// generics everywhere, see [`DynWordProvider`](DynWordProvider).
+pub type DynFixedWordSetProvider = FixedWordSetProvider<Box<dyn DynWordProvider>>;
+
+/// A generator providing one word from a separate provider for each position.
+///
+/// Each passphrase word is sampled from the provider for its position, for example an adjective
+/// followed by an animal. The entropy is the sum of the entropy of all providers.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::word::PositionalWordSetProvider, prelude::*};
+///
+/// let provider = PositionalWordSetProvider::new(vec![
+///     Box::new(["brave", "calm"]),
+///     Box::new(["otter", "panda"]),
+/// ]);
+/// let words = provider.words();
+/// assert!(words[1] == "otter" || words[1] == "panda");
+/// assert_eq!(2.0, provider.entropy().bits());
+/// ```
+#[derive(Debug)]
+pub struct PositionalWordSetProvider {
+    /// The word provider for each position.
+    providers: Vec<Box<dyn DynWordProvider>>,
+}
+
+impl PositionalWordSetProvider {
+    /// Construct a word set provider with the given word provider for each position.
+    ///
+    /// # Panic
+    ///
+    /// `providers` must not be empty.
+    pub fn new(providers: Vec<Box<dyn DynWordProvider>>) -> Self {
+        if providers.is_empty() {
+            panic!("cannot construct PositionalWordSetProvider without providers");
+        }
+
+        Self { providers }
+    }
+}
+
+impl HasEntropy for PositionalWordSetProvider {
+    fn entropy(&self) -> Entropy {
+        self.providers.iter().map(|p| p.entropy()).sum()
+    }
+}
+
+impl WordSetProvider for PositionalWordSetProvider {
+    fn words(&self) -> Vec<String> {
+        self.providers.iter().map(|p| p.dyn_word()).collect()
+    }
+
+    fn words_with_rng(&self, rng: &mut dyn SecureRng) -> Vec<String> {
+        self.providers
+            .iter()
+            .map(|p| p.dyn_word_with_rng(rng))
+            .collect()
+    }
+
+    fn word_refs(&self) -> Option<Vec<&str>> {
+        self.providers.iter().map(|p| p.dyn_sample_ref()).collect()
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        let mut words = Vec::new();
+        for provider in &self.providers {
+            words.extend(provider.dyn_known_words()?);
+        }
+        Some(words)
+    }
+
+    fn word_count(&self) -> Option<usize> {
+        Some(self.providers.len())
+    }
+
+    /// The provider of each position is replaced by a uniform sampler of its filtered words.
+    fn filter_words(
+        &self,
+        filter: &dyn WordFilter,
+    ) -> Result<Box<dyn WordSetProvider>, WordFilterError> {
+        let providers = self
+            .providers
+            .iter()
+            .map(|provider| {
+                let known = provider
+                    .dyn_known_words()
+                    .ok_or(WordFilterError::Unsupported)?;
+                let words = apply_word_filter(known, filter);
+                if words.is_empty() {
+                    return Err(WordFilterError::Empty);
+                }
+                Ok(Box::new(WordSampler::new(words)) as Box<dyn DynWordProvider>)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Box::new(PositionalWordSetProvider::new(providers)))
+    }
+}
+
+/// The pool of spelled-out numbers used by [`NumberWordInjector`](NumberWordInjector).
+///
+/// This contains 32 (2<sup>5</sup>) numbers that are a single word, providing 5 bits of entropy
+/// when uniformly sampled.
+pub const NUMBER_WORDS: [&str; 32] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+    "thirty",
+    "forty",
+    "fifty",
+    "sixty",
+    "seventy",
+    "eighty",
+    "ninety",
+    "hundred",
+    "thousand",
+    "million",
+    "billion",
+];
+
+/// A word set provider injecting spelled-out numbers as extra passphrase words.
+///
+/// This wraps another word set provider, and inserts a fixed number of words from the
+/// [`NUMBER_WORDS`](NUMBER_WORDS) pool at random positions in each set. This is a memorable
+/// alternative to numeric suffixes, such as `correct horse forty battery staple`.
+///
+/// Each injected number adds `log2(32) = 5` bits of entropy. Their random positions add
+/// `log2(C(n + k, k))` bits, for `k` numbers injected in `n` words, if the number of words of the
+/// wrapped provider is known.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{component::word::{FixedWordSetProvider, NumberWordInjector}, prelude::*};
+/// use chbs::word::WordList;
+///
+/// let provider = FixedWordSetProvider::new(WordList::default().sampler(), 4);
+/// let injector = NumberWordInjector::new(provider, 1);
+/// assert_eq!(5, injector.words().len());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NumberWordInjector<S>
+where
+    S: WordSetProvider,
+{
+    /// The word set provider to inject numbers into.
+    provider: S,
+
+    /// The number of number words to inject.
+    count: usize,
+}
+
+impl<S> NumberWordInjector<S>
+where
+    S: WordSetProvider,
+{
+    /// Construct a number word injector, injecting `count` numbers into sets of the given
+    /// provider.
+    pub fn new(provider: S, count: usize) -> Self {
+        Self { provider, count }
+    }
+}
+
+impl<S> HasEntropy for NumberWordInjector<S>
+where
+    S: WordSetProvider,
+{
+    fn entropy(&self) -> Entropy {
+        let numbers = Entropy::from_real(NUMBER_WORDS.len() as f64) * self.count as f64;
+
+        // Entropy of positions, the number of ways to choose the number positions
+        let positions = match self.provider.word_count() {
+            Some(n) => Entropy::from_combinations((n + self.count) as u64, self.count as u64),
+            None => Entropy::zero(),
+        };
+
+        self.provider.entropy() + numbers + positions
+    }
+}
+
+impl<S> WordSetProvider for NumberWordInjector<S>
+where
+    S: WordSetProvider,
 {
     fn words(&self) -> Vec<String> {
-        let mut res: Vec<String> = vec![];
-        for _ in 0..self.words {
-            res.push(self.provider.word());
+        self.words_with_rng(&mut default_rng())
+    }
+
+    fn words_with_rng(&self, rng: &mut dyn SecureRng) -> Vec<String> {
+        let mut words = self.provider.words_with_rng(rng);
+        for _ in 0..self.count {
+            let number = NUMBER_WORDS.choose(rng).unwrap();
+            let position = gen_index(rng, words.len() + 1);
+            words.insert(position, (*number).to_owned());
+        }
+        words
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        let mut words = self.provider.known_words()?;
+        words.extend_from_slice(&NUMBER_WORDS);
+        Some(words)
+    }
+
+    fn word_count(&self) -> Option<usize> {
+        self.provider.word_count().map(|n| n + self.count)
+    }
+}
+
+/// A word filter only accepting words with a length in the given range.
+///
+/// Length is counted in characters. Short words are easier to guess in isolation, and long words
+/// are harder to type.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::word::WordLengthFilter, prelude::*};
+///
+/// let filter = WordLengthFilter::new(4..=8);
+/// assert!(filter.filter_word("horse").is_some());
+/// assert!(filter.filter_word("cat").is_none());
+/// ```
+#[derive(Debug)]
+pub struct WordLengthFilter {
+    /// The accepted range of word lengths.
+    range: RangeInclusive<usize>,
+}
+
+impl WordLengthFilter {
+    /// Construct a filter accepting words with a length in the given `range`.
+    pub fn new(range: RangeInclusive<usize>) -> Self {
+        Self { range }
+    }
+}
+
+impl WordFilter for WordLengthFilter {
+    fn filter_word<'a>(&self, word: &'a str) -> Option<Cow<'a, str>> {
+        if self.range.contains(&word.chars().count()) {
+            Some(Cow::Borrowed(word))
+        } else {
+            None
         }
-        res
     }
 }
 
+/// A word filter vetoing blocked words, such as profanity.
+///
+/// Words are matched case-insensitively, and must match a blocked word as a whole.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::word::BlockedWordFilter, prelude::*};
+///
+/// let filter = BlockedWordFilter::new(vec!["Darn".into()]);
+/// assert!(filter.filter_word("darn").is_none());
+/// assert!(filter.filter_word("horse").is_some());
+/// ```
+#[derive(Debug)]
+pub struct BlockedWordFilter {
+    /// The lowercase blocked words.
+    blocked: HashSet<String>,
+}
+
+impl BlockedWordFilter {
+    /// Construct a filter vetoing any of the `blocked` words.
+    pub fn new(blocked: Vec<String>) -> Self {
+        Self {
+            blocked: blocked.into_iter().map(|w| w.to_lowercase()).collect(),
+        }
+    }
+}
+
+impl WordFilter for BlockedWordFilter {
+    fn filter_word<'a>(&self, word: &'a str) -> Option<Cow<'a, str>> {
+        if self.blocked.contains(&word.to_lowercase()) {
+            None
+        } else {
+            Some(Cow::Borrowed(word))
+        }
+    }
+}
+
+/// An error applying a [`WordFilter`](WordFilter) to a word set provider.
+///
+/// See [`WordSetProvider::filter_words`](WordSetProvider::filter_words).
+#[derive(Error, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WordFilterError {
+    /// The word set provider doesn't support filtering, as its words are not known upfront.
+    #[error("word set provider doesn't support word filters")]
+    Unsupported,
+
+    /// The filter vetoes all words.
+    #[error("word filter vetoes all words")]
+    Empty,
+}
+
 /// A word styler to capitalize passphrase words.
 ///
 /// This word styler component capitalizes words for a passphrase in different styles depending
@@ -102,33 +699,32 @@ impl WordCapitalizer {
 }
 
 impl HasEntropy for WordCapitalizer {
+    /// The entropy this styler adds to a single word.
+    ///
+    /// Each word ends up in one of three styles: fully capitalized, first character capitalized,
+    /// or unchanged. This is the Shannon entropy of that distribution.
     fn entropy(&self) -> Entropy {
         // For capitalizing all, capitalizing the first character doesn't change anything
-        if let Probability::Always = self.all {
-            Entropy::zero()
-        } else {
-            self.first.entropy() + self.all.entropy()
-        }
+        let all = self.all.value();
+        let first = (1.0 - all) * self.first.value();
+        let none = (1.0 - all) * (1.0 - self.first.value());
+        Entropy::from_distribution(vec![all, first, none])
     }
 }
 
 impl WordStyler for WordCapitalizer {
-    fn style_word(&self, mut word: String) -> String {
+    fn style_word(&self, word: String) -> String {
+        self.style_word_with_rng(word, &mut default_rng())
+    }
+
+    fn style_word_with_rng(&self, mut word: String, mut rng: &mut dyn SecureRng) -> String {
         if word.is_empty() {
             return word;
         }
 
-        let mut rng = thread_rng();
-
         // Capitalize the first character
         if self.first.gen_bool(&mut rng) {
-            let first = word
-                .chars()
-                .map(|c| c.to_uppercase().to_string())
-                .next()
-                .unwrap_or_else(String::new);
-            let rest: String = word.chars().skip(1).collect();
-            word = first + &rest;
+            word = uppercase_first_grapheme(&word);
         }
 
         // Capitalize whole words
@@ -138,4 +734,119 @@ impl WordStyler for WordCapitalizer {
 
         word
     }
+
+    fn style_word_cow<'a>(&self, word: Cow<'a, str>) -> Cow<'a, str> {
+        // Only take ownership if the word may be changed
+        match (self.first, self.all) {
+            (Probability::Never, Probability::Never) => word,
+            _ => Cow::Owned(self.style_word(word.into_owned())),
+        }
+    }
+}
+
+/// A word styler capitalizing a random interior character of passphrase words.
+///
+/// Some legacy password policies require an uppercase letter that is not the first character.
+/// With the configured probability, this uppercases one randomly selected character of each word
+/// other than the first. Words of a single character are left unchanged.
+///
+/// # Entropy
+///
+/// Both the choice to capitalize and the selected position add entropy. The number of positions
+/// depends on the word length, so entropy is credited for the configured minimum word length
+/// only, which must be the length of the shortest word in the wordlist, see
+/// [`Scheme::validate`](crate::scheme::Scheme::validate). Words are assumed to consist of
+/// lowercase letters, as uppercasing other characters has no effect. Place this styler before any
+/// styler capitalizing whole words.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{component::word::InteriorCapitalizer, prelude::*, probability::Probability};
+///
+/// let styler = InteriorCapitalizer::new(Probability::Always, 5);
+/// let word = styler.style_word("horse".into());
+/// assert!(word.starts_with('h') && word.chars().filter(|c| c.is_uppercase()).count() == 1);
+/// assert_eq!(2.0, styler.entropy().bits());
+/// ```
+#[derive(Debug)]
+pub struct InteriorCapitalizer {
+    /// The probability to capitalize an interior character of a word.
+    probability: Probability,
+
+    /// The length of the shortest word in graphemes, to credit position entropy for.
+    min_word_length: usize,
+}
+
+impl InteriorCapitalizer {
+    /// Construct an interior capitalizer.
+    ///
+    /// An interior character is capitalized with the given `probability`. Entropy of the
+    /// selected position is credited for words of `min_word_length` graphemes.
+    pub fn new(probability: Probability, min_word_length: usize) -> Self {
+        Self {
+            probability,
+            min_word_length,
+        }
+    }
+
+    /// The entropy this styler adds to a single word of the given length in graphemes.
+    ///
+    /// A word is either unchanged, or has one of its interior positions capitalized with equal
+    /// probability. This is the Shannon entropy of that distribution.
+    fn length_entropy(&self, length: usize) -> Entropy {
+        let positions = length.saturating_sub(1);
+        if positions == 0 {
+            return Entropy::zero();
+        }
+
+        let capitalize = self.probability.value();
+        let position = capitalize / positions as f64;
+        let mut distribution = vec![position; positions];
+        distribution.push(1.0 - capitalize);
+        Entropy::from_distribution(distribution)
+    }
+}
+
+impl HasEntropy for InteriorCapitalizer {
+    /// The entropy this styler adds to a single word of the minimum length.
+    fn entropy(&self) -> Entropy {
+        self.length_entropy(self.min_word_length)
+    }
+}
+
+impl WordStyler for InteriorCapitalizer {
+    fn style_word(&self, word: String) -> String {
+        self.style_word_with_rng(word, &mut default_rng())
+    }
+
+    fn style_word_with_rng(&self, word: String, mut rng: &mut dyn SecureRng) -> String {
+        let count = grapheme_count(&word);
+        if count < 2 || !self.probability.gen_bool(&mut rng) {
+            return word;
+        }
+
+        let index = 1 + gen_index(rng, count - 1);
+        let (start, end) = (
+            grapheme_offset(&word, index),
+            grapheme_offset(&word, index + 1),
+        );
+        let mut styled = String::with_capacity(word.len());
+        styled.push_str(&word[..start]);
+        styled.push_str(&word[start..end].to_uppercase());
+        styled.push_str(&word[end..]);
+        styled
+    }
+
+    fn style_word_cow<'a>(&self, word: Cow<'a, str>) -> Cow<'a, str> {
+        // Only take ownership if the word may be changed
+        match self.probability {
+            Probability::Never => word,
+            _ => Cow::Owned(self.style_word(word.into_owned())),
+        }
+    }
+
+    fn word_entropy(&self, word: &str) -> Entropy {
+        self.length_entropy(grapheme_count(word))
+    }
 }
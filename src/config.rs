@@ -8,16 +8,27 @@
 //! builder to use if you prefer to use the builder pattern.
 //!
 //! The most basic configuration structure provides is [`BasicConfig`](BasicConfig), see it's
-//! documentation for information on how to use it and for some examples.
+//! documentation for information on how to use it and for some examples. For memorable
+//! identifiers built from themed words, such as `brave-teal-otter`, see
+//! [`ThemedConfig`](ThemedConfig).
+//!
+//! To find a configuration reaching a strength target within length limits, see [`fit`](fit).
+
+use thiserror::Error;
 
 use crate::component::{
     phrase::BasicPhraseBuilder,
-    word::{FixedWordSetProvider, WordCapitalizer},
+    word::{FixedWordSetProvider, PositionalWordSetProvider, WordCapitalizer},
 };
+use crate::entropy::Entropy;
+use crate::policy::{Constraints, Policy};
 use crate::prelude::*;
 use crate::probability::Probability;
 use crate::scheme::{Scheme, SchemeBuilder};
-use crate::word::{WordList, WordSampler};
+use crate::validate::{check_separator, Warning};
+#[cfg(feature = "wordlist-eff-large")]
+use crate::word::WordList;
+use crate::word::{theme::Theme, WordSampler};
 
 use super::{DEFAULT_SEPARATOR, DEFAULT_WORDS};
 
@@ -33,17 +44,32 @@ use super::{DEFAULT_SEPARATOR, DEFAULT_WORDS};
 /// generate a passphrase.
 ///
 /// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
 /// use chbs::{config::BasicConfig, prelude::*};
 ///
 /// // Define the configuration
-/// let mut config = BasicConfig::default();
-/// config.separator = "-".into();
+/// let config = BasicConfig::default().with_separator("-");
 ///
 /// // Build the scheme for generation
 /// let mut scheme = config.to_scheme();
 ///
 /// // Generate and output
 /// println!("Passphrase: {}", scheme.generate());
+/// # }
+/// ```
+///
+/// This struct is non-exhaustive, as fields may be added in future releases. Use
+/// [`new`](BasicConfig::new) or [`default`](BasicConfig::default) along with the `with_*` setters
+/// to construct it:
+///
+/// ```rust
+/// use chbs::{config::BasicConfig, prelude::*, probability::Probability};
+///
+/// let config = BasicConfig::new(["correct", "horse", "battery", "staple"])
+///     .with_words(4)
+///     .with_capitalize_first(Probability::Never);
+/// println!("Passphrase: {}", config.to_scheme().generate());
 /// ```
 ///
 /// Or use the [`BasicConfigBuilder`](BasicConfigBuilder) instead for a builder pattern:
@@ -60,6 +86,8 @@ use super::{DEFAULT_SEPARATOR, DEFAULT_WORDS};
 /// ```
 #[derive(Builder, Clone, Debug)]
 #[builder(setter(into))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct BasicConfig<P>
 where
     P: WordProvider,
@@ -78,21 +106,126 @@ where
 
     /// Whether to capitalize whole words.
     pub capitalize_words: Probability,
+
+    /// Whether words must be unique within a passphrase.
+    ///
+    /// This lowers the entropy, see
+    /// [`FixedWordSetProvider::with_unique`](FixedWordSetProvider::with_unique).
+    #[builder(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unique_words: bool,
 }
 
-impl Default for BasicConfig<WordSampler> {
-    /// Build a default basic configuration instance.
+impl<P> BasicConfig<P>
+where
+    P: WordProvider,
+{
+    /// Build a basic configuration using the given word provider.
     ///
-    /// This configuration uses the defaul wordlist as word provider for generating passphrases.
-    fn default() -> BasicConfig<WordSampler> {
+    /// All other options are set to their [default](BasicConfig::default).
+    pub fn new(word_provider: P) -> Self {
         BasicConfig {
             words: DEFAULT_WORDS,
-            word_provider: WordList::default().sampler(),
+            word_provider,
             separator: DEFAULT_SEPARATOR.into(),
             capitalize_first: Probability::half(),
             capitalize_words: Probability::Never,
+            unique_words: false,
         }
     }
+
+    /// Set the number of words the passphrase will consist of.
+    pub fn with_words(mut self, words: usize) -> Self {
+        self.words = words;
+        self
+    }
+
+    /// Set the provider random passphrase words are obtained from.
+    pub fn with_word_provider<Q: WordProvider>(self, word_provider: Q) -> BasicConfig<Q> {
+        BasicConfig {
+            words: self.words,
+            word_provider,
+            separator: self.separator,
+            capitalize_first: self.capitalize_first,
+            capitalize_words: self.capitalize_words,
+            unique_words: self.unique_words,
+        }
+    }
+
+    /// Set the separator string to use between passphrase words.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Set whether to capitalize the first characters of words.
+    pub fn with_capitalize_first<T: Into<Probability>>(mut self, capitalize_first: T) -> Self {
+        self.capitalize_first = capitalize_first.into();
+        self
+    }
+
+    /// Set whether to capitalize whole words.
+    pub fn with_capitalize_words<T: Into<Probability>>(mut self, capitalize_words: T) -> Self {
+        self.capitalize_words = capitalize_words.into();
+        self
+    }
+
+    /// Set whether words must be unique within a passphrase.
+    ///
+    /// # Panics
+    ///
+    /// Building a scheme panics if the word provider has fewer distinct words than the number of
+    /// words.
+    pub fn with_unique_words(mut self, unique_words: bool) -> Self {
+        self.unique_words = unique_words;
+        self
+    }
+}
+
+impl<P> BasicConfig<P>
+where
+    P: WordProvider + 'static,
+{
+    /// Validate this configuration.
+    ///
+    /// This checks for configurations that likely don't behave as intended, such as a separator
+    /// occurring inside wordlist entries or always capitalizing words.
+    /// See [`Scheme::validate`](Scheme::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::config::BasicConfig;
+    ///
+    /// let mut config = BasicConfig::default();
+    /// assert!(config.validate().is_empty());
+    ///
+    /// // The default wordlist contains hyphenated words
+    /// config.separator = "-".into();
+    /// assert_eq!(1, config.validate().len());
+    ///
+    /// // Always capitalizing words doesn't add entropy
+    /// config.separator = " ".into();
+    /// config.capitalize_words = true.into();
+    /// assert_eq!(1, config.validate().len());
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Vec<Warning> {
+        self.to_scheme().validate()
+    }
+}
+
+#[cfg(feature = "wordlist-eff-large")]
+impl Default for BasicConfig<WordSampler> {
+    /// Build a default basic configuration instance.
+    ///
+    /// This configuration uses the defaul wordlist as word provider for generating passphrases.
+    /// Only available with the `wordlist-eff-large` feature, enabled by default.
+    fn default() -> BasicConfig<WordSampler> {
+        BasicConfig::new(WordList::default().sampler())
+    }
 }
 
 impl<P> ToScheme for BasicConfig<P>
@@ -101,9 +234,117 @@ where
 {
     fn to_scheme(&self) -> Scheme {
         SchemeBuilder::default()
-            .word_set_provider(Box::new(FixedWordSetProvider::new(
-                self.word_provider.clone(),
-                self.words,
+            .word_set_provider(Box::new(
+                FixedWordSetProvider::new(self.word_provider.clone(), self.words)
+                    .with_unique(self.unique_words),
+            ))
+            .word_stylers(vec![Box::new(WordCapitalizer::new(
+                self.capitalize_first,
+                self.capitalize_words,
+            ))])
+            .phrase_builder(Box::new(BasicPhraseBuilder::new(self.separator.clone())))
+            .phrase_stylers(Vec::new())
+            .build()
+            .unwrap()
+    }
+}
+
+/// A themed passphrase configuration struct.
+///
+/// This configures a list of slots, each with a [`Theme`](Theme) selecting the wordlist a word for
+/// that position is sampled from, such as a color followed by an animal. This produces
+/// memorable Docker-style identifiers, like `brave-teal-otter`. The entropy of a passphrase is
+/// the sum of the entropy of each slot.
+///
+/// The built-in themes are small, see the [`theme`](crate::word::theme) module. Add slots or
+/// custom wordlists to reach the desired entropy.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{config::ThemedConfig, prelude::*, word::theme::Theme};
+///
+/// let config = ThemedConfig::new(vec![Theme::Color, Theme::Animal, Theme::Number]);
+/// let scheme = config.to_scheme();
+/// println!("Identifier: {}", scheme.generate());
+/// assert_eq!(6.0 + 6.0 + 100f64.log2(), scheme.entropy().bits());
+/// ```
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ThemedConfig {
+    /// The theme of each passphrase word, in order.
+    pub slots: Vec<Theme>,
+
+    /// The separator string to use between passphrase words.
+    pub separator: String,
+
+    /// Whether to capitalize the first characters of words.
+    pub capitalize_first: Probability,
+
+    /// Whether to capitalize whole words.
+    pub capitalize_words: Probability,
+}
+
+impl ThemedConfig {
+    /// Build a themed configuration with the given theme for each slot.
+    ///
+    /// Words are separated by `-` and not capitalized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slots` is empty.
+    pub fn new(slots: Vec<Theme>) -> Self {
+        if slots.is_empty() {
+            panic!("cannot construct ThemedConfig without slots");
+        }
+
+        ThemedConfig {
+            slots,
+            separator: "-".into(),
+            capitalize_first: Probability::Never,
+            capitalize_words: Probability::Never,
+        }
+    }
+
+    /// Set the theme of each passphrase word.
+    pub fn with_slots(mut self, slots: Vec<Theme>) -> Self {
+        self.slots = slots;
+        self
+    }
+
+    /// Set the separator string to use between passphrase words.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Set whether to capitalize the first characters of words.
+    pub fn with_capitalize_first<T: Into<Probability>>(mut self, capitalize_first: T) -> Self {
+        self.capitalize_first = capitalize_first.into();
+        self
+    }
+
+    /// Set whether to capitalize whole words.
+    pub fn with_capitalize_words<T: Into<Probability>>(mut self, capitalize_words: T) -> Self {
+        self.capitalize_words = capitalize_words.into();
+        self
+    }
+}
+
+impl Default for ThemedConfig {
+    /// Build a default themed configuration, with an adjective, color and animal slot.
+    fn default() -> Self {
+        Self::new(vec![Theme::Adjective, Theme::Color, Theme::Animal])
+    }
+}
+
+impl ToScheme for ThemedConfig {
+    fn to_scheme(&self) -> Scheme {
+        SchemeBuilder::default()
+            .word_set_provider(Box::new(PositionalWordSetProvider::new(
+                self.slots.iter().map(Theme::provider).collect(),
             )))
             .word_stylers(vec![Box::new(WordCapitalizer::new(
                 self.capitalize_first,
@@ -115,3 +356,203 @@ where
             .unwrap()
     }
 }
+
+/// The names of all built-in presets, see [`preset`](preset).
+pub const PRESETS: [&str; 4] = ["default", "xkcd", "strong", "pin6"];
+
+/// Get a built-in configuration preset by name.
+///
+/// This allows exposing presets through a single string option, such as in a CLI or service.
+/// Names are matched case-insensitively. Returns `None` if there is no preset with the given
+/// name, or if it requires the default wordlist while the `wordlist-eff-large` feature is
+/// disabled. See [`PRESETS`](PRESETS) for all available names:
+///
+/// - `default`: the [default](BasicConfig::default) configuration.
+/// - `xkcd`: four lowercase words separated by spaces, as in the comic the crate is named after.
+/// - `strong`: eight words, for high-value secrets such as master passwords.
+/// - `pin6`: six random digits, for numeric codes. This provides very little entropy.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::config::{preset, PRESETS};
+///
+/// for name in PRESETS.iter() {
+///     let scheme = preset(name).unwrap().to_scheme();
+///     println!("{}: {}", name, scheme.generate());
+/// }
+///
+/// assert!(preset("unknown").is_none());
+/// # }
+/// ```
+pub fn preset(name: &str) -> Option<Box<dyn ToScheme>> {
+    let config: Box<dyn ToScheme> = match name.to_ascii_lowercase().as_str() {
+        #[cfg(feature = "wordlist-eff-large")]
+        "default" => Box::new(BasicConfig::default()),
+        #[cfg(feature = "wordlist-eff-large")]
+        "xkcd" => Box::new(
+            BasicConfig::default()
+                .with_words(4)
+                .with_separator(" ")
+                .with_capitalize_first(false),
+        ),
+        #[cfg(feature = "wordlist-eff-large")]
+        "strong" => Box::new(BasicConfig::default().with_words(8)),
+        "pin6" => Box::new(
+            BasicConfig::new(["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"])
+                .with_words(6)
+                .with_separator("")
+                .with_capitalize_first(false),
+        ),
+        _ => return None,
+    };
+    Some(config)
+}
+
+/// Find a basic configuration satisfying the given policy and constraints.
+///
+/// This searches the allowed wordlists, word counts and capitalization stylers for a
+/// configuration reaching the [minimum entropy](Policy::min_entropy) while never generating
+/// passphrases longer than the [maximum length](Constraints::max_length). Lengths are based on
+/// the longest word in a wordlist, so every generated passphrase fits.
+///
+/// The best configuration found is returned, being the one with the shortest maximum passphrase
+/// length. Ties are broken by choosing the configuration with the most entropy. Wordlists having
+/// less than two words, or having words containing the separator, are skipped.
+///
+/// # Errors
+///
+/// Returns a [`FitError`](FitError) explaining why no configuration satisfies both the policy
+/// and constraints.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{config, entropy::Entropy, policy::{Constraints, Policy}, prelude::*};
+///
+/// let policy = Policy::new(Entropy::from_bits(60));
+/// let constraints = Constraints::default().with_max_length(64);
+///
+/// let config = config::fit(&policy, &constraints).unwrap();
+/// let scheme = config.to_scheme();
+/// assert!(scheme.entropy() >= policy.min_entropy);
+/// assert!(scheme.generate().chars().count() <= 64);
+///
+/// // Not possible to fit in such a short passphrase
+/// let err = config::fit(&policy, &constraints.with_max_length(16)).unwrap_err();
+/// println!("{}", err);
+/// ```
+pub fn fit(
+    policy: &Policy,
+    constraints: &Constraints,
+) -> Result<BasicConfig<WordSampler>, FitError> {
+    if !policy.min_entropy.is_valid() {
+        return Err(FitError::InvalidEntropy(policy.min_entropy));
+    }
+
+    let stylers = [
+        (Probability::Never, Probability::Never),
+        (Probability::half(), Probability::Never),
+        (Probability::half(), Probability::half()),
+    ];
+    let separator_length = constraints.separator.chars().count();
+    let max_length = constraints.max_length.unwrap_or(usize::MAX);
+
+    let mut best: Option<(usize, Entropy, BasicConfig<WordSampler>)> = None;
+    let mut min_length: Option<usize> = None;
+    let mut max_entropy = Entropy::zero();
+
+    for wordlist in &constraints.wordlists {
+        let words = wordlist.words();
+        if words.len() < 2 || check_separator(&constraints.separator, words).is_err() {
+            continue;
+        }
+
+        // Worst case passphrase length for a number of words
+        let word_length = words.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+        let length = |count: usize| {
+            count
+                .saturating_mul(word_length)
+                .saturating_add(count.saturating_sub(1).saturating_mul(separator_length))
+        };
+
+        for (first, all) in stylers.iter() {
+            let config = BasicConfig::new(wordlist.sampler())
+                .with_separator(constraints.separator.as_str())
+                .with_capitalize_first(*first)
+                .with_capitalize_words(*all);
+            let entropy = |count: usize| config.clone().with_words(count).to_scheme().entropy();
+
+            // Find the minimal word count reaching the target, entropy is linear in word count
+            let per_word = entropy(1).bits();
+            let mut count = ((policy.min_entropy.bits() / per_word).ceil() as usize).max(1);
+            while entropy(count) < policy.min_entropy {
+                count += 1;
+            }
+
+            let required = length(count);
+            min_length = Some(min_length.map_or(required, |l| l.min(required)));
+
+            // Track the most entropy reachable within the maximum length
+            let fitting = max_length.saturating_add(separator_length)
+                / (word_length + separator_length).max(1);
+            if fitting > 0 {
+                let fitting_entropy = entropy(fitting.min(count));
+                if fitting_entropy > max_entropy {
+                    max_entropy = fitting_entropy;
+                }
+            }
+
+            if required > max_length {
+                continue;
+            }
+            let total = entropy(count);
+            let better = match &best {
+                Some((l, e, _)) => required < *l || (required == *l && total > *e),
+                None => true,
+            };
+            if better {
+                best = Some((required, total, config.clone().with_words(count)));
+            }
+        }
+    }
+
+    match (best, min_length) {
+        (Some((_, _, config)), _) => Ok(config),
+        (None, Some(min_length)) => Err(FitError::TooLong {
+            min_length,
+            max_length,
+            max_entropy,
+        }),
+        (None, None) => Err(FitError::NoWordlists),
+    }
+}
+
+/// An error explaining why [`fit`](fit) couldn't find a configuration.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum FitError {
+    /// The minimum entropy of the policy is negative or not finite.
+    #[error("policy requires invalid minimum entropy of {0}")]
+    InvalidEntropy(Entropy),
+
+    /// None of the wordlists can be used.
+    ///
+    /// Wordlists must have at least two words, none of which may contain the separator.
+    #[error("no usable wordlists, a wordlist with at least two words not containing the separator is required")]
+    NoWordlists,
+
+    /// Reaching the minimum entropy requires passphrases longer than the maximum length.
+    #[error("reaching the minimum entropy requires passphrases of up to {min_length} characters, exceeding the maximum of {max_length}, which allows at most {max_entropy}")]
+    TooLong {
+        /// The shortest maximum passphrase length reaching the minimum entropy.
+        min_length: usize,
+
+        /// The maximum passphrase length allowed by the constraints.
+        max_length: usize,
+
+        /// The most entropy reachable within the maximum length.
+        max_entropy: Entropy,
+    },
+}
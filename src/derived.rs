@@ -0,0 +1,89 @@
+//! Deterministic passphrase derivation from a master secret
+//!
+//! This module provides the [`DerivedScheme`](DerivedScheme) type, which derives passphrases
+//! from a master secret and a context, such as a site name. The same inputs always produce the
+//! same passphrase, which allows stateless and reproducible per-site passphrases without storing
+//! them anywhere.
+//!
+//! Only available with the `derived` feature.
+
+use hkdf::Hkdf;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use sha2::Sha256;
+
+use crate::scheme::Scheme;
+
+/// The HKDF salt, separating derived passphrases from other uses of the same master secret.
+const SALT: &[u8] = b"chbs derived scheme v1";
+
+/// A scheme deriving passphrases deterministically from a master secret.
+///
+/// For each passphrase, a seed is derived using HKDF-SHA256 from the master secret, with the
+/// context as info. The seed initializes a ChaCha20 random number generator, which is used for
+/// all randomness during generation through
+/// [`Scheme::generate_with_rng`](Scheme::generate_with_rng). All components of the wrapped scheme
+/// are reused, custom components must implement the `_with_rng` methods for derivation to be
+/// deterministic.
+///
+/// # Security
+///
+/// A derived passphrase can never have more entropy than the master secret, regardless of the
+/// [entropy](Scheme::entropy) of the scheme. Use a master secret with high entropy. Anyone
+/// knowing the master secret can derive all passphrases, and a leaked derived passphrase may be
+/// used to brute force a weak master secret.
+///
+/// # Stability
+///
+/// Derived passphrases depend on the scheme configuration, including the exact wordlist, and on
+/// the sampling algorithms used. Keep these fixed to reproduce passphrases. Derived passphrases
+/// are identical on every platform, but may change between major versions of this crate. See
+/// [`Scheme::generate_seeded`](Scheme::generate_seeded) for requirements on custom components.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{config::BasicConfig, derived::DerivedScheme, prelude::*};
+///
+/// let scheme = DerivedScheme::new(BasicConfig::default().to_scheme());
+///
+/// let secret = b"my very secret master secret";
+/// let phrase = scheme.derive(secret, "example.com");
+/// assert_eq!(phrase, scheme.derive(secret, "example.com"));
+/// assert_ne!(phrase, scheme.derive(secret, "example.org"));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DerivedScheme {
+    /// The scheme used to generate passphrases.
+    scheme: Scheme,
+}
+
+impl DerivedScheme {
+    /// Construct a derived scheme for the given scheme.
+    pub fn new(scheme: Scheme) -> Self {
+        Self { scheme }
+    }
+
+    /// Derive the passphrase for the given master secret and context.
+    pub fn derive(&self, master_secret: &[u8], context: &str) -> String {
+        self.scheme
+            .generate_with_rng(&mut rng(master_secret, context))
+    }
+
+    /// Get the scheme used to generate passphrases.
+    pub fn scheme(&self) -> &Scheme {
+        &self.scheme
+    }
+}
+
+/// Derive a random number generator for the given master secret and context.
+fn rng(master_secret: &[u8], context: &str) -> ChaCha20Rng {
+    let mut seed = <ChaCha20Rng as SeedableRng>::Seed::default();
+    Hkdf::<Sha256>::new(Some(SALT), master_secret)
+        .expand(context.as_bytes(), &mut seed)
+        .expect("seed length is valid for HKDF-SHA256");
+    ChaCha20Rng::from_seed(seed)
+}
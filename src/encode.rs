@@ -0,0 +1,326 @@
+//! Reversible encoding of bytes as passphrase words
+//!
+//! A [`PhraseEncoder`](PhraseEncoder) maps arbitrary bytes to a sequence of wordlist words and
+//! back, similar to [niceware](https://github.com/diracdeltas/niceware). This renders a key,
+//! such as a 128-bit recovery key, as a phrase that is easier to write down, read aloud or type
+//! than hexadecimal, and decodes it again later.
+//!
+//! Bytes are encoded as a big-endian bit stream, each word encoding a fixed number of bits. The
+//! wordlist must therefore have a power of two number of words, use
+//! [`WordList::truncate_pow2`](WordList::truncate_pow2) to truncate a list. The wordlist and
+//! separator must be identical for encoding and decoding.
+//!
+//! Unlike generated passphrases, an encoded phrase is exactly as secret as the encoded bytes.
+//!
+//! Bytes may also be encoded using the [PGP word list](https://en.wikipedia.org/wiki/PGP_word_list)
+//! with [`encode_pgp`](encode_pgp), commonly used to read key fingerprints aloud. It alternates
+//! between two lists by byte position, so swapped or missing words are detected when decoding.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
+//! use chbs::{encode::PhraseEncoder, word::WordList};
+//!
+//! let encoder = PhraseEncoder::new(&WordList::default().truncate_pow2()).unwrap();
+//! let key = [0x2a; 16];
+//!
+//! // A 128-bit key is encoded as 11 words of 12 bits
+//! let phrase = encoder.encode(&key);
+//! assert_eq!(11, phrase.split(' ').count());
+//! assert_eq!(key.to_vec(), encoder.decode_exact(&phrase, 16).unwrap());
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::validate::{check_separator, SeparatorError};
+use crate::word::{StaticWordList, WordList};
+
+use super::DEFAULT_SEPARATOR;
+
+/// The maximum number of bits a single word may encode.
+const MAX_BITS_PER_WORD: usize = 24;
+
+/// An encoder of bytes as passphrase words, and back.
+///
+/// See the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct PhraseEncoder {
+    /// The wordlist to encode with.
+    wordlist: WordList,
+
+    /// The index of each lowercase word in the wordlist.
+    indices: HashMap<String, usize>,
+
+    /// The number of bits each word encodes.
+    bits: usize,
+
+    /// The separator between words.
+    separator: String,
+}
+
+impl PhraseEncoder {
+    /// Construct an encoder for the given wordlist, separating words with a space.
+    ///
+    /// An error is returned if the number of words is not a power of two of at least two words,
+    /// or if the list contains duplicate words, ignoring case.
+    pub fn new(wordlist: &WordList) -> Result<Self, EncodeError> {
+        let len = wordlist.words().len();
+        let bits = len.trailing_zeros() as usize;
+        if len < 2 || !len.is_power_of_two() || bits > MAX_BITS_PER_WORD {
+            return Err(EncodeError::WordListSize(len));
+        }
+
+        let mut indices = HashMap::with_capacity(len);
+        for (index, word) in wordlist.words().iter().enumerate() {
+            if indices.insert(word.to_lowercase(), index).is_some() {
+                return Err(EncodeError::DuplicateWord(word.clone()));
+            }
+        }
+
+        Self {
+            wordlist: wordlist.clone(),
+            indices,
+            bits,
+            separator: String::new(),
+        }
+        .with_separator(DEFAULT_SEPARATOR)
+    }
+
+    /// Set the separator between words.
+    ///
+    /// An error is returned if the separator is empty or occurs inside any word, as phrases
+    /// couldn't be split into their words reliably.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Result<Self, EncodeError> {
+        let separator = separator.into();
+        if separator.is_empty() {
+            return Err(EncodeError::EmptySeparator);
+        }
+        check_separator(&separator, self.wordlist.words())?;
+
+        self.separator = separator;
+        Ok(self)
+    }
+
+    /// Get the number of bits each word encodes.
+    pub fn bits_per_word(&self) -> usize {
+        self.bits
+    }
+
+    /// Get the number of words `len` bytes are encoded as.
+    pub fn word_count(&self, len: usize) -> usize {
+        div_ceil(len * 8, self.bits)
+    }
+
+    /// Encode the given bytes as phrase.
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        self.encode_words(bytes).join(&self.separator)
+    }
+
+    /// Encode the given bytes as words.
+    ///
+    /// The last word is padded with zero bits.
+    pub fn encode_words(&self, bytes: &[u8]) -> Vec<&str> {
+        let bit = |i: usize| bytes.get(i / 8).map_or(0, |byte| byte >> (7 - i % 8) & 1);
+        (0..self.word_count(bytes.len()))
+            .map(|word| {
+                let index = (0..self.bits).fold(0, |index, i| {
+                    index << 1 | bit(word * self.bits + i) as usize
+                });
+                self.wordlist.words()[index].as_str()
+            })
+            .collect()
+    }
+
+    /// Decode the given phrase into bytes.
+    ///
+    /// Words are matched case-insensitively. Whitespace around words is ignored. An error is
+    /// returned if a word is not in the wordlist, or if the phrase isn't a valid encoding.
+    ///
+    /// With more than 8 bits per word, the same number of words may encode different numbers of
+    /// bytes. For example, with 12 bits per word both 2 and 3 bytes are encoded as two words. If
+    /// the last bytes are zero the length is ambiguous, and an error is returned. Use
+    /// [`decode_exact`](PhraseEncoder::decode_exact) when the number of bytes is known, such as
+    /// for keys.
+    pub fn decode(&self, phrase: &str) -> Result<Vec<u8>, EncodeError> {
+        let (mut bytes, words) = self.decode_bits(phrase)?;
+        let mut lengths = (0..=bytes.len())
+            .filter(|&len| self.word_count(len) == words && is_padding(&bytes, len));
+        let len = match (lengths.next(), lengths.next()) {
+            (Some(len), None) => len,
+            (Some(_), Some(_)) => return Err(EncodeError::AmbiguousLength),
+            (None, _) => return Err(EncodeError::Padding),
+        };
+
+        bytes.truncate(len);
+        Ok(bytes)
+    }
+
+    /// Decode the given phrase into exactly `len` bytes.
+    ///
+    /// This is like [`decode`](PhraseEncoder::decode), but never ambiguous. An error is returned
+    /// if the phrase doesn't encode `len` bytes.
+    pub fn decode_exact(&self, phrase: &str, len: usize) -> Result<Vec<u8>, EncodeError> {
+        let (mut bytes, words) = self.decode_bits(phrase)?;
+        if self.word_count(len) != words {
+            return Err(EncodeError::WordCount(words));
+        }
+        if !is_padding(&bytes, len) {
+            return Err(EncodeError::Padding);
+        }
+
+        bytes.truncate(len);
+        Ok(bytes)
+    }
+
+    /// Decode all bits of the given phrase, including padding, along with the number of words.
+    fn decode_bits(&self, phrase: &str) -> Result<(Vec<u8>, usize), EncodeError> {
+        let phrase = phrase.trim();
+        if phrase.is_empty() {
+            return Ok((Vec::new(), 0));
+        }
+
+        let indices = phrase
+            .split(self.separator.as_str())
+            .map(|word| {
+                let word = word.trim();
+                self.indices
+                    .get(&word.to_lowercase())
+                    .copied()
+                    .ok_or_else(|| EncodeError::UnknownWord(word.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bits = indices.len() * self.bits;
+        let mut bytes = vec![0u8; div_ceil(bits, 8)];
+        for (word, index) in indices.iter().enumerate() {
+            for b in 0..self.bits {
+                let position = word * self.bits + b;
+                let value = (index >> (self.bits - 1 - b) & 1) as u8;
+                bytes[position / 8] |= value << (7 - position % 8);
+            }
+        }
+        Ok((bytes, indices.len()))
+    }
+}
+
+/// Encode the given bytes using the PGP word list, such as a key fingerprint.
+///
+/// Each byte is encoded as a single word, separated by a space. Bytes at even positions use the
+/// [even](crate::word::BUILTIN_PGP_EVEN) words, bytes at odd positions use the
+/// [odd](crate::word::BUILTIN_PGP_ODD) words.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::encode::{decode_pgp, encode_pgp};
+///
+/// let fingerprint = [0xE5, 0x82, 0x94, 0xF2];
+/// assert_eq!("topmost Istanbul Pluto vagabond", encode_pgp(&fingerprint));
+/// assert_eq!(fingerprint.to_vec(), decode_pgp("topmost istanbul pluto vagabond").unwrap());
+/// ```
+pub fn encode_pgp(bytes: &[u8]) -> String {
+    let (even, odd) = pgp_wordlists();
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(i, &byte)| {
+            if i % 2 == 0 {
+                even[byte as usize]
+            } else {
+                odd[byte as usize]
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(DEFAULT_SEPARATOR)
+}
+
+/// Decode the given PGP word list phrase into bytes.
+///
+/// Words may be separated by any whitespace, and are matched case-insensitively. An error is
+/// returned if a word is unknown, or if a word is from the wrong list for its position, which
+/// happens when words are swapped or missing.
+pub fn decode_pgp(phrase: &str) -> Result<Vec<u8>, EncodeError> {
+    let (even, odd) = pgp_wordlists();
+    let position =
+        |list: &[&str], word: &str| list.iter().position(|w| w.eq_ignore_ascii_case(word));
+    phrase
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| {
+            let (list, other) = if i % 2 == 0 { (even, odd) } else { (odd, even) };
+            match position(list, word) {
+                Some(byte) => Ok(byte as u8),
+                None if position(other, word).is_some() => Err(EncodeError::PgpPosition(i)),
+                None => Err(EncodeError::UnknownWord(word.into())),
+            }
+        })
+        .collect()
+}
+
+/// Get the PGP even and odd word lists.
+fn pgp_wordlists() -> (&'static [&'static str], &'static [&'static str]) {
+    (
+        StaticWordList::builtin_pgp_even().words(),
+        StaticWordList::builtin_pgp_odd().words(),
+    )
+}
+
+/// Divide `a` by `b`, rounding up.
+fn div_ceil(a: usize, b: usize) -> usize {
+    let quotient = a / b;
+    if quotient * b < a {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Check whether all bytes after the first `len` are zero, as padding.
+fn is_padding(bytes: &[u8], len: usize) -> bool {
+    bytes.iter().skip(len).all(|&byte| byte == 0)
+}
+
+/// A [`PhraseEncoder`](PhraseEncoder) error.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum EncodeError {
+    /// The wordlist doesn't have a power of two number of words.
+    #[error("wordlist of {0} words can't be used for encoding, must be a power of two")]
+    WordListSize(usize),
+
+    /// The wordlist contains a duplicate word.
+    #[error("wordlist contains duplicate word '{0}'")]
+    DuplicateWord(String),
+
+    /// The separator is empty.
+    #[error("separator must not be empty")]
+    EmptySeparator,
+
+    /// The separator occurs inside words.
+    #[error(transparent)]
+    Separator(#[from] SeparatorError),
+
+    /// The phrase has a number of words not encoding the expected number of bytes.
+    #[error("phrase of {0} words doesn't encode the expected number of bytes")]
+    WordCount(usize),
+
+    /// The number of bytes the phrase encodes is ambiguous.
+    #[error("phrase encodes an ambiguous number of bytes, decode with a known length")]
+    AmbiguousLength,
+
+    /// A word in the phrase is not in the wordlist.
+    #[error("unknown word '{0}'")]
+    UnknownWord(String),
+
+    /// A PGP word at the given position is from the wrong list, words are swapped or missing.
+    #[error("PGP word at position {0} is from the wrong list, words may be swapped or missing")]
+    PgpPosition(usize),
+
+    /// The phrase has non-zero padding bits, and isn't a valid encoding.
+    #[error("phrase is not a valid encoding, padding bits are set")]
+    Padding,
+}
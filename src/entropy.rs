@@ -13,11 +13,24 @@ use std::{
     ops::{Add, Div, Mul, Sub},
 };
 
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+#[cfg(feature = "bigint")]
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::component::ComponentKind;
+use crate::validate::Warning;
+
 /// Password entropy.
 ///
 /// The entropy number used internally represents the number of base 2 entropy bits,
 /// and is calculated using `log2(choices)`.
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Entropy(f64);
 
 impl Entropy {
@@ -48,10 +61,242 @@ impl Entropy {
         Entropy(real.into().log2())
     }
 
+    /// Construct entropy for an ordered selection of `k` distinct items out of `n`.
+    ///
+    /// This is the entropy of uniformly choosing one of the `n! / (n - k)!` permutations, such as
+    /// sampling `k` words from a wordlist of `n` words without replacement.
+    ///
+    /// The result is calculated as sum of logarithms, and does not overflow for large pools. Use
+    /// [`permutations`](permutations) with the `bigint` feature for the exact pool size.
+    ///
+    /// # Panics
+    ///
+    /// `k` must not be larger than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::entropy::Entropy;
+    ///
+    /// // 5 distinct words from 7776, slightly less than with replacement
+    /// let entropy = Entropy::from_permutations(7776, 5);
+    /// assert!(entropy < Entropy::from_real(7776) * 5);
+    /// assert!((Entropy::from_permutations(5, 2) - Entropy::from_real(20)).bits().abs() < 1e-9);
+    /// ```
+    pub fn from_permutations(n: u64, k: u64) -> Self {
+        if k > n {
+            panic!("cannot calculate entropy of selecting more items than available");
+        }
+
+        Entropy(((n - k + 1)..=n).map(|i| (i as f64).log2()).sum())
+    }
+
+    /// Construct entropy for an unordered selection of `k` distinct items out of `n`.
+    ///
+    /// This is the entropy of uniformly choosing one of the `n! / (k! (n - k)!)` combinations,
+    /// such as choosing `k` positions out of `n` to insert something at.
+    ///
+    /// The result is calculated as sum of logarithms, and does not overflow for large pools. Use
+    /// [`combinations`](combinations) with the `bigint` feature for the exact pool size.
+    ///
+    /// # Panics
+    ///
+    /// `k` must not be larger than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::entropy::Entropy;
+    ///
+    /// assert!((Entropy::from_combinations(5, 2) - Entropy::from_real(10)).bits().abs() < 1e-9);
+    /// assert_eq!(Entropy::zero(), Entropy::from_combinations(5, 5));
+    /// ```
+    pub fn from_combinations(n: u64, k: u64) -> Self {
+        if k > n {
+            panic!("cannot calculate entropy of selecting more items than available");
+        }
+
+        // Use the symmetric smallest selection for fewer terms
+        let k = k.min(n - k);
+        let factorial: f64 = (1..=k).map(|i| (i as f64).log2()).sum();
+        Entropy((Self::from_permutations(n, k).0 - factorial).max(0.0))
+    }
+
+    /// Construct entropy from an exact number of uniform choices.
+    ///
+    /// This is equal to [`from_real`](Entropy::from_real), but supports pool sizes that don't fit
+    /// in a float without losing precision, such as those calculated by
+    /// [`permutations`](permutations) and [`combinations`](combinations).
+    ///
+    /// Only available with the `bigint` feature.
+    #[cfg(feature = "bigint")]
+    pub fn from_big_real(real: &BigUint) -> Self {
+        // Take the 64 most significant bits, and account for the discarded ones
+        let shift = real.bits().saturating_sub(64);
+        let top = (real >> shift).to_u64().unwrap_or(0);
+        Entropy((top as f64).log2() + shift as f64)
+    }
+
+    /// Construct entropy from a probability distribution.
+    ///
+    /// This calculates the Shannon entropy of the distribution given as list of outcome
+    /// probabilities, which should sum up to `1`. Outcomes with a probability of zero are ignored.
+    ///
+    /// For a uniform distribution this is equal to [`from_real`](Entropy::from_real) with the
+    /// number of outcomes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::entropy::Entropy;
+    ///
+    /// assert_eq!(Entropy::one(), Entropy::from_distribution(vec![0.5, 0.5]));
+    /// assert_eq!(Entropy::zero(), Entropy::from_distribution(vec![1.0, 0.0]));
+    /// ```
+    pub fn from_distribution<I>(probabilities: I) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        Entropy(
+            probabilities
+                .into_iter()
+                .filter(|p| *p > 0.0)
+                .map(|p| -p * p.log2())
+                .sum::<f64>()
+                // Prevent returning negative zero
+                .max(0.0),
+        )
+    }
+
+    /// Fold the acceptance rate of a rejection-based constraint into this entropy.
+    ///
+    /// When passphrases violating a constraint are regenerated, only accepted passphrases remain
+    /// possible, lowering the entropy by `-log2(acceptance)` bits. The conservative
+    /// [lower bound](Acceptance::lower_bound) of the acceptance rate is used. The result is never
+    /// negative.
+    ///
+    /// ```rust
+    /// use chbs::entropy::{Acceptance, Entropy};
+    ///
+    /// let entropy = Entropy::from_bits(64.0);
+    /// assert_eq!(entropy, entropy.with_acceptance(&Acceptance::new(1000, 1000)));
+    /// assert!(entropy.with_acceptance(&Acceptance::new(500, 1000)).bits() < 63.0);
+    /// ```
+    pub fn with_acceptance(self, acceptance: &Acceptance) -> Self {
+        Entropy((self.0 + acceptance.lower_bound().log2()).max(0.0))
+    }
+
     /// Get the number of entropy bits.
     pub fn bits(self) -> f64 {
         self.0
     }
+
+    /// Check whether this is a valid entropy value, being finite and not negative.
+    ///
+    /// The arithmetic operators don't check their results, so subtraction and division may
+    /// produce invalid values. Use the `checked_` methods to prevent this.
+    pub fn is_valid(self) -> bool {
+        self.0.is_finite() && self.0 >= 0.0
+    }
+
+    /// Add `other` entropy, returning `None` if the result is not [valid](Entropy::is_valid).
+    pub fn checked_add(self, other: Entropy) -> Option<Entropy> {
+        Some(self + other).filter(|e| e.is_valid())
+    }
+
+    /// Subtract `other` entropy, returning `None` if the result is not
+    /// [valid](Entropy::is_valid), such as when it would be negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::entropy::Entropy;
+    ///
+    /// assert_eq!(Some(Entropy::one()), Entropy::from_bits(3).checked_sub(Entropy::from_bits(2)));
+    /// assert_eq!(None, Entropy::from_bits(2).checked_sub(Entropy::from_bits(3)));
+    /// ```
+    pub fn checked_sub(self, other: Entropy) -> Option<Entropy> {
+        Some(self - other).filter(|e| e.is_valid())
+    }
+
+    /// Subtract `other` entropy, clamping the result at zero bits.
+    ///
+    /// Results that are not finite are clamped to zero bits as well.
+    pub fn saturating_sub(self, other: Entropy) -> Entropy {
+        self.checked_sub(other).unwrap_or_else(Entropy::zero)
+    }
+
+    /// Multiply by `factor`, returning `None` if the result is not [valid](Entropy::is_valid).
+    pub fn checked_mul<F: Into<f64>>(self, factor: F) -> Option<Entropy> {
+        Some(self * factor).filter(|e| e.is_valid())
+    }
+
+    /// Divide by `divisor`, returning `None` if the result is not [valid](Entropy::is_valid),
+    /// such as when dividing by zero.
+    pub fn checked_div<F: Into<f64>>(self, divisor: F) -> Option<Entropy> {
+        Some(self / divisor).filter(|e| e.is_valid())
+    }
+
+    /// Get the number of guesses needed to exhaust all choices, `2^bits`.
+    ///
+    /// On average, an attacker finds a passphrase in half this number of guesses.
+    pub fn guesses(self) -> f64 {
+        self.0.exp2()
+    }
+
+    /// Explain this entropy in a short human-readable English text.
+    ///
+    /// The text compares this entropy to a random password, and estimates the average time to
+    /// guess a passphrase at the given `guess_rate` in guesses per second. It is meant for direct
+    /// display in end-user interfaces. See [`Explanation`](Explanation) for details.
+    ///
+    /// To localize the text, use [`explain_with`](Entropy::explain_with) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::entropy::Entropy;
+    ///
+    /// assert_eq!(
+    ///     Entropy::from_bits(77).explain(1e10),
+    ///     "≈ 77 bits; comparable to a 13-character random password; ~240,000 years at 10¹⁰ guesses/s",
+    /// );
+    /// ```
+    pub fn explain(self, guess_rate: f64) -> String {
+        self.explanation(guess_rate).to_string()
+    }
+
+    /// Explain this entropy using the given `template` function.
+    ///
+    /// This allows localizing the text produced by [`explain`](Entropy::explain). The template
+    /// is given the [`Explanation`](Explanation) to render.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::entropy::Entropy;
+    ///
+    /// let text = Entropy::from_bits(77).explain_with(1e10, |e| {
+    ///     format!("≈ {:.0} bits, {} caractères aléatoires", e.bits, e.random_chars)
+    /// });
+    /// assert_eq!(text, "≈ 77 bits, 13 caractères aléatoires");
+    /// ```
+    pub fn explain_with<F>(self, guess_rate: f64, template: F) -> String
+    where
+        F: Fn(&Explanation) -> String,
+    {
+        template(&self.explanation(guess_rate))
+    }
+
+    /// Get the [`Explanation`](Explanation) of this entropy at the given `guess_rate`.
+    pub fn explanation(self, guess_rate: f64) -> Explanation {
+        Explanation {
+            bits: self.0,
+            random_chars: (self.0 / (RANDOM_PASSWORD_CHARS as f64).log2()).ceil() as usize,
+            guess_rate,
+            seconds: self.guesses() / 2.0 / guess_rate,
+        }
+    }
 }
 
 impl Display for Entropy {
@@ -121,3 +366,416 @@ pub trait HasEntropy {
     /// If this component does not have any effect on passphrase entropy `1` should be returned.
     fn entropy(&self) -> Entropy;
 }
+
+/// The z-score for the one-sided 99.9% confidence level of acceptance rate lower bounds.
+const ACCEPTANCE_Z: f64 = 3.09;
+
+/// The measured acceptance rate of a rejection-based constraint.
+///
+/// Constraints such as a maximum length, a [ban list](crate::guard::BanList) or a policy reject
+/// some generated passphrases, which are then regenerated. This lowers the effective entropy, as
+/// rejected passphrases can never be issued. The acceptance rate measures which fraction of
+/// generated passphrases is accepted, and is folded into the entropy using
+/// [`Entropy::with_acceptance`](Entropy::with_acceptance).
+///
+/// Measure the acceptance rate of a scheme using
+/// [`Scheme::measure_acceptance`](crate::scheme::Scheme::measure_acceptance).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Acceptance {
+    /// The number of accepted samples.
+    accepted: usize,
+
+    /// The total number of samples.
+    samples: usize,
+}
+
+impl Acceptance {
+    /// Construct an acceptance rate from the given number of `accepted` out of all `samples`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is zero, or if `accepted` is larger than `samples`.
+    pub fn new(accepted: usize, samples: usize) -> Self {
+        if samples == 0 || accepted > samples {
+            panic!("cannot construct acceptance rate, invalid number of samples");
+        }
+
+        Self { accepted, samples }
+    }
+
+    /// Get the number of accepted samples.
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// Get the total number of samples.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Get the measured acceptance rate, from `0` to `1`.
+    pub fn rate(&self) -> f64 {
+        self.accepted as f64 / self.samples as f64
+    }
+
+    /// Get a conservative lower bound of the acceptance rate, from `0` to `1`.
+    ///
+    /// The measured rate is an estimate, and the true rate may be lower. This returns the lower
+    /// bound of the Wilson score interval at a one-sided 99.9% confidence level, or `1` if all
+    /// samples were accepted. More samples produce a tighter bound.
+    pub fn lower_bound(&self) -> f64 {
+        if self.accepted == self.samples {
+            return 1.0;
+        }
+
+        let n = self.samples as f64;
+        let p = self.rate();
+        let z2 = ACCEPTANCE_Z * ACCEPTANCE_Z;
+        let center = p + z2 / (2.0 * n);
+        let margin = ACCEPTANCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        ((center - margin) / (1.0 + z2 / n)).max(0.0)
+    }
+
+    /// Get the entropy lost through rejection, `-log2(lower_bound)`.
+    pub fn entropy_loss(&self) -> Entropy {
+        Entropy(-self.lower_bound().log2())
+    }
+}
+
+/// An entropy report for a [`Scheme`](::scheme::Scheme).
+///
+/// This provides a breakdown of the entropy each component contributes to the total, along with
+/// any [warnings](Warning) found that affect how the entropy should be interpreted.
+///
+/// Construct a report using [`Scheme::entropy_report`](::scheme::Scheme::entropy_report).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EntropyReport {
+    /// The entropy each component contributes, in pipeline order.
+    pub components: Vec<ComponentEntropy>,
+
+    /// The total entropy of passphrases.
+    pub total: Entropy,
+
+    /// Warnings affecting how the entropy should be interpreted.
+    pub warnings: Vec<Warning>,
+}
+
+/// The entropy a single component contributes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ComponentEntropy {
+    /// The kind of the component.
+    pub kind: ComponentKind,
+
+    /// The index of the component within its kind.
+    pub index: usize,
+
+    /// The entropy the component contributes.
+    pub entropy: Entropy,
+}
+
+/// Calculate the exact number of ordered selections of `k` distinct items out of `n`.
+///
+/// This is `n! / (n - k)!`, which is zero if `k` is larger than `n`. See
+/// [`Entropy::from_permutations`](Entropy::from_permutations).
+///
+/// Only available with the `bigint` feature.
+#[cfg(feature = "bigint")]
+pub fn permutations(n: u64, k: u64) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    ((n - k + 1)..=n).fold(BigUint::one(), |acc, i| acc * i)
+}
+
+/// Calculate the exact number of unordered selections of `k` distinct items out of `n`.
+///
+/// This is `n! / (k! (n - k)!)`, which is zero if `k` is larger than `n`. See
+/// [`Entropy::from_combinations`](Entropy::from_combinations).
+///
+/// Only available with the `bigint` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "bigint")]
+/// # {
+/// use chbs::entropy::{combinations, Entropy};
+///
+/// let pool = combinations(7776, 5);
+/// assert_eq!("236614524158170080", pool.to_string());
+/// let entropy = Entropy::from_big_real(&pool) - Entropy::from_combinations(7776, 5);
+/// assert!(entropy.bits().abs() < 1e-9);
+/// # }
+/// ```
+#[cfg(feature = "bigint")]
+pub fn combinations(n: u64, k: u64) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    let k = k.min(n - k);
+    (1..=k).fold(BigUint::one(), |acc, i| acc * (n - k + i) / i)
+}
+
+/// The number of characters random passwords are assumed to consist of, all ASCII letters and
+/// digits.
+const RANDOM_PASSWORD_CHARS: usize = 62;
+
+/// A human-readable explanation of an [`Entropy`](Entropy).
+///
+/// Construct this using [`Entropy::explanation`](Entropy::explanation). It displays as short
+/// English text, such as:
+///
+/// ```txt
+/// ≈ 77 bits; comparable to a 13-character random password; ~240,000 years at 10¹⁰ guesses/s
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Explanation {
+    /// The number of entropy bits.
+    pub bits: f64,
+
+    /// The length of a random password of ASCII letters and digits with at least the same
+    /// entropy.
+    pub random_chars: usize,
+
+    /// The assumed number of guesses per second.
+    pub guess_rate: f64,
+
+    /// The average number of seconds to guess a passphrase, which takes half of all guesses.
+    pub seconds: f64,
+}
+
+impl Explanation {
+    /// Get the average time to guess a passphrase in the largest fitting unit.
+    pub fn duration(&self) -> (f64, TimeUnit) {
+        let units = [
+            (TimeUnit::Years, 365.25 * 24.0 * 3600.0),
+            (TimeUnit::Days, 24.0 * 3600.0),
+            (TimeUnit::Hours, 3600.0),
+            (TimeUnit::Minutes, 60.0),
+        ];
+        units
+            .iter()
+            .find(|(_, secs)| self.seconds >= *secs)
+            .map(|(unit, secs)| (self.seconds / secs, *unit))
+            .unwrap_or((self.seconds, TimeUnit::Seconds))
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (duration, unit) = self.duration();
+        write!(
+            f,
+            "≈ {:.0} bits; comparable to a {}-character random password; ~{} {} at {} guesses/s",
+            self.bits,
+            self.random_chars,
+            format_approx(duration),
+            unit,
+            format_rate(self.guess_rate),
+        )
+    }
+}
+
+/// A unit of time used in an [`Explanation`](Explanation).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TimeUnit {
+    /// Seconds.
+    Seconds,
+
+    /// Minutes.
+    Minutes,
+
+    /// Hours.
+    Hours,
+
+    /// Days.
+    Days,
+
+    /// Years of 365.25 days.
+    Years,
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            TimeUnit::Seconds => "seconds",
+            TimeUnit::Minutes => "minutes",
+            TimeUnit::Hours => "hours",
+            TimeUnit::Days => "days",
+            TimeUnit::Years => "years",
+        })
+    }
+}
+
+/// Format an approximate number rounded to two significant digits, with thousands separators.
+///
+/// Very large numbers are formatted as power of ten.
+fn format_approx(n: f64) -> String {
+    if n < 1.0 {
+        return "<1".into();
+    }
+
+    let exp = n.log10().floor() as i32;
+    if exp >= 15 {
+        return format!("{:.1} × {}", n / 10f64.powi(exp), power_of_ten(exp));
+    }
+
+    let scale = 10f64.powi((exp - 1).max(0));
+    let digits = ((n / scale).round() * scale) as u64;
+    let digits = digits.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && i % 3 == digits.len() % 3 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Format a guess rate, as power of ten if it is one.
+fn format_rate(rate: f64) -> String {
+    let exp = rate.log10().round() as i32;
+    if exp > 3 && (10f64.powi(exp) - rate).abs() < f64::EPSILON * rate {
+        power_of_ten(exp)
+    } else {
+        format_approx(rate)
+    }
+}
+
+/// Format `10` to the power of `exp`, using superscript digits.
+fn power_of_ten(exp: i32) -> String {
+    const SUPERSCRIPT: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    let mut out = String::from("10");
+    if exp < 0 {
+        out.push('⁻');
+    }
+    out.extend(
+        exp.unsigned_abs()
+            .to_string()
+            .bytes()
+            .map(|b| SUPERSCRIPT[(b - b'0') as usize]),
+    );
+    out
+}
+
+/// The number of characters in the printable ASCII symbol set, including space.
+const ASCII_SYMBOLS: usize = 33;
+
+/// The number of characters assumed for passphrases containing non-ASCII characters.
+const NON_ASCII_CHARS: usize = 100;
+
+/// A report of guess counts for a passphrase under multiple attacker models.
+///
+/// A single entropy number assumes the attacker knows exactly how passphrases are generated. This
+/// report compares that to attackers with less knowledge, so realistic numbers can be presented.
+/// Each model is expressed as [`Entropy`](Entropy), the base 2 logarithm of the number of guesses
+/// needed to exhaust the search space, see [`Entropy::guesses`](Entropy::guesses).
+///
+/// Construct a report using [`Scheme::attacker_report`](::scheme::Scheme::attacker_report).
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AttackerReport {
+    /// The attacker knows the scheme and wordlist.
+    ///
+    /// This is the entropy of the scheme, and is the number that should be relied on.
+    pub scheme_aware: Entropy,
+
+    /// The attacker brute forces all character combinations of the passphrase length.
+    ///
+    /// The character set is derived from the character classes used in the passphrase: lowercase,
+    /// uppercase, digits, ASCII symbols and non-ASCII characters. This is usually extremely
+    /// optimistic for passphrases.
+    pub brute_force: Entropy,
+
+    /// The attacker combines words from a generic dictionary and applies mangling rules.
+    ///
+    /// See [`DictionaryAttack`](DictionaryAttack) for the assumed attack.
+    pub dictionary: Entropy,
+}
+
+impl AttackerReport {
+    /// Build a report for the given `phrase` and the entropy of the scheme it was generated with.
+    ///
+    /// If the number of words is unknown, words in the phrase are counted as runs of alphabetic
+    /// characters.
+    pub(crate) fn new(
+        phrase: &str,
+        scheme_aware: Entropy,
+        words: Option<usize>,
+        attack: &DictionaryAttack,
+    ) -> Self {
+        let words = words.unwrap_or_else(|| {
+            phrase
+                .split(|c: char| !c.is_alphabetic())
+                .filter(|w| !w.is_empty())
+                .count()
+        });
+
+        Self {
+            scheme_aware,
+            brute_force: brute_force(phrase),
+            dictionary: Entropy::from_real(attack.dictionary_size as f64) * words as f64
+                + Entropy::from_real(attack.mangling_rules as f64),
+        }
+    }
+
+    /// Get the entropy under the strongest attacker, the model needing the fewest guesses.
+    pub fn weakest(&self) -> Entropy {
+        [self.brute_force, self.dictionary]
+            .iter()
+            .fold(self.scheme_aware, |a, &b| if b < a { b } else { a })
+    }
+}
+
+/// The assumed dictionary attack for an [`AttackerReport`](AttackerReport).
+///
+/// The attacker tries all combinations of the number of words in the passphrase from a generic
+/// dictionary, and applies each mangling rule to every combination. Mangling rules include
+/// changing capitalization, separators and appending characters, as used by password crackers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DictionaryAttack {
+    /// The number of words in the attacker's dictionary.
+    pub dictionary_size: usize,
+
+    /// The number of mangling rules the attacker applies.
+    pub mangling_rules: usize,
+}
+
+impl Default for DictionaryAttack {
+    /// A dictionary of the 100,000 most common words with 64 mangling rules, similar to
+    /// commonly used cracking rule sets.
+    fn default() -> Self {
+        Self {
+            dictionary_size: 100_000,
+            mangling_rules: 64,
+        }
+    }
+}
+
+/// Get the entropy of brute forcing all character combinations of the given phrase's length.
+fn brute_force(phrase: &str) -> Entropy {
+    let has = |f: fn(&char) -> bool| phrase.chars().any(|c| f(&c));
+    let charset = [
+        (has(char::is_ascii_lowercase), 26),
+        (has(char::is_ascii_uppercase), 26),
+        (has(char::is_ascii_digit), 10),
+        (
+            has(|c| c.is_ascii_punctuation() || *c == ' '),
+            ASCII_SYMBOLS,
+        ),
+        (has(|c| !c.is_ascii()), NON_ASCII_CHARS),
+    ]
+    .iter()
+    .filter(|(used, _)| *used)
+    .map(|(_, size)| size)
+    .sum::<usize>();
+
+    Entropy::from_real(charset.max(1) as f64) * phrase.chars().count() as f64
+}
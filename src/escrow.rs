@@ -0,0 +1,314 @@
+//! Stable wire format to escrow generated passphrases
+//!
+//! This module encodes a [`GeneratedPassphrase`](GeneratedPassphrase) in a versioned binary
+//! format, so recovery phrases can be escrowed and verified later. A record holds the
+//! passphrase, its words, the index of each word in the wordlist, the
+//! [fingerprint](Scheme::fingerprint) of the scheme it was generated with and, for audited
+//! passphrases, the randomness it was generated from.
+//!
+//! Decoding a record requires the scheme it was generated with. The fingerprint and word indices
+//! are checked against the scheme, and the passphrase is reconstructed from the recorded
+//! randomness if available, see [`Scheme::generate_audited`](Scheme::generate_audited). Words are
+//! matched to the wordlist ignoring case, as word stylers may change it. Schemes with a word
+//! provider that doesn't [know its words](crate::component::traits::WordSetProvider::known_words)
+//! record unknown indices, their records are only checked against the fingerprint.
+//!
+//! Records may be encrypted with a caller-provided [`EscrowCipher`](EscrowCipher), such as an
+//! authenticated cipher keyed by a key management service. Without a cipher, records hold the
+//! passphrase in plain text.
+//!
+//! # Format
+//!
+//! A record starts with the magic bytes `CHBS`, followed by the [version](VERSION) byte and a
+//! flags byte. The rest is the payload, encrypted if the lowest flag bit is set. All integers are
+//! big-endian, and strings and byte arrays are prefixed with their length as `u32`:
+//!
+//! - The scheme fingerprint, as `u64`.
+//! - The entropy in bits, as `f64`.
+//! - The passphrase.
+//! - The number of words as `u32`, followed by each word.
+//! - The number of word indices as `u32`, equal to the number of words, followed by each index as
+//!   `u32`. An unknown index is encoded as `u32::MAX`.
+//! - The recorded randomness, empty if not audited.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
+//! use chbs::{config::BasicConfig, escrow, prelude::*};
+//!
+//! let scheme = BasicConfig::default().to_scheme();
+//! let passphrase = scheme.generate_audited();
+//!
+//! let record = escrow::encode(&passphrase, &scheme, None).unwrap();
+//! let restored = escrow::decode(&record, &scheme, None).unwrap();
+//! assert_eq!(passphrase.phrase(), restored.phrase());
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use thiserror::Error;
+
+use crate::scheme::{Audit, GeneratedPassphrase, Scheme};
+
+/// The magic bytes every record starts with.
+const MAGIC: &[u8; 4] = b"CHBS";
+
+/// The flag marking an encrypted payload.
+const FLAG_ENCRYPTED: u8 = 0b0000_0001;
+
+/// The encoded value of an unknown word index.
+const UNKNOWN_INDEX: u32 = u32::MAX;
+
+/// The current version of the escrow format.
+pub const VERSION: u8 = 1;
+
+/// A cipher to encrypt escrow records with.
+///
+/// Implement this for the cipher and key of your choice. An authenticated cipher should be used,
+/// so modified records fail to decrypt.
+pub trait EscrowCipher {
+    /// Encrypt the given plaintext payload.
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8>;
+
+    /// Decrypt the given ciphertext payload.
+    ///
+    /// Returns `None` if decryption or authentication fails.
+    fn decrypt(&self, ciphertext: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// Encode the given passphrase, generated with `scheme`, as escrow record.
+///
+/// The payload is encrypted if a `cipher` is given.
+///
+/// # Errors
+///
+/// Returns an error if a word of the passphrase isn't in the wordlist of `scheme`.
+pub fn encode(
+    passphrase: &GeneratedPassphrase,
+    scheme: &Scheme,
+    cipher: Option<&dyn EscrowCipher>,
+) -> Result<Vec<u8>, EscrowError> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&scheme.fingerprint().to_be_bytes());
+    payload.extend_from_slice(&passphrase.entropy().bits().to_be_bytes());
+    write_bytes(&mut payload, passphrase.phrase().as_bytes());
+    write_len(&mut payload, passphrase.words().len());
+    for word in passphrase.words() {
+        write_bytes(&mut payload, word.as_bytes());
+    }
+    let (indices, randomness) = match passphrase.audit() {
+        Some(audit) => (audit.word_indices().to_vec(), audit.randomness()),
+        None => (resolve_indices(scheme, passphrase.words())?, &[][..]),
+    };
+    write_len(&mut payload, indices.len());
+    for index in indices {
+        let index = index.map_or(UNKNOWN_INDEX, |i| i as u32);
+        payload.extend_from_slice(&index.to_be_bytes());
+    }
+    write_bytes(&mut payload, randomness);
+
+    let (flags, payload) = match cipher {
+        Some(cipher) => (FLAG_ENCRYPTED, cipher.encrypt(&payload)),
+        None => (0, payload),
+    };
+
+    let mut record = MAGIC.to_vec();
+    record.push(VERSION);
+    record.push(flags);
+    record.extend_from_slice(&payload);
+    Ok(record)
+}
+
+/// Decode and verify the given escrow record, generated with `scheme`.
+///
+/// A `cipher` must be given if the record is encrypted. The record is verified against the
+/// scheme, and the passphrase is reconstructed from the recorded randomness if available.
+///
+/// # Errors
+///
+/// Returns an error if the record is malformed, can't be decrypted, or doesn't match the scheme.
+pub fn decode(
+    record: &[u8],
+    scheme: &Scheme,
+    cipher: Option<&dyn EscrowCipher>,
+) -> Result<GeneratedPassphrase, EscrowError> {
+    if record.len() < MAGIC.len() + 2 || &record[..MAGIC.len()] != MAGIC {
+        return Err(EscrowError::Malformed);
+    }
+    let version = record[MAGIC.len()];
+    if version != VERSION {
+        return Err(EscrowError::UnsupportedVersion(version));
+    }
+    let flags = record[MAGIC.len() + 1];
+    let payload = &record[MAGIC.len() + 2..];
+
+    let decrypted;
+    let payload = if flags & FLAG_ENCRYPTED != 0 {
+        let cipher = cipher.ok_or(EscrowError::MissingCipher)?;
+        decrypted = cipher.decrypt(payload).ok_or(EscrowError::Decrypt)?;
+        &decrypted[..]
+    } else {
+        payload
+    };
+
+    let mut reader = Reader(payload);
+    let fingerprint = u64::from_be_bytes(reader.array()?);
+    if fingerprint != scheme.fingerprint() {
+        return Err(EscrowError::SchemeMismatch);
+    }
+    // The entropy is informational for other readers, it may be measured and is not checked
+    let _entropy = f64::from_be_bytes(reader.array()?);
+    let phrase = reader.string()?;
+    let words = (0..reader.len()?)
+        .map(|_| reader.string())
+        .collect::<Result<Vec<_>, _>>()?;
+    let word_indices = (0..reader.len()?)
+        .map(|_| {
+            let index = u32::from_be_bytes(reader.array()?);
+            Ok(if index == UNKNOWN_INDEX {
+                None
+            } else {
+                Some(index as usize)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let randomness = reader.bytes()?.to_vec();
+    if !reader.0.is_empty() || word_indices.len() != words.len() {
+        return Err(EscrowError::Malformed);
+    }
+
+    let audit = if randomness.is_empty() {
+        // Without randomness to replay, the words must match the recorded indices
+        match resolve_indices(scheme, &words) {
+            Ok(indices) if indices == word_indices => {}
+            _ => return Err(EscrowError::Mismatch),
+        }
+        None
+    } else {
+        Some(Audit {
+            randomness,
+            word_indices,
+        })
+    };
+    let passphrase = scheme.passphrase_from_parts(phrase, words, audit);
+
+    // Audited passphrases must be reconstructed from the recorded randomness
+    if let Some(audit) = passphrase.audit() {
+        match scheme.replay_audited(audit.randomness()) {
+            Some(replayed) if replayed == passphrase => {}
+            _ => return Err(EscrowError::Mismatch),
+        }
+    }
+
+    Ok(passphrase)
+}
+
+/// Resolve the index of each of the given words in the known wordlist of `scheme`.
+///
+/// Words are matched ignoring case, to the first matching word in the wordlist. All indices are
+/// unknown if the scheme doesn't know its words.
+fn resolve_indices(scheme: &Scheme, words: &[String]) -> Result<Vec<Option<usize>>, EscrowError> {
+    let known = match scheme.known_words() {
+        Some(known) => known,
+        None => return Ok(vec![None; words.len()]),
+    };
+    let mut indices = HashMap::with_capacity(known.len());
+    for (i, word) in known.into_iter().enumerate() {
+        indices.entry(word.to_lowercase()).or_insert(i);
+    }
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| match indices.get(&word.to_lowercase()) {
+            Some(&i) => Ok(Some(i)),
+            None => Err(EscrowError::UnknownWord { index }),
+        })
+        .collect()
+}
+
+/// Write the given length prefix.
+fn write_len(buf: &mut Vec<u8>, len: usize) {
+    buf.extend_from_slice(&(len as u32).to_be_bytes());
+}
+
+/// Write the given bytes, prefixed with their length.
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_len(buf, bytes.len());
+    buf.extend_from_slice(bytes);
+}
+
+/// A reader over a record payload.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// Read the next `len` bytes.
+    fn take(&mut self, len: usize) -> Result<&'a [u8], EscrowError> {
+        if self.0.len() < len {
+            return Err(EscrowError::Malformed);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    /// Read a fixed size array.
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], EscrowError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    /// Read a length prefix.
+    fn len(&mut self) -> Result<usize, EscrowError> {
+        Ok(u32::from_be_bytes(self.array()?) as usize)
+    }
+
+    /// Read bytes prefixed with their length.
+    fn bytes(&mut self) -> Result<&'a [u8], EscrowError> {
+        let len = self.len()?;
+        self.take(len)
+    }
+
+    /// Read a string prefixed with its length.
+    fn string(&mut self) -> Result<String, EscrowError> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| EscrowError::Malformed)
+    }
+}
+
+/// An error decoding an escrow record.
+#[derive(Error, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EscrowError {
+    /// The record is not a valid escrow record.
+    #[error("malformed escrow record")]
+    Malformed,
+
+    /// The record uses an unsupported format version.
+    #[error("unsupported escrow record version {0}")]
+    UnsupportedVersion(u8),
+
+    /// The record is encrypted, but no cipher was given.
+    #[error("escrow record is encrypted, but no cipher was given")]
+    MissingCipher,
+
+    /// The record could not be decrypted.
+    #[error("failed to decrypt escrow record")]
+    Decrypt,
+
+    /// The record was generated with a different scheme.
+    #[error("escrow record was generated with a different scheme")]
+    SchemeMismatch,
+
+    /// The passphrase or its words don't match the recorded randomness and word indices.
+    #[error("escrow record passphrase doesn't match its recorded randomness or word indices")]
+    Mismatch,
+
+    /// A word of the passphrase to encode isn't in the wordlist of the scheme.
+    #[error("passphrase word #{index} is not in the wordlist of the scheme")]
+    UnknownWord {
+        /// The index of the word in the passphrase.
+        index: usize,
+    },
+}
@@ -0,0 +1,669 @@
+//! Guards rejecting unwanted passphrases
+//!
+//! This module provides types wrapping a [`Scheme`](crate::scheme::Scheme), regenerating
+//! passphrases that must not be issued:
+//!
+//! - [`UniqueGuard`](UniqueGuard): guarantees that no passphrase is issued twice within the same
+//!   session. This is useful for interactive tools repeatedly offering a "next suggestion".
+//! - [`Guard`](Guard): rejects passphrases not accepted by a [`GuardPredicate`](GuardPredicate).
+//!   The following guards use a built-in predicate:
+//!   - [`BanGuard`](BanGuard): rejects passphrases containing a term from a user-supplied
+//!     [`BanList`](BanList), such as a company name, product names or previously issued phrases.
+//!   - [`FrequencyGuard`](FrequencyGuard): caps how often any single word appears across a batch
+//!     of passphrases, so credentials issued to one organization don't look alike.
+//!   - [`LengthGuard`](LengthGuard): keeps the length of passphrases within bounds, for systems
+//!     limiting the length of passwords.
+//!
+//! Each guard bounds its attempts to generate an acceptable passphrase by
+//! [`GenerationLimits`](GenerationLimits), set through `with_limits`. When exceeded, an error
+//! naming the guard and the [exceeded limit](LimitExceeded) is returned. If the scheme itself
+//! fails to generate a passphrase, its [`RejectedError`](RejectedError) is returned.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::config::BasicConfig;
+use crate::entropy::{Acceptance, Entropy};
+use crate::limits::{GenerationLimits, LimitExceeded};
+use crate::prelude::*;
+use crate::scheme::{GeneratedPassphrase, RejectedError, Scheme};
+
+/// The maximum number of words tried by [`LengthGuard::fit_words`](LengthGuard::fit_words).
+const FIT_MAX_WORDS: usize = 32;
+
+/// The minimum acceptance rate of a number of words chosen by
+/// [`LengthGuard::fit_words`](LengthGuard::fit_words).
+const FIT_MIN_ACCEPTANCE: f64 = 0.25;
+
+/// A guard ensuring generated passphrases are unique within a session.
+///
+/// This wraps a [`Scheme`](Scheme) and remembers all passphrases it has issued. When a newly
+/// generated passphrase collides with a previously issued one, it is regenerated. The number of
+/// attempts is bounded, and an error is returned when no unique passphrase could be generated.
+///
+/// Issued passphrases are kept in memory for the lifetime of the guard.
+///
+/// The guard implements `Iterator`, which stops when no unique passphrase could be generated. Use
+/// [`try_next`](UniqueGuard::try_next) to obtain the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{config::BasicConfig, guard::UniqueGuard, prelude::*};
+///
+/// let mut guard = UniqueGuard::new(BasicConfig::default().to_scheme());
+///
+/// let first = guard.try_next().unwrap();
+/// let second = guard.try_next().unwrap();
+/// assert_ne!(first, second);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct UniqueGuard {
+    /// The scheme used to generate passphrases.
+    scheme: Scheme,
+
+    /// All passphrases issued in this session.
+    issued: HashSet<String>,
+
+    /// The limits on attempts to generate a unique passphrase.
+    limits: GenerationLimits,
+}
+
+impl UniqueGuard {
+    /// Construct a uniqueness guard for the given scheme.
+    pub fn new(scheme: Scheme) -> Self {
+        Self {
+            scheme,
+            issued: HashSet::new(),
+            limits: GenerationLimits::default(),
+        }
+    }
+
+    /// Set the maximum number of attempts to generate a unique passphrase.
+    ///
+    /// # Panics
+    ///
+    /// `max_attempts` must be higher than zero.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        if max_attempts == 0 {
+            panic!("cannot construct UniqueGuard with zero attempts");
+        }
+
+        self.limits.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the limits on attempts to generate a passphrase.
+    pub fn with_limits(mut self, limits: GenerationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Generate a passphrase that has not been issued before in this session.
+    ///
+    /// An error is returned if no unique passphrase was generated within the limits, or if the
+    /// scheme failed to generate a passphrase.
+    pub fn try_next(&mut self) -> Result<String, UniqueGuardError> {
+        let mut attempts = self.limits.start();
+        loop {
+            attempts.next().map_err(UniqueGuardError::Exhausted)?;
+            let phrase = self.scheme.try_generate()?;
+            if !self.issued.contains(&phrase) {
+                self.issued.insert(phrase.clone());
+                return Ok(phrase);
+            }
+        }
+    }
+
+    /// Get the number of passphrases issued in this session.
+    pub fn issued(&self) -> usize {
+        self.issued.len()
+    }
+
+    /// Forget all passphrases issued in this session.
+    pub fn clear(&mut self) {
+        self.issued.clear();
+    }
+
+    /// Get the scheme used to generate passphrases.
+    pub fn scheme(&self) -> &Scheme {
+        &self.scheme
+    }
+}
+
+impl Iterator for UniqueGuard {
+    type Item = String;
+
+    /// Generate a passphrase that has not been issued before in this session.
+    ///
+    /// This returns `None` if no unique passphrase could be generated.
+    fn next(&mut self) -> Option<String> {
+        self.try_next().ok()
+    }
+}
+
+/// A [`UniqueGuard`](UniqueGuard) error.
+#[derive(Error, Debug)]
+pub enum UniqueGuardError {
+    /// No unique passphrase was generated within the limits.
+    #[error("failed to generate unique passphrase {0}")]
+    Exhausted(LimitExceeded),
+
+    /// The scheme failed to generate a passphrase.
+    #[error("failed to generate passphrase: {0}")]
+    Rejected(#[from] RejectedError),
+}
+
+/// A list of terms that must not appear in passphrases.
+///
+/// A passphrase is banned if it contains any of the terms, ignoring case. Terms are matched
+/// anywhere in the passphrase, so a short term may ban many passphrases. Previously issued
+/// passphrases may be added as terms to prevent them from being issued again.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::guard::BanList;
+///
+/// let list = BanList::new(vec!["Acme".into(), "roadrunner".into()]);
+/// assert!(list.is_banned("correct acme horse"));
+/// assert!(!list.is_banned("correct horse battery staple"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BanList {
+    /// The banned terms, in lowercase.
+    terms: HashSet<String>,
+}
+
+impl BanList {
+    /// Construct a ban list with the given terms.
+    ///
+    /// Empty terms are ignored.
+    pub fn new(terms: Vec<String>) -> Self {
+        let mut list = Self::default();
+        for term in terms {
+            list.insert(&term);
+        }
+        list
+    }
+
+    /// Load a ban list from the file at the given `path`.
+    ///
+    /// The file must contain one term on each line. Leading and trailing whitespace is trimmed,
+    /// empty lines and lines starting with `#` are skipped.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let mut list = Self::default();
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .for_each(|term| list.insert(term));
+        Ok(list)
+    }
+
+    /// Add the given term to this list.
+    ///
+    /// Empty terms are ignored.
+    pub fn insert(&mut self, term: &str) {
+        if !term.is_empty() {
+            self.terms.insert(term.to_lowercase());
+        }
+    }
+
+    /// Check whether the given passphrase contains a banned term.
+    pub fn is_banned(&self, phrase: &str) -> bool {
+        let phrase = phrase.to_lowercase();
+        self.terms.iter().any(|term| phrase.contains(term.as_str()))
+    }
+
+    /// Get the number of banned terms.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Check whether this list has no banned terms.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+/// A predicate deciding which generated passphrases a [`Guard`](Guard) issues.
+///
+/// This is implemented for [`BanList`](BanList), [`WordFrequency`](WordFrequency), ranges of
+/// passphrase lengths in characters, and closures taking a
+/// [`GeneratedPassphrase`](GeneratedPassphrase).
+pub trait GuardPredicate {
+    /// Check whether the given passphrase may be issued.
+    ///
+    /// A passphrase is issued when this returns `true`, so the predicate may record it.
+    fn accept(&mut self, passphrase: &GeneratedPassphrase) -> bool;
+}
+
+impl<F> GuardPredicate for F
+where
+    F: FnMut(&GeneratedPassphrase) -> bool,
+{
+    fn accept(&mut self, passphrase: &GeneratedPassphrase) -> bool {
+        self(passphrase)
+    }
+}
+
+impl GuardPredicate for BanList {
+    fn accept(&mut self, passphrase: &GeneratedPassphrase) -> bool {
+        !self.is_banned(passphrase.phrase())
+    }
+}
+
+impl GuardPredicate for RangeInclusive<usize> {
+    fn accept(&mut self, passphrase: &GeneratedPassphrase) -> bool {
+        self.validate_phrase(passphrase.phrase()).is_ok()
+    }
+}
+
+/// A guard rejecting passphrases that its predicate doesn't accept.
+///
+/// This wraps a [`Scheme`](Scheme) and regenerates passphrases rejected by the given
+/// [`GuardPredicate`](GuardPredicate). The number of attempts is bounded, and an error is
+/// returned when no acceptable passphrase could be generated. Rejecting passphrases lowers their
+/// entropy, see the built-in [`BanGuard`](BanGuard), [`FrequencyGuard`](FrequencyGuard) and
+/// [`LengthGuard`](LengthGuard).
+///
+/// The guard implements `Iterator`, which stops when no acceptable passphrase could be
+/// generated. Use [`try_next`](Guard::try_next) to obtain the error instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{config::BasicConfig, guard::Guard, prelude::*, scheme::GeneratedPassphrase};
+///
+/// let scheme = BasicConfig::default().to_scheme();
+/// let mut guard = Guard::new(scheme, |p: &GeneratedPassphrase| p.words()[0].len() > 5);
+///
+/// let phrase = guard.try_next().unwrap();
+/// assert!(phrase.split(' ').next().unwrap().len() > 5);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Guard<P> {
+    /// The scheme used to generate passphrases.
+    scheme: Scheme,
+
+    /// The predicate deciding which passphrases are issued.
+    predicate: P,
+
+    /// The limits on attempts to generate an acceptable passphrase.
+    limits: GenerationLimits,
+}
+
+impl<P> Guard<P>
+where
+    P: GuardPredicate,
+{
+    /// Construct a guard for the given scheme, issuing passphrases accepted by `predicate`.
+    pub fn new(scheme: Scheme, predicate: P) -> Self {
+        Self {
+            scheme,
+            predicate,
+            limits: GenerationLimits::default(),
+        }
+    }
+
+    /// Set the maximum number of attempts to generate an acceptable passphrase.
+    ///
+    /// # Panics
+    ///
+    /// `max_attempts` must be higher than zero.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        if max_attempts == 0 {
+            panic!("cannot construct Guard with zero attempts");
+        }
+
+        self.limits.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the limits on attempts to generate a passphrase.
+    pub fn with_limits(mut self, limits: GenerationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Generate a passphrase accepted by the predicate.
+    ///
+    /// An error is returned if no acceptable passphrase was generated within the limits, or if
+    /// the scheme failed to generate a passphrase.
+    pub fn try_next(&mut self) -> Result<String, GuardError> {
+        let mut attempts = self.limits.start();
+        loop {
+            attempts.next().map_err(GuardError::Exhausted)?;
+            let passphrase = self.scheme.try_generate_detailed()?;
+            if self.predicate.accept(&passphrase) {
+                return Ok(passphrase.into_phrase());
+            }
+        }
+    }
+
+    /// Generate a batch of `n` passphrases accepted by the predicate.
+    ///
+    /// An error is returned if any passphrase could not be generated.
+    pub fn generate_many(&mut self, n: usize) -> Result<Vec<String>, GuardError> {
+        (0..n).map(|_| self.try_next()).collect()
+    }
+
+    /// Get the predicate deciding which passphrases are issued.
+    pub fn predicate(&self) -> &P {
+        &self.predicate
+    }
+
+    /// Get the predicate deciding which passphrases are issued, mutably.
+    ///
+    /// This allows changing the predicate while the guard is in use, for example to ban each
+    /// issued passphrase.
+    pub fn predicate_mut(&mut self) -> &mut P {
+        &mut self.predicate
+    }
+
+    /// Get the scheme used to generate passphrases.
+    pub fn scheme(&self) -> &Scheme {
+        &self.scheme
+    }
+}
+
+impl<P> Iterator for Guard<P>
+where
+    P: GuardPredicate,
+{
+    type Item = String;
+
+    /// Generate a passphrase accepted by the predicate.
+    ///
+    /// This returns `None` if no acceptable passphrase could be generated.
+    fn next(&mut self) -> Option<String> {
+        self.try_next().ok()
+    }
+}
+
+/// A [`Guard`](Guard) error.
+#[derive(Error, Debug)]
+pub enum GuardError {
+    /// No acceptable passphrase was generated within the limits.
+    #[error("failed to generate acceptable passphrase {0}")]
+    Exhausted(LimitExceeded),
+
+    /// The scheme failed to generate a passphrase.
+    #[error("failed to generate passphrase: {0}")]
+    Rejected(#[from] RejectedError),
+
+    /// No number of words produces passphrases of acceptable length, see
+    /// [`LengthGuard::fit_words`](Guard::fit_words).
+    #[error("no number of words produces passphrases of acceptable length")]
+    Unsatisfiable,
+}
+
+/// A guard rejecting passphrases that contain banned terms.
+///
+/// This regenerates passphrases containing a term from the given [`BanList`](BanList).
+///
+/// Rejecting passphrases slightly lowers their entropy. This is negligible unless the ban list
+/// rejects a large part of all passphrases, which is reported through the attempt limit.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{config::BasicConfig, guard::{BanGuard, BanList}, prelude::*};
+///
+/// let list = BanList::new(vec!["acme".into()]);
+/// let mut guard = BanGuard::new(BasicConfig::default().to_scheme(), list);
+///
+/// let phrase = guard.try_next().unwrap();
+/// assert!(!phrase.to_lowercase().contains("acme"));
+/// # }
+/// ```
+pub type BanGuard = Guard<BanList>;
+
+impl Guard<BanList> {
+    /// Estimate the entropy of passphrases issued by this guard.
+    ///
+    /// This is the entropy of the scheme, lowered by the rate of passphrases rejected for
+    /// containing a banned term. The rejection rate is measured over the given number of
+    /// `samples`, see [`Scheme::measure_acceptance`](Scheme::measure_acceptance).
+    pub fn entropy(&self, samples: usize) -> Entropy {
+        let acceptance = self
+            .scheme
+            .measure_acceptance(samples, |phrase| !self.predicate.is_banned(phrase));
+        self.scheme.entropy().with_acceptance(&acceptance)
+    }
+}
+
+/// A cap on how often any single word appears across issued passphrases.
+///
+/// Words are compared ignoring case. A passphrase is accepted if none of its words would exceed
+/// the cap, see [`FrequencyGuard`](FrequencyGuard).
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::guard::WordFrequency;
+///
+/// let frequency = WordFrequency::new(3);
+/// assert_eq!(0, frequency.word_count("acme"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordFrequency {
+    /// The maximum number of times a word may be issued.
+    max_per_word: usize,
+
+    /// The number of times each word was issued, in lowercase.
+    counts: HashMap<String, usize>,
+}
+
+impl WordFrequency {
+    /// Construct a cap issuing each word at most `max_per_word` times.
+    ///
+    /// # Panics
+    ///
+    /// `max_per_word` must be higher than zero.
+    pub fn new(max_per_word: usize) -> Self {
+        if max_per_word == 0 {
+            panic!("cannot construct WordFrequency with a cap of zero");
+        }
+
+        Self {
+            max_per_word,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Get the number of times the given word was issued, ignoring case.
+    pub fn word_count(&self, word: &str) -> usize {
+        self.counts.get(&word.to_lowercase()).copied().unwrap_or(0)
+    }
+
+    /// Forget all issued words, to start a new batch.
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl GuardPredicate for WordFrequency {
+    fn accept(&mut self, passphrase: &GeneratedPassphrase) -> bool {
+        let words: Vec<String> = passphrase
+            .words()
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect();
+
+        // Count words within the passphrase as well, as a word may repeat in it
+        let mut added: HashMap<&str, usize> = HashMap::new();
+        for word in &words {
+            *added.entry(word).or_default() += 1;
+        }
+        let allowed = added
+            .iter()
+            .all(|(word, n)| self.counts.get(*word).unwrap_or(&0) + n <= self.max_per_word);
+
+        if allowed {
+            for (word, n) in added {
+                *self.counts.entry(word.to_owned()).or_default() += n;
+            }
+        }
+        allowed
+    }
+}
+
+/// A guard capping how often any single word appears across issued passphrases.
+///
+/// When issuing many passphrases at once, such as for all members of an organization, some words
+/// are bound to repeat, which makes credentials look eerily similar. This regenerates
+/// passphrases containing a word that was already issued the maximum number of times, see
+/// [`WordFrequency`](WordFrequency). Word counts are kept across batches, clear them through
+/// [`predicate_mut`](Guard::predicate_mut) to start a new batch.
+///
+/// # Entropy
+///
+/// Rejecting passphrases lowers their entropy, as words at the cap can no longer be chosen. After
+/// issuing `n` passphrases of `k` words with a cap of `c`, at most `n × k / c` words are at the
+/// cap. For a wordlist of `N` words, each word then has at least `log2(N - n × k / c)` bits of
+/// entropy instead of `log2(N)`. This is negligible for large wordlists and a reasonable cap, for
+/// example 1000 passphrases of 5 words capped at 3 leave at least 12.5 of 12.9 bits per word
+/// with the EFF large wordlist. A low cap on a large batch quickly exhausts the wordlist, which
+/// is reported through the attempt limit.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{
+///     config::BasicConfig,
+///     guard::{FrequencyGuard, WordFrequency},
+///     prelude::*,
+/// };
+///
+/// let mut guard = FrequencyGuard::new(BasicConfig::default().to_scheme(), WordFrequency::new(1));
+/// let phrases = guard.generate_many(100).unwrap();
+///
+/// // No word is used twice in the batch
+/// let mut words: Vec<String> = phrases
+///     .iter()
+///     .flat_map(|p| p.split(' ').map(str::to_lowercase))
+///     .collect();
+/// words.sort();
+/// words.dedup();
+/// assert_eq!(500, words.len());
+/// # }
+/// ```
+pub type FrequencyGuard = Guard<WordFrequency>;
+
+/// A guard keeping the length of passphrases within bounds.
+///
+/// Many systems limit the length of passwords, and some require a minimum length. This
+/// regenerates passphrases with a length in characters outside the given range.
+///
+/// To pick the number of words of a [`BasicConfig`](BasicConfig) best fitting the range, use
+/// [`fit_words`](Guard::fit_words).
+///
+/// # Entropy
+///
+/// Rejecting passphrases lowers their entropy, as rejected passphrases are never issued. Unlike
+/// for a [`BanGuard`](BanGuard) this is significant when the range is tight, estimate it using
+/// [`entropy`](Guard::entropy).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{config::BasicConfig, guard::LengthGuard, prelude::*};
+///
+/// let scheme = BasicConfig::default().with_words(4).to_scheme();
+/// let mut guard = LengthGuard::new(scheme, 16..=32);
+///
+/// let phrase = guard.try_next().unwrap();
+/// assert!((16..=32).contains(&phrase.chars().count()));
+/// assert!(guard.entropy(1000) < guard.scheme().entropy());
+/// # }
+/// ```
+pub type LengthGuard = Guard<RangeInclusive<usize>>;
+
+impl Guard<RangeInclusive<usize>> {
+    /// Construct a length guard for the given configuration, adjusting its number of words.
+    ///
+    /// Each number of words is tried, and the one giving the most [entropy](Guard::entropy)
+    /// within the length range is chosen. The acceptance rate of each is measured over the given
+    /// number of `samples`. Numbers of words of which less than a quarter of the passphrases are
+    /// accepted are skipped, as generating them would often be exhausted. An error is returned
+    /// if no number of words produces passphrases of acceptable length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, guard::LengthGuard, prelude::*};
+    ///
+    /// let guard = LengthGuard::fit_words(&BasicConfig::default(), 20..=32, 1000).unwrap();
+    /// assert!(guard.entropy(1000).bits() > 30.0);
+    /// # }
+    /// ```
+    pub fn fit_words<P>(
+        config: &BasicConfig<P>,
+        length: RangeInclusive<usize>,
+        samples: usize,
+    ) -> Result<Self, GuardError>
+    where
+        P: WordProvider + 'static,
+    {
+        let mut best: Option<(Entropy, LengthGuard)> = None;
+        for words in 1..=FIT_MAX_WORDS {
+            let guard = Self::new(config.clone().with_words(words).to_scheme(), length.clone());
+            let acceptance = guard.acceptance(samples);
+
+            // Longer passphrases won't fit either once they stop fitting after some did
+            if acceptance.rate() < FIT_MIN_ACCEPTANCE {
+                if best.is_some() {
+                    break;
+                }
+                continue;
+            }
+
+            let entropy = guard.scheme.entropy().with_acceptance(&acceptance);
+            if !matches!(&best, Some((e, _)) if *e >= entropy) {
+                best = Some((entropy, guard));
+            }
+        }
+        best.map(|(_, guard)| guard)
+            .ok_or(GuardError::Unsatisfiable)
+    }
+
+    /// Estimate the entropy of passphrases issued by this guard.
+    ///
+    /// This is the entropy of the scheme, lowered by the rate of passphrases rejected for their
+    /// length. The rejection rate is measured over the given number of `samples`, see
+    /// [`Scheme::measure_acceptance`](Scheme::measure_acceptance).
+    pub fn entropy(&self, samples: usize) -> Entropy {
+        self.scheme
+            .entropy()
+            .with_acceptance(&self.acceptance(samples))
+    }
+
+    /// Measure the acceptance rate over the given number of `samples`.
+    fn acceptance(&self, samples: usize) -> Acceptance {
+        self.scheme.measure_acceptance(samples, |phrase| {
+            self.predicate.validate_phrase(phrase).is_ok()
+        })
+    }
+}
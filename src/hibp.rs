@@ -0,0 +1,211 @@
+//! Screening passphrases against the Have I Been Pwned breach corpus
+//!
+//! Some organizations mandate that passwords are screened against known breach corpora. This
+//! module provides [`BreachChecker`](BreachChecker) implementations for the
+//! [Have I Been Pwned](https://haveibeenpwned.com/Passwords) Pwned Passwords corpus:
+//!
+//! - [`RangeClient`](RangeClient): queries the online range API. It uses the k-anonymity model,
+//!   only the first 5 characters of the SHA-1 hash of a passphrase are sent, the passphrase
+//!   itself never leaves the machine.
+//! - [`HashFile`](HashFile): checks against a downloaded hash file, for offline environments.
+//!
+//! Use [`Scheme::generate_unpwned`](crate::scheme::Scheme::generate_unpwned) to generate a
+//! passphrase that is not in the corpus.
+//!
+//! Randomly generated passphrases with sufficient entropy are extremely unlikely to appear in a
+//! breach corpus, so this is mainly useful to satisfy policy. Rejecting breached passphrases
+//! lowers the entropy by a negligible amount.
+//!
+//! Only available with the `hibp` feature.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
+//! use chbs::{config::BasicConfig, hibp::RangeClient, prelude::*};
+//!
+//! let scheme = BasicConfig::default().to_scheme();
+//! let passphrase = scheme.generate_unpwned(&RangeClient::new()).unwrap();
+//! println!("Passphrase: {}", passphrase);
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use thiserror::Error;
+
+/// The default endpoint of the Pwned Passwords range API.
+pub const DEFAULT_ENDPOINT: &str = "https://api.pwnedpasswords.com/range/";
+
+/// The number of hash characters sent to the range API.
+const PREFIX_LEN: usize = 5;
+
+/// Something that checks whether a passphrase appears in a breach corpus.
+pub trait BreachChecker {
+    /// Check whether the given `phrase` appears in the breach corpus.
+    fn is_pwned(&self, phrase: &str) -> Result<bool, HibpError>;
+}
+
+/// A client for the Pwned Passwords range API.
+///
+/// For each check, the first 5 characters of the SHA-1 hash of the passphrase are sent to the
+/// API, which responds with all hash suffixes in the corpus sharing that prefix. The suffixes are
+/// then matched locally. Responses are padded to hide the number of results.
+#[derive(Debug, Clone)]
+pub struct RangeClient {
+    /// The range API endpoint, to which the hash prefix is appended.
+    endpoint: String,
+}
+
+impl RangeClient {
+    /// Construct a client for the default [endpoint](DEFAULT_ENDPOINT).
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT.into())
+    }
+
+    /// Construct a client for a custom range API `endpoint`, such as a self-hosted mirror.
+    ///
+    /// The hash prefix is appended to the endpoint for each request.
+    pub fn with_endpoint(endpoint: String) -> Self {
+        Self { endpoint }
+    }
+}
+
+impl Default for RangeClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BreachChecker for RangeClient {
+    fn is_pwned(&self, phrase: &str) -> Result<bool, HibpError> {
+        let hash = sha1_hex(phrase);
+        let (prefix, suffix) = hash.split_at(PREFIX_LEN);
+
+        let body = ureq::get(&format!("{}{}", self.endpoint, prefix))
+            .set("Add-Padding", "true")
+            .call()
+            .map_err(|err| HibpError::Request(Box::new(err)))?
+            .into_string()?;
+
+        for line in body.lines() {
+            let (found, count) = parse_line(line).ok_or(HibpError::InvalidResponse)?;
+
+            // Padding entries have a count of zero
+            if found.eq_ignore_ascii_case(suffix) && count > 0 {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+/// An offline set of breached SHA-1 hashes.
+///
+/// This loads a Pwned Passwords hash file as downloaded from Have I Been Pwned, with lines in the
+/// `HASH:COUNT` format, or a file with just a hash on each line. All hashes are held in memory,
+/// so a subset of the full corpus may be preferred.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::hibp::{BreachChecker, HashFile};
+///
+/// let path = std::env::temp_dir().join("chbs-hibp-hashes.txt");
+/// std::fs::write(&path, "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\n").unwrap();
+///
+/// let hashes = HashFile::load(&path).unwrap();
+/// assert!(hashes.is_pwned("password").unwrap());
+/// assert!(!hashes.is_pwned("correct horse battery staple").unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HashFile {
+    /// The set of uppercase hexadecimal SHA-1 hashes.
+    hashes: HashSet<String>,
+}
+
+impl HashFile {
+    /// Load a hash file from the given `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, HibpError> {
+        let mut hashes = HashSet::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (hash, _) = parse_line(&line).ok_or(HibpError::InvalidHashFile)?;
+            hashes.insert(hash.to_ascii_uppercase());
+        }
+        Ok(Self { hashes })
+    }
+
+    /// The number of hashes in this file.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Whether this file holds no hashes.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+impl BreachChecker for HashFile {
+    fn is_pwned(&self, phrase: &str) -> Result<bool, HibpError> {
+        Ok(self.hashes.contains(&sha1_hex(phrase)))
+    }
+}
+
+/// Get the uppercase hexadecimal SHA-1 hash of the given `phrase`.
+fn sha1_hex(phrase: &str) -> String {
+    sha1_smol::Sha1::from(phrase)
+        .digest()
+        .to_string()
+        .to_ascii_uppercase()
+}
+
+/// Parse a `HASH:COUNT` or `HASH` line into its hash and count.
+///
+/// A missing count is parsed as `1`.
+fn parse_line(line: &str) -> Option<(&str, u64)> {
+    let line = line.trim();
+    let mut parts = line.splitn(2, ':');
+    let hash = parts.next()?;
+    if hash.is_empty() || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let count = match parts.next() {
+        Some(count) => count.trim().parse().ok()?,
+        None => 1,
+    };
+    Some((hash, count))
+}
+
+/// An error screening a passphrase.
+#[derive(Debug, Error)]
+pub enum HibpError {
+    /// The request to the range API failed.
+    #[error("failed to query Pwned Passwords range API")]
+    Request(#[source] Box<ureq::Error>),
+
+    /// The range API responded with data that could not be parsed.
+    #[error("invalid response from Pwned Passwords range API")]
+    InvalidResponse,
+
+    /// Failed to read a response or hash file.
+    #[error("failed to read breach data")]
+    Io(#[from] io::Error),
+
+    /// A hash file contained a line that is not a hash.
+    #[error("invalid line in hash file")]
+    InvalidHashFile,
+
+    /// No passphrase outside the breach corpus was generated within the attempt limit.
+    #[error("failed to generate unbreached passphrase after {0} attempts")]
+    Exhausted(usize),
+}
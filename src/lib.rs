@@ -19,17 +19,21 @@
 //! - A [`Scheme`](scheme::Scheme) defines how a passphrase is generated. Passphrases are only
 //!   generated through a scheme.
 //! - A [`Scheme`](scheme::Scheme) contains components which represents how the passphrase is built
-//!   up and styled. Four kinds of components exist, defining the passphrase generation
+//!   up and styled. Six kinds of components exist, defining the passphrase generation
 //!   steps. For some kinds one must be defined,
 //!   for other kinds any number is fine:
 //!     1.  [`WordSetProvider`](component::traits::WordSetProvider) (`1` required):
 //!         provides a list of words to use in a passphrase.
-//!     2.  [`WordStyler`](component::traits::WordStyler) (`>=0` required):
+//!     2.  [`WordFilter`](component::traits::WordFilter) (`>=0` required):
+//!         vetoes or replaces words of the wordlist, for example to exclude profanity.
+//!     3.  [`WordStyler`](component::traits::WordStyler) (`>=0` required):
 //!         styles passphrase words, for example, to capitalize.
-//!     3.  [`PhraseBuilder`](component::traits::PhraseBuilder) (`1` required):
+//!     4.  [`PhraseBuilder`](component::traits::PhraseBuilder) (`1` required):
 //!         builds a phrase from a set of passphrase words.
-//!     4.  [`PhraseStyler`](component::traits::PhraseBuilder) (`>=0` required):
+//!     5.  [`PhraseStyler`](component::traits::PhraseBuilder) (`>=0` required):
 //!         styles a whole passphrase.
+//!     6.  [`PhraseValidator`](component::traits::PhraseValidator) (`>=0` required):
+//!         rejects passphrases not meeting requirements, which are then regenerated.
 //!
 //! Things to understand:
 //! - Passphrase generation schemes are commonly created by using a configuration
@@ -64,15 +68,20 @@
 //! library defaults ([src][example_passphrase]):
 //!
 //! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
 //! use chbs::passphrase;
 //!
 //! println!("Passphrase: {:?}", passphrase());
+//! # }
 //! ```
 //!
 //! Generating a passphrase with configuration is recommended, here is a basic
 //! example ([src][example_passphrase_config]):
 //!
 //! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
 //! use chbs::{config::BasicConfig, prelude::*, probability::Probability};
 //!
 //! // Build a custom configuration to:
@@ -85,6 +94,7 @@
 //!
 //! println!("Passphrase: {:?}", scheme.generate());
 //! println!("Entropy: {:?}", scheme.entropy().bits());
+//! # }
 //! ```
 //!
 //! More examples are available in the documentation throughout the crate,
@@ -106,15 +116,45 @@
 extern crate derive_builder;
 extern crate rand;
 
+#[cfg(feature = "wordlist-eff-large")]
 use crate::config::BasicConfig;
+#[cfg(feature = "wordlist-eff-large")]
 use crate::prelude::*;
 
+// The Python bindings expose the default configuration
+#[cfg(all(feature = "pyo3", not(feature = "wordlist-eff-large")))]
+compile_error!("the pyo3 feature requires the wordlist-eff-large feature");
+
+#[cfg(feature = "bip39")]
+pub mod bip39;
+pub mod charset;
 pub mod component;
 pub mod config;
+#[cfg(feature = "derived")]
+pub mod derived;
+pub mod encode;
 pub mod entropy;
+pub mod escrow;
+pub mod guard;
+#[cfg(feature = "hibp")]
+pub mod hibp;
+pub mod limits;
+pub mod locale;
+pub mod policy;
 pub mod prelude;
 pub mod probability;
+#[cfg(feature = "pyo3")]
+pub mod python;
+pub mod registry;
+pub mod render;
+pub mod rng;
 pub mod scheme;
+pub mod score;
+#[cfg(feature = "zeroize")]
+pub mod secret;
+pub mod template;
+pub mod testvectors;
+pub mod validate;
 pub mod word;
 
 /// The default number of words the passphrase will consist of.
@@ -141,6 +181,8 @@ const DEFAULT_SEPARATOR: &str = " ";
 /// passphrases it is recommended to build a [`Scheme`](scheme::Scheme) instead as it's much more
 /// performant.
 ///
+/// Only available with the `wordlist-eff-large` feature, enabled by default.
+///
 /// # Entropy
 ///
 /// To figure out what entropy these passphrases have, use:
@@ -151,10 +193,31 @@ const DEFAULT_SEPARATOR: &str = " ";
 /// let entropy = BasicConfig::default().to_scheme().entropy();
 /// println!("passphrase() entropy: {:?}", entropy);
 /// ```
+#[cfg(feature = "wordlist-eff-large")]
 pub fn passphrase() -> String {
     BasicConfig::default().to_scheme().generate()
 }
 
+/// Generate a secure passphrase with the default configuration, using the given randomness source.
+///
+/// This is like [`passphrase`](passphrase), but obtains all randomness from `rng`, such as
+/// `rand::rngs::OsRng` or a hardware-backed source, instead of the implicit thread-local source.
+/// See [`Scheme::generate_with_rng`](scheme::Scheme::generate_with_rng).
+///
+/// Only available with the `wordlist-eff-large` feature, enabled by default.
+///
+/// # Examples
+///
+/// ```rust
+/// use rand::rngs::OsRng;
+///
+/// println!("Passphrase: {:?}", chbs::passphrase_with_rng(&mut OsRng));
+/// ```
+#[cfg(feature = "wordlist-eff-large")]
+pub fn passphrase_with_rng(rng: &mut dyn SecureRng) -> String {
+    BasicConfig::default().to_scheme().generate_with_rng(rng)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::mpsc::RecvError;
@@ -162,16 +225,24 @@ mod tests {
     use std::thread;
 
     use super::config::BasicConfig;
-    use super::passphrase;
+    use super::probability::Probability;
     use super::scheme::{Scheme, ToScheme};
     use super::word::WordList;
 
     /// How many times to iterate for small or infinite tests.
     const ITERS: usize = 32;
 
+    /// Build a wordlist from the given words.
+    fn list(words: &[&str]) -> WordList {
+        WordList::try_new(words.iter().map(|&w| w.to_owned()).collect()).unwrap()
+    }
+
     /// Generating a passphrase must produce a string of at least 10 characters.
+    #[cfg(feature = "wordlist-eff-large")]
     #[test]
     fn passphrase_len() {
+        use super::passphrase;
+
         assert!(
             passphrase().len() >= 10,
             "passphrase generated by defaults helper is too short",
@@ -179,8 +250,11 @@ mod tests {
     }
 
     /// Repeatedly generating passphrases should produce somewhat unique results.
+    #[cfg(feature = "wordlist-eff-large")]
     #[test]
     fn passphrase_unique() {
+        use super::passphrase;
+
         // Generate phrases with helper and dedup
         let mut phrases: Vec<String> = (1..=ITERS).map(|_| passphrase()).collect();
         phrases.dedup();
@@ -189,6 +263,7 @@ mod tests {
         assert!(phrases.len() > 1);
     }
 
+    #[cfg(feature = "wordlist-eff-large")]
     #[test]
     fn sampler_into_iterator() {
         let words = WordList::default();
@@ -197,9 +272,1704 @@ mod tests {
         assert_eq!(8, result.len());
     }
 
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn static_wordlist_matches_builtin() {
+        use super::word::StaticWordList;
+
+        let words = StaticWordList::builtin_eff_large().words();
+        assert_eq!(7776, words.len());
+        assert!(super::word::BUILTIN_EFF_LARGE
+            .lines()
+            .eq(words.iter().copied()));
+    }
+
+    #[test]
+    fn collection_providers() {
+        use super::prelude::*;
+
+        let words = vec!["correct".to_owned(), "horse".to_owned()];
+        assert!(words.contains(&words.word()));
+        assert_eq!(1.0, words.entropy().bits());
+
+        let slice: &[&str] = &["battery", "staple"];
+        assert!(slice.contains(&slice.word().as_str()));
+        assert_eq!(2.0, ["a", "b", "c", "d"].entropy().bits());
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn generate_borrowed_and_owned() {
+        use super::prelude::*;
+
+        let config = BasicConfig::default();
+        assert_eq!(5, config.to_scheme().generate().split(' ').count());
+
+        let config = BasicConfig {
+            word_provider: WordList::default().sampler().map(|w| w.to_uppercase()),
+            words: config.words,
+            separator: config.separator,
+            capitalize_first: config.capitalize_first,
+            capitalize_words: config.capitalize_words,
+            unique_words: config.unique_words,
+        };
+        let phrase = config.to_scheme().generate();
+        assert_eq!(5, phrase.split(' ').count());
+        assert_eq!(phrase.to_uppercase(), phrase);
+    }
+
+    #[test]
+    fn unique_guard_exhausts() {
+        use super::guard::UniqueGuard;
+
+        let config = BasicConfig {
+            words: 1,
+            word_provider: ["a", "b"],
+            separator: " ".into(),
+            capitalize_first: false.into(),
+            capitalize_words: false.into(),
+            unique_words: false,
+        };
+        let guard = UniqueGuard::new(config.to_scheme()).with_max_attempts(ITERS);
+
+        let mut phrases: Vec<String> = guard.collect();
+        phrases.sort();
+        assert_eq!(vec!["a", "b"], phrases);
+    }
+
+    /// Ban guard must never issue banned passphrases, and exhaust if all are banned. Closure
+    /// guards must only issue accepted passphrases.
+    #[test]
+    fn ban_guard_rejects_banned() {
+        use super::guard::{BanGuard, BanList, Guard};
+        use super::scheme::GeneratedPassphrase;
+
+        let config = BasicConfig {
+            words: 1,
+            word_provider: ["Acme", "horse"],
+            separator: " ".into(),
+            capitalize_first: false.into(),
+            capitalize_words: false.into(),
+            unique_words: false,
+        };
+        let guard = BanGuard::new(config.to_scheme(), BanList::new(vec!["acme".into()]));
+        assert!(guard.take(ITERS).all(|phrase| phrase == "horse"));
+
+        let entropy =
+            BanGuard::new(config.to_scheme(), BanList::new(vec!["acme".into()])).entropy(ITERS);
+        assert!(entropy.bits() < 1.0);
+
+        let list = BanList::new(vec!["acme".into(), "horse".into()]);
+        let mut guard = BanGuard::new(config.to_scheme(), list).with_max_attempts(ITERS);
+        assert!(guard.try_next().is_err());
+
+        // Closures act as predicate as well
+        let mut guard = Guard::new(config.to_scheme(), |p: &GeneratedPassphrase| {
+            p.phrase() == "Acme"
+        });
+        assert_eq!(Some("Acme".into()), guard.next());
+    }
+
+    /// Frequency guard must cap word use across a batch, and exhaust when all words are capped.
+    #[test]
+    fn frequency_guard_caps_words() {
+        use super::guard::{FrequencyGuard, WordFrequency};
+
+        let config = BasicConfig {
+            words: 1,
+            word_provider: ["a", "b", "c"],
+            separator: " ".into(),
+            capitalize_first: Probability::half(),
+            capitalize_words: false.into(),
+            unique_words: false,
+        };
+        let mut guard =
+            FrequencyGuard::new(config.to_scheme(), WordFrequency::new(2)).with_max_attempts(256);
+
+        let mut phrases: Vec<String> = guard
+            .generate_many(6)
+            .unwrap()
+            .iter()
+            .map(|p| p.to_lowercase())
+            .collect();
+        phrases.sort();
+        assert_eq!(vec!["a", "a", "b", "b", "c", "c"], phrases);
+        assert_eq!(2, guard.predicate().word_count("A"));
+        assert!(guard.try_next().is_err());
+
+        guard.predicate_mut().clear();
+        assert!(guard.try_next().is_ok());
+    }
+
+    /// Length guards must keep passphrases within bounds, and fit the number of words.
+    #[test]
+    fn length_guard_bounds() {
+        use super::guard::{GuardError, LengthGuard};
+        use super::prelude::*;
+
+        // Guards share the length check of phrase validators
+        assert!((2..=3).validate_phrase("bb").is_ok());
+        assert!((2..=3).validate_phrase("a").is_err());
+        assert!((2..=3).validate_phrase("dddd").is_err());
+
+        let config = BasicConfig {
+            words: 1,
+            word_provider: ["a", "bb", "ccc", "dddd"],
+            separator: "-".into(),
+            capitalize_first: false.into(),
+            capitalize_words: false.into(),
+            unique_words: false,
+        };
+        let mut guard = LengthGuard::new(config.to_scheme(), 2..=3);
+        for phrase in guard.by_ref().take(32) {
+            assert!(phrase == "bb" || phrase == "ccc");
+        }
+        // The conservative estimate is near 0.85 rather than the exact single bit, the bounds
+        // leave over five standard deviations of sampling noise on either side
+        let bits = guard.entropy(1000).bits();
+        assert!(bits > 0.5 && bits < 1.1);
+
+        // Two words of 1 to 4 characters with separator fit, three words partially
+        let guard = LengthGuard::fit_words(&config, 3..=7, 1000).unwrap();
+        assert!(guard.scheme().entropy().bits() >= 4.0);
+        assert!(guard.entropy(1000) < guard.scheme().entropy());
+        assert!(guard.take(32).all(|p| (3..=7).contains(&p.len())));
+
+        assert!(matches!(
+            LengthGuard::fit_words(&config, 1000..=1000, 100),
+            Err(GuardError::Unsatisfiable)
+        ));
+        assert!(matches!(
+            LengthGuard::new(config.to_scheme(), 10..=20).try_next(),
+            Err(GuardError::Exhausted(_))
+        ));
+    }
+
+    /// Test vectors must verify, and report passphrases that changed.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn test_vectors_verify() {
+        use super::registry::{ComponentSpec, Params, Registry, SchemeSpec};
+        use super::testvectors::{TestVectorError, TestVectors};
+
+        let registry = Registry::with_builtins();
+        let spec = SchemeSpec {
+            word_set_provider: ComponentSpec::new("fixed", Params::new().with("words", "3")),
+            word_filters: vec![],
+            word_stylers: vec![ComponentSpec::new(
+                "capitalize",
+                Params::new().with("first", "0.5"),
+            )],
+            phrase_builder: ComponentSpec::new("basic", Params::new().with("separator", "-")),
+            phrase_stylers: vec![],
+            phrase_validators: vec![],
+        };
+
+        let mut vectors = TestVectors::generate(&registry, spec, 0..8).unwrap();
+        assert_eq!(8, vectors.vectors.len());
+        assert_eq!(Ok(()), vectors.verify(&registry));
+
+        vectors.vectors[3].phrase.push('!');
+        assert!(matches!(
+            vectors.verify(&registry),
+            Err(TestVectorError::Mismatch { seed: 3, .. })
+        ));
+
+        vectors.spec.phrase_builder.name = "unknown".into();
+        assert!(matches!(
+            vectors.verify(&registry),
+            Err(TestVectorError::Registry(_))
+        ));
+    }
+
+    /// Checked-in test vectors must keep verifying, on every platform.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn test_vectors_known_answer() {
+        use super::registry::{ComponentSpec, Params, Registry, SchemeSpec};
+        use super::testvectors::{TestVector, TestVectors};
+
+        let registry = Registry::with_builtins();
+        let spec = SchemeSpec {
+            word_set_provider: ComponentSpec::new("fixed", Params::new().with("words", "4")),
+            word_filters: vec![],
+            word_stylers: vec![ComponentSpec::new(
+                "interior",
+                Params::new().with("probability", "0.5"),
+            )],
+            phrase_builder: ComponentSpec::new("charset", Params::new()),
+            phrase_stylers: vec![
+                ComponentSpec::new("year", Params::new()),
+                ComponentSpec::new("append", Params::new().with("classes", "digit,symbol")),
+            ],
+            phrase_validators: vec![],
+        };
+        let vector = |seed, phrase: &str| TestVector {
+            seed,
+            phrase: phrase.into(),
+        };
+        let vectors = TestVectors {
+            version: "0.1.1".into(),
+            spec,
+            vectors: vec![
+                vector(0, "anemic_wieLdable_taSsel_cRummy 20008%"),
+                vector(1, "prepay,commOn,ooZy,illusive 20054,"),
+                vector(2, "blimP@federal@saCrifice@parasite 20159%"),
+            ],
+        };
+        assert_eq!(Ok(()), vectors.verify(&registry));
+    }
+
+    /// Derived passphrases must match known answers, on every platform.
+    #[cfg(all(feature = "derived", feature = "wordlist-eff-large"))]
+    #[test]
+    fn derived_known_answer() {
+        use super::derived::DerivedScheme;
+
+        let scheme = DerivedScheme::new(BasicConfig::default().to_scheme());
+        let secret = b"correct horse battery staple";
+        assert_eq!(
+            "strangle Refreeze Caviar Spoiled appraiser",
+            scheme.derive(secret, "example.com")
+        );
+        assert_eq!(
+            "Emblaze Duvet Frenzied phonebook Robbing",
+            scheme.derive(secret, "example.org")
+        );
+    }
+
+    /// Policy schemes must generate compliant passphrases reaching the minimum entropy.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn policy_scheme_complies() {
+        use super::component::phrase::CharClass;
+        use super::entropy::Entropy;
+        use super::policy::{preset, Policy, PRESETS};
+
+        for name in PRESETS.iter() {
+            let policy = preset(name).unwrap();
+            let scheme = policy.to_scheme();
+            assert!(scheme.entropy() >= policy.min_entropy);
+            for phrase in scheme.generate_many(32) {
+                assert_eq!(Ok(()), policy.check(&phrase));
+            }
+        }
+
+        let policy = Policy::new(Entropy::from_bits(1))
+            .with_min_length(40)
+            .with_required_classes(vec![CharClass::Symbol]);
+        let phrase = policy.to_scheme().generate();
+        assert!(phrase.chars().count() >= 40);
+        assert!(phrase.ends_with(|c| CharClass::Symbol.matches(c)));
+        assert!(policy.check("short!").is_err());
+        assert!(policy.check(&"a".repeat(40)).is_err());
+    }
+
+    /// Interior capitalization must never touch the first character, and credit positions.
+    #[test]
+    fn interior_capitalizer_positions() {
+        use super::component::phrase::BasicPhraseBuilder;
+        use super::component::word::{FixedWordSetProvider, InteriorCapitalizer};
+        use super::prelude::*;
+        use super::validate::Warning;
+
+        let styler = InteriorCapitalizer::new(Probability::Always, 4);
+        assert_eq!(3f64.log2(), styler.entropy().bits());
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..200 {
+            let word = styler.style_word("abcd".into());
+            assert!(word.starts_with('a'));
+            seen.insert(word);
+        }
+        assert_eq!(3, seen.len());
+
+        assert_eq!(
+            "e\u{301}E\u{301}",
+            styler.style_word("e\u{301}e\u{301}".into())
+        );
+        assert_eq!("a", styler.style_word("a".into()));
+        assert_eq!(
+            1.0,
+            InteriorCapitalizer::new(Probability::half(), 2)
+                .entropy()
+                .bits()
+        );
+        assert_eq!(
+            0.0,
+            InteriorCapitalizer::new(Probability::half(), 1)
+                .entropy()
+                .bits()
+        );
+
+        // Crediting positions for words longer than the shortest known word must be reported
+        let build = |styler| {
+            Scheme::build()
+                .word_set_provider(Box::new(FixedWordSetProvider::new(["ab", "abcd"], 2)))
+                .word_stylers(vec![Box::new(styler)])
+                .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+                .phrase_stylers(Vec::new())
+                .build()
+                .unwrap()
+        };
+        assert!(matches!(
+            &build(styler).validate()[..],
+            [Warning::OverstatedEntropy { word, .. }] if word == "ab"
+        ));
+        let styler = InteriorCapitalizer::new(Probability::half(), 2);
+        assert!(build(styler).validate().is_empty());
+    }
+
+    /// Fitting must find the shortest configuration, or explain why none fits.
+    #[test]
+    fn fit_policy_constraints() {
+        use super::config::{fit, FitError};
+        use super::entropy::Entropy;
+        use super::policy::{Constraints, Policy};
+
+        let constraints = Constraints::default()
+            .with_separator("-")
+            .with_wordlists(vec![list(&["aa", "bb"]), list(&["a", "b", "c", "d"])]);
+
+        // Two words from the list of single characters are shortest
+        let config = fit(&Policy::new(Entropy::from_bits(4)), &constraints).unwrap();
+        let scheme = config.to_scheme();
+        assert!(scheme.entropy() >= Entropy::from_bits(4));
+        assert_eq!(3, scheme.generate().len());
+
+        let err = fit(
+            &Policy::new(Entropy::from_bits(16)),
+            &constraints.clone().with_max_length(5),
+        )
+        .unwrap_err();
+        match err {
+            FitError::TooLong {
+                min_length,
+                max_length,
+                max_entropy,
+            } => {
+                assert_eq!(5, max_length);
+                assert!(min_length > 5);
+                assert!(max_entropy < Entropy::from_bits(16));
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+
+        assert_eq!(
+            Err(FitError::NoWordlists),
+            fit(
+                &Policy::default(),
+                &constraints.with_wordlists(vec![list(&["a-b", "c"])])
+            )
+            .map(|_| ())
+        );
+    }
+
+    /// Word filters must filter the underlying wordlist, and entropy must reflect its size.
+    #[test]
+    fn word_filter_entropy() {
+        use super::component::phrase::BasicPhraseBuilder;
+        use super::component::word::{
+            BlockedWordFilter, FixedWordSetProvider, NumberWordInjector, WordLengthFilter,
+        };
+        use super::prelude::*;
+
+        let words = list(&["a", "bb", "ccc", "dddd"]);
+        let build = |provider: Box<dyn WordSetProvider>, filter: Box<dyn WordFilter>| {
+            Scheme::build()
+                .word_set_provider(provider)
+                .add_word_filter(filter)
+                .word_stylers(Vec::new())
+                .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+                .phrase_stylers(Vec::new())
+                .build()
+        };
+
+        let provider = FixedWordSetProvider::new(words.sampler(), 3);
+        let scheme = build(Box::new(provider), Box::new(WordLengthFilter::new(2..=3))).unwrap();
+        assert_eq!(3.0, scheme.entropy().bits());
+        for _ in 0..ITERS {
+            let phrase = scheme.generate();
+            assert!(phrase.split(' ').all(|w| w == "bb" || w == "ccc"));
+        }
+
+        // Filters vetoing all words, or providers not supporting filters, fail to build
+        let provider = FixedWordSetProvider::new(words.sampler(), 3);
+        let filter =
+            BlockedWordFilter::new(vec!["A".into(), "bb".into(), "ccc".into(), "dddd".into()]);
+        assert!(build(Box::new(provider), Box::new(filter)).is_err());
+        let provider = NumberWordInjector::new(FixedWordSetProvider::new(words.sampler(), 3), 1);
+        assert!(build(Box::new(provider), Box::new(WordLengthFilter::new(1..=4))).is_err());
+    }
+
+    /// Escrow records must round-trip, and reject tampering, wrong schemes and missing ciphers.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn escrow_round_trip() {
+        use super::escrow::{decode, encode, EscrowCipher, EscrowError};
+
+        /// A toy cipher for testing, XOR with a key byte, tagged with the key.
+        struct Xor(u8);
+
+        impl EscrowCipher for Xor {
+            fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+                let mut ciphertext: Vec<u8> = plaintext.iter().map(|b| b ^ self.0).collect();
+                ciphertext.push(self.0);
+                ciphertext
+            }
+
+            fn decrypt(&self, ciphertext: &[u8]) -> Option<Vec<u8>> {
+                match ciphertext.split_last()? {
+                    (tag, ciphertext) if *tag == self.0 => {
+                        Some(ciphertext.iter().map(|b| b ^ self.0).collect())
+                    }
+                    _ => None,
+                }
+            }
+        }
+
+        let scheme = BasicConfig::default().to_scheme();
+        for _ in 0..ITERS {
+            let passphrase = scheme.generate_audited();
+            let record = encode(&passphrase, &scheme, Some(&Xor(0x5a))).unwrap();
+            assert_eq!(
+                Ok(passphrase.clone()),
+                decode(&record, &scheme, Some(&Xor(0x5a)))
+            );
+            assert_eq!(
+                Err(EscrowError::MissingCipher),
+                decode(&record, &scheme, None)
+            );
+            assert_eq!(
+                Err(EscrowError::Decrypt),
+                decode(&record, &scheme, Some(&Xor(1)))
+            );
+
+            // Unaudited passphrases are checked against their word indices
+            let passphrase = scheme.generate_detailed();
+            let mut record = encode(&passphrase, &scheme, None).unwrap();
+            assert_eq!(Ok(passphrase.clone()), decode(&record, &scheme, None));
+
+            // The first word starts after the passphrase, the word count and its length prefix
+            record[6 + 8 + 8 + 4 + passphrase.phrase().len() + 4 + 4] = b'#';
+            assert_eq!(Err(EscrowError::Mismatch), decode(&record, &scheme, None));
+        }
+
+        let other = BasicConfig::new(["correct", "horse"]).to_scheme();
+        assert_eq!(
+            Err(EscrowError::UnknownWord { index: 0 }),
+            encode(&scheme.generate_detailed(), &other, None)
+        );
+
+        let passphrase = scheme.generate_audited();
+        let mut record = encode(&passphrase, &scheme, None).unwrap();
+        let other = BasicConfig::default().with_words(6).to_scheme();
+        assert_eq!(
+            Err(EscrowError::SchemeMismatch),
+            decode(&record, &other, None)
+        );
+
+        // Tampering with the passphrase must be detected, it starts after the header and
+        // fingerprint, entropy and length prefix
+        record[26] ^= 1;
+        assert_eq!(Err(EscrowError::Mismatch), decode(&record, &scheme, None));
+        record[4] = 2;
+        assert_eq!(
+            Err(EscrowError::UnsupportedVersion(2)),
+            decode(&record, &scheme, None)
+        );
+        assert_eq!(Err(EscrowError::Malformed), decode(b"CHBS", &scheme, None));
+    }
+
+    /// Fingerprints must only cover the scheme definition, and be the same on every platform.
+    #[test]
+    fn scheme_fingerprint() {
+        use super::component::phrase::{BannedSubstringValidator, BasicPhraseBuilder};
+        use super::component::word::FixedWordSetProvider;
+
+        let config = BasicConfig::new(["correct", "horse", "battery", "staple"]).with_words(4);
+        assert_eq!(0xe903_8a33_889b_1abc, config.to_scheme().fingerprint());
+
+        // The entropy of schemes measuring acceptance is sampled, the fingerprint must not vary
+        let build = || {
+            Scheme::build()
+                .word_set_provider(Box::new(FixedWordSetProvider::new(["a", "b", "c", "d"], 1)))
+                .word_stylers(Vec::new())
+                .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+                .phrase_stylers(Vec::new())
+                .add_phrase_validator(Box::new(BannedSubstringValidator::new(vec!["a".into()])))
+                .acceptance_samples(16)
+                .build()
+                .unwrap()
+        };
+        let fingerprint = build().fingerprint();
+        for _ in 0..ITERS {
+            assert_eq!(fingerprint, build().fingerprint());
+        }
+    }
+
+    /// Phrase validators must reject passphrases, which are regenerated until accepted.
+    #[test]
+    fn phrase_validator_retries() {
+        use super::component::phrase::{BannedSubstringValidator, BasicPhraseBuilder};
+        use super::component::word::FixedWordSetProvider;
+
+        let build = |samples| {
+            Scheme::build()
+                .word_set_provider(Box::new(FixedWordSetProvider::new(
+                    list(&["a", "b"]).sampler(),
+                    1,
+                )))
+                .word_stylers(Vec::new())
+                .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+                .phrase_stylers(Vec::new())
+                .add_phrase_validator(Box::new(BannedSubstringValidator::new(vec!["a".into()])))
+                .acceptance_samples(samples)
+                .build()
+                .unwrap()
+        };
+
+        let mut scheme = build(0);
+        assert!(scheme.by_ref().take(ITERS).all(|phrase| phrase == "b"));
+        for _ in 0..ITERS {
+            assert_eq!("b", scheme.generate());
+            assert_eq!("b", scheme.generate_seeded(7));
+            assert_eq!("b", scheme.generate_detailed().phrase());
+        }
+        assert_eq!(1.0, scheme.entropy().bits());
+        assert!(scheme.acceptance().is_none());
+
+        // Replaying an audited passphrase must reproduce rejected attempts
+        let audited = scheme.generate_audited();
+        let mut replay = audited.audit().unwrap().replay();
+        assert_eq!(audited.phrase(), scheme.generate_with_rng(&mut replay));
+
+        // Rejected space is folded into the entropy when measured
+        let scheme = build(1000);
+        assert!(scheme.acceptance().unwrap().rate() < 0.6);
+        assert!(scheme.entropy().bits() < 0.1);
+    }
+
+    /// Rejection loops must stop at the configured limits, and report which limit was exceeded.
+    #[test]
+    fn generation_limits_exceeded() {
+        use std::time::Duration;
+
+        use super::component::phrase::{BasicPhraseBuilder, MaxLengthValidator};
+        use super::component::word::FixedWordSetProvider;
+        use super::guard::{UniqueGuard, UniqueGuardError};
+        use super::limits::{GenerationLimits, LimitExceeded};
+
+        let build = |limits| {
+            Scheme::build()
+                .word_set_provider(Box::new(FixedWordSetProvider::new(["horse"], 1)))
+                .word_stylers(Vec::new())
+                .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+                .phrase_stylers(Vec::new())
+                .add_phrase_validator(Box::new(MaxLengthValidator::new(2)))
+                .limits(limits)
+                .build()
+                .unwrap()
+        };
+
+        let err = build(GenerationLimits::new(3)).try_generate().unwrap_err();
+        assert_eq!(LimitExceeded::Attempts(3), err.limit);
+        assert!(err.reason.contains("exceeding the maximum of 2"));
+
+        // A duration bounds attempts, but at least one is always made
+        let limits = GenerationLimits::new(usize::MAX).with_max_duration(Duration::from_millis(10));
+        let err = build(limits).try_generate().unwrap_err();
+        assert!(matches!(err.limit, LimitExceeded::Duration { attempts, .. } if attempts >= 1));
+        let limits = GenerationLimits::new(usize::MAX).with_max_duration(Duration::from_secs(0));
+        assert_eq!(
+            1,
+            build(limits).try_generate().unwrap_err().limit.attempts()
+        );
+
+        let scheme = BasicConfig {
+            words: 1,
+            word_provider: ["a"],
+            separator: " ".into(),
+            capitalize_first: false.into(),
+            capitalize_words: false.into(),
+            unique_words: false,
+        }
+        .to_scheme();
+        let mut guard = UniqueGuard::new(scheme).with_limits(GenerationLimits::new(5));
+        assert_eq!("a", guard.try_next().unwrap());
+        assert!(matches!(
+            guard.try_next(),
+            Err(UniqueGuardError::Exhausted(LimitExceeded::Attempts(5)))
+        ));
+    }
+
+    /// Encoded bytes must decode again, with ambiguous lengths reported.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn encode_round_trip() {
+        use super::encode::{EncodeError, PhraseEncoder};
+        use rand::RngCore;
+
+        // Lengths are never ambiguous with up to 8 bits per word
+        let list = WordList::builtin_eff_large().truncate_pow2();
+        let encoder = PhraseEncoder::new(&list).unwrap();
+        let small = PhraseEncoder::new(&list.clone().truncate_pow2_max(128)).unwrap();
+        for len in 0..40 {
+            let mut bytes = vec![0u8; len];
+            rand::thread_rng().fill_bytes(&mut bytes);
+
+            let phrase = encoder.encode(&bytes);
+            assert_eq!(encoder.word_count(len), encoder.encode_words(&bytes).len());
+            assert_eq!(
+                bytes,
+                encoder.decode_exact(&phrase.to_uppercase(), len).unwrap()
+            );
+            assert_eq!(bytes, small.decode(&small.encode(&bytes)).unwrap());
+        }
+
+        // Two and three bytes both take two words of 12 bits
+        let phrase = encoder.encode(&[1, 2, 0]);
+        assert_eq!(Err(EncodeError::AmbiguousLength), encoder.decode(&phrase));
+        assert_eq!(vec![1, 2], encoder.decode_exact(&phrase, 2).unwrap());
+        assert_eq!(vec![1, 2, 0], encoder.decode_exact(&phrase, 3).unwrap());
+        assert_eq!(
+            Err(EncodeError::WordCount(2)),
+            encoder.decode_exact(&phrase, 1)
+        );
+
+        // Padding bits must be zero
+        let last = list.words().last().unwrap();
+        assert_eq!(Err(EncodeError::Padding), encoder.decode(last));
+        assert!(matches!(
+            encoder.decode("abacus chbs"),
+            Err(EncodeError::UnknownWord(_))
+        ));
+
+        assert_eq!(
+            Err(EncodeError::WordListSize(7776)),
+            PhraseEncoder::new(&WordList::builtin_eff_large()).map(|_| ())
+        );
+        assert!(matches!(
+            PhraseEncoder::new(&list).unwrap().with_separator("-"),
+            Err(EncodeError::Separator(_))
+        ));
+    }
+
+    /// Built-in wordlists must be parsed once, and share their words afterwards.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn builtin_wordlists_cached() {
+        let list = WordList::builtin_eff_large();
+        assert_eq!(list.words().as_ptr(), WordList::default().words().as_ptr());
+        assert_eq!(
+            list.words().as_ptr(),
+            WordList::builtin("EFF_LARGE").unwrap().words().as_ptr()
+        );
+
+        // Modifying a built-in list must not affect the cached list
+        let truncated = list.truncate_pow2();
+        assert_eq!(4096, truncated.words().len());
+        assert_eq!(7776, WordList::builtin_eff_large().words().len());
+    }
+
+    /// Samplers and schemes must share the words of their list instead of copying them.
+    #[test]
+    fn wordlist_shared_with_samplers() {
+        use super::prelude::*;
+
+        let list = list(&["correct", "horse"]);
+        let first = list.words()[0].as_ptr();
+        let sampler = list.sampler();
+        assert_eq!(first, sampler.known_words().unwrap()[0].as_ptr());
+        assert_eq!(first, sampler.clone().known_words().unwrap()[0].as_ptr());
+        let builder = list.sampler_builder().build().unwrap();
+        assert_eq!(first, builder.known_words().unwrap()[0].as_ptr());
+    }
+
+    #[test]
+    fn wordlist_transform() {
+        use super::word::WordListError;
+
+        let words = list(&["dog", "cat", "dog", "horse", "ox"]);
+        assert_eq!(
+            &["dog", "cat", "horse", "ox"],
+            words.dedup().unwrap().words()
+        );
+        assert_eq!(
+            &["dog", "cat", "dog"],
+            words.retain_length(3..=3).unwrap().words()
+        );
+        assert_eq!(
+            &["dog", "dog", "horse", "ox"],
+            words.filter(|w| w.contains('o')).unwrap().words()
+        );
+        assert!(matches!(
+            words.filter(|w| w.contains('r')),
+            Err(WordListError::SingleWord)
+        ));
+        assert!(matches!(
+            words.retain_length(6..=10),
+            Err(WordListError::Empty)
+        ));
+
+        let other = list(&["ox", "yak", "yak"]);
+        assert_eq!(
+            &["dog", "cat", "dog", "horse", "ox", "yak", "yak"],
+            words.merge(&other).words(),
+        );
+        assert_eq!(5, words.merge(&other).dedup().unwrap().words().len());
+        assert!(matches!(
+            list(&["yak", "yak"]).dedup(),
+            Err(WordListError::SingleWord)
+        ));
+
+        // Derived lists don't change the list they are derived from
+        assert_eq!(5, words.words().len());
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn wordlist_analyze() {
+        use super::entropy::Entropy;
+        use super::prelude::*;
+
+        let report = WordList::default().analyze();
+        assert_eq!(7776, report.words);
+        assert!(report.is_distinct());
+        assert_eq!(report.nominal_entropy, report.entropy);
+        assert_eq!(WordList::default().entropy(), report.entropy);
+
+        let words = ["dog", "cat", "dog", "dog", "éclair"];
+        let report = list(&words).analyze();
+        assert_eq!((5, 3), (report.words, report.distinct));
+        assert_eq!(vec!["dog"], report.duplicates);
+        assert_eq!((3, 6), (report.min_length, report.max_length));
+        assert_eq!(3.6, report.average_length);
+        assert_eq!("acdgilorté", report.characters.iter().collect::<String>());
+        assert!(report.prefix_free);
+        assert_eq!(
+            Entropy::from_distribution(vec![0.6, 0.2, 0.2]),
+            report.entropy,
+        );
+        assert!(report.entropy_loss().bits() > 0.0);
+    }
+
+    #[test]
+    fn wordlist_comments_bom_crlf() {
+        use super::word::WordListFormat;
+
+        let plain = "\u{feff}# EFF wordlist\r\nabacus abdomen\r\n  # comment\r\n\r\nabdominal\r\n";
+        let list = WordList::from_reader(plain.as_bytes()).unwrap();
+        assert_eq!(&["abacus", "abdomen", "abdominal"], list.words());
+        assert_eq!(
+            WordListFormat::Plain,
+            WordListFormat::detect(plain).unwrap()
+        );
+
+        let diced = "\u{feff}# diced\r\n11111\tabacus \r\n#2 (1,1,1,1,2)\tabdomen\r\n";
+        let list = WordList::from_reader_diced(diced.as_bytes()).unwrap();
+        assert_eq!(&["abacus", "abdomen"], list.words());
+        assert_eq!(
+            WordListFormat::Diced,
+            WordListFormat::detect(diced).unwrap()
+        );
+
+        let list =
+            WordList::from_delimited("\u{feff}# words\r\nice cream\r\nhot dog", "\n").unwrap();
+        assert_eq!(&["ice cream", "hot dog"], list.words());
+    }
+
+    #[test]
+    fn weighted_sampler_entropy() {
+        use super::entropy::Entropy;
+        use super::prelude::*;
+        use super::word::weighted::WeightedWordList;
+
+        let words = |words: &[&str]| words.iter().map(|&w| w.to_owned()).collect();
+        let list = WeightedWordList::new(words(&["a", "b", "c", "d"]), vec![1.0; 4]).unwrap();
+        assert_eq!(Entropy::from_bits(2.0), list.sampler().entropy());
+
+        // Shannon entropy of 1/2, 1/4 and 1/4
+        let list = WeightedWordList::new(words(&["a", "b", "c"]), vec![2.0, 1.0, 1.0]).unwrap();
+        assert_eq!(1.5, list.sampler().entropy().bits());
+        assert_eq!(1.0, list.sampler().min_entropy().bits());
+
+        // Repeated words are one outcome
+        let list =
+            WeightedWordList::new(words(&["a", "b", "a", "c"]), vec![1.0, 1.0, 1.0, 1.0]).unwrap();
+        assert_eq!(1.5, list.sampler().entropy().bits());
+        assert_eq!(1.0, list.sampler().min_entropy().bits());
+
+        // The entropy floor also treats repeated words as one outcome, a has probability 2/3
+        let list = WeightedWordList::new(words(&["a", "a", "b"]), vec![1.0; 3]).unwrap();
+        assert!(list.biased_sampler(0.0, Entropy::from_bits(1.0)).is_err());
+        assert!(list.common_sampler(Entropy::from_bits(1.0)).is_err());
+        let sampler = list.common_sampler(Entropy::from_bits(0.5)).unwrap();
+        assert!(sampler.min_entropy() >= Entropy::from_bits(0.5));
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn unique_words() {
+        use super::entropy::Entropy;
+
+        let words = ["correct", "horse", "battery", "staple"];
+        let config = BasicConfig::new(words)
+            .with_words(4)
+            .with_separator(" ")
+            .with_capitalize_first(false)
+            .with_unique_words(true);
+        let scheme = config.to_scheme();
+        assert_eq!(Entropy::from_permutations(4, 4), scheme.entropy());
+        for phrase in scheme.take(ITERS) {
+            let mut phrase: Vec<_> = phrase.split(' ').collect();
+            phrase.sort_unstable();
+            assert_eq!(vec!["battery", "correct", "horse", "staple"], phrase);
+        }
+
+        let config = BasicConfig::default()
+            .with_capitalize_first(false)
+            .with_unique_words(true);
+        let n = WordList::default().words().len() as u64;
+        assert_eq!(
+            Entropy::from_permutations(n, 5).bits().round(),
+            config.to_scheme().entropy().bits().round(),
+        );
+    }
+
+    /// Unique words from providers not sampling by reference must not hang.
+    #[test]
+    fn unique_words_without_refs() {
+        use super::component::{phrase::BasicPhraseBuilder, word::FixedWordSetProvider};
+        use super::entropy::Entropy;
+        use super::guard::{UniqueGuard, UniqueGuardError};
+        use super::limits::{GenerationLimits, LimitExceeded};
+        use super::prelude::*;
+        use super::word::provider::word_provider_fn;
+        use rand::Rng;
+
+        let provider = word_provider_fn(Entropy::from_real(4), |rng: &mut dyn SecureRng| {
+            ["correct", "horse", "battery", "staple"][rng.gen_range(0..4)].to_owned()
+        });
+        let provider = FixedWordSetProvider::new(provider, 4).with_unique(true);
+        assert!(provider.word_refs().is_none());
+        let mut words = provider.words();
+        words.sort_unstable();
+        assert_eq!(vec!["battery", "correct", "horse", "staple"], words);
+
+        // Exceeding the limits on obtaining unique words must fail generation without panicking
+        let provider = word_provider_fn(Entropy::from_real(1), |_: &mut dyn SecureRng| "a".into());
+        let provider = FixedWordSetProvider::new(provider, 2)
+            .with_unique(true)
+            .with_limits(GenerationLimits::new(8));
+        assert_eq!(Err(LimitExceeded::Attempts(8)), provider.try_words());
+        let scheme = Scheme::new(
+            Box::new(provider),
+            Vec::new(),
+            Box::new(BasicPhraseBuilder::new(" ".into())),
+            Vec::new(),
+        );
+        assert_eq!(
+            LimitExceeded::Attempts(8),
+            scheme.try_generate().unwrap_err().limit
+        );
+        assert!(scheme.try_generate_detailed().is_err());
+        assert!(matches!(
+            UniqueGuard::new(scheme).try_next(),
+            Err(UniqueGuardError::Rejected(_))
+        ));
+    }
+
+    /// Filtering known words must yield exact entropy, and rejecting all words must fail.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn filter_word_provider() {
+        use super::component::word::WordFilterError;
+        use super::entropy::Entropy;
+        use super::prelude::*;
+        use super::word::provider::word_provider_fn;
+
+        let list = WordList::try_new(vec!["a".into(), "bb".into(), "cc".into(), "ddd".into()]);
+        let provider = list.unwrap().sampler().filter(|w| w.len() == 2).unwrap();
+        assert_eq!(Entropy::from_real(2), provider.entropy());
+        assert_eq!(vec!["bb", "cc"], provider.known_words().unwrap());
+        let provider = WordList::default().sampler();
+        assert_eq!(
+            Some(WordFilterError::Empty),
+            provider.filter(|_| false).err()
+        );
+
+        let provider = word_provider_fn(Entropy::from_real(4), |_: &mut dyn SecureRng| "a".into());
+        assert_eq!(
+            Some(WordFilterError::Empty),
+            provider.filter(|w| w != "a").err()
+        );
+    }
+
+    /// Cost estimates must cover generated lengths and classify rejecting schemes as expensive.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn scheme_estimate() {
+        use super::component::phrase::MaxLengthValidator;
+        use super::scheme::CostClass;
+
+        let scheme = BasicConfig::default().to_scheme();
+        let estimate = scheme.estimate();
+        assert_eq!(CostClass::Low, estimate.cost);
+        assert_eq!(1.0, estimate.attempts);
+        assert_eq!(19..=49, estimate.length);
+        for phrase in scheme.take(ITERS) {
+            assert!(estimate.length.contains(&phrase.chars().count()));
+        }
+
+        let rejecting = Scheme::build()
+            .word_set_provider(Box::new(super::component::word::FixedWordSetProvider::new(
+                WordList::default().sampler(),
+                5,
+            )))
+            .word_stylers(vec![])
+            .phrase_builder(Box::new(super::component::phrase::BasicPhraseBuilder::new(
+                " ".into(),
+            )))
+            .phrase_stylers(vec![])
+            .phrase_validators(vec![Box::new(MaxLengthValidator::new(4))])
+            .build()
+            .unwrap();
+        let estimate = rejecting.estimate();
+        assert_eq!(CostClass::High, estimate.cost);
+        assert_eq!(64.0, estimate.attempts);
+        assert!(estimate.allocations >= 64.0);
+    }
+
+    /// PGP word list encoding must match the reference fingerprint, and detect swapped words.
+    #[test]
+    fn pgp_vectors() {
+        use super::encode::{decode_pgp, encode_pgp, EncodeError};
+
+        let fingerprint = [
+            0xE5, 0x82, 0x94, 0xF2, 0xE9, 0xA2, 0x27, 0x48, 0x6E, 0x8B, 0x06, 0x1B, 0x31, 0xCC,
+            0x52, 0x8F, 0xD7, 0xFA, 0x3F, 0x19,
+        ];
+        let phrase = "topmost Istanbul Pluto vagabond treadmill Pacific brackish dictator \
+            goldfish Medusa afflict bravado chatter revolver Dupont midsummer stopwatch \
+            whimsical cowbell bottomless";
+        assert_eq!(phrase, encode_pgp(&fingerprint));
+        assert_eq!(fingerprint.to_vec(), decode_pgp(phrase).unwrap());
+
+        assert_eq!(
+            Err(EncodeError::PgpPosition(1)),
+            decode_pgp("topmost Pluto Istanbul vagabond")
+        );
+        assert!(matches!(
+            decode_pgp("topmost chbs"),
+            Err(EncodeError::UnknownWord(_))
+        ));
+        for name in &["pgp_even", "pgp_odd"] {
+            assert_eq!(256, WordList::builtin(name).unwrap().words().len());
+        }
+    }
+
+    /// Mixed providers must account for words shared between providers.
+    #[test]
+    fn mixed_provider_entropy() {
+        use super::prelude::*;
+        use super::word::provider::MixedWordProvider;
+
+        let disjoint = MixedWordProvider::new(vec![
+            (list(&["a", "b"]).sampler(), 1.0),
+            (list(&["c", "d"]).sampler(), 1.0),
+        ]);
+        assert_eq!(2.0, disjoint.entropy().bits());
+
+        // Word b is picked with probability 1/2
+        let shared = MixedWordProvider::new(vec![
+            (list(&["a", "b"]).sampler(), 1.0),
+            (list(&["b", "c"]).sampler(), 1.0),
+        ]);
+        assert_eq!(1.5, shared.entropy().bits());
+
+        let skewed = MixedWordProvider::new(vec![
+            (list(&["a", "b"]).sampler(), 3.0),
+            (list(&["c", "d", "e", "f"]).sampler(), 1.0),
+        ]);
+        assert!((skewed.entropy().bits() - 1.25 - 0.811_278).abs() < 1e-6);
+        for _ in 0..ITERS {
+            assert_eq!(1, skewed.word().len());
+        }
+    }
+
+    /// Bloom filters must contain every word, also after serialization, with few false positives.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn bloom_filter_membership() {
+        use super::word::bloom::BloomFilter;
+
+        let list = WordList::builtin_eff_large();
+        let filter = list.bloom_filter(0.01);
+        assert!(list.words().iter().all(|w| filter.contains_maybe(w)));
+
+        let filter = BloomFilter::from_bytes(&filter.to_bytes()).unwrap();
+        assert!(list.words().iter().all(|w| filter.contains_maybe(w)));
+        let false_positives = (0..10_000)
+            .filter(|i| filter.contains_maybe(&format!("#{}", i)))
+            .count();
+        assert!(false_positives < 300);
+
+        assert!(BloomFilter::from_bytes(b"CHBF").is_err());
+    }
+
+    #[test]
+    fn intern_wordlists() {
+        let first = list(&["intern", "test"]).intern();
+        assert!(first.ptr_eq(&list(&["intern", "test"]).intern()));
+        assert!(!first.ptr_eq(&list(&["intern", "other"]).intern()));
+        assert!(!first.ptr_eq(&list(&["intern", "test"])));
+    }
+
+    #[test]
+    fn word_sampler_builder() {
+        let list = list(&["a", "bb", "cc", "DD", "eee"]);
+
+        let sampler = list
+            .sampler_builder()
+            .min_length(2)
+            .max_length(2)
+            .block(vec!["dd"])
+            .replacement(false)
+            .build()
+            .unwrap();
+        let mut words: Vec<String> = sampler.into_iter().collect();
+        words.sort();
+        assert_eq!(words, vec!["bb", "cc"]);
+
+        assert!(list.sampler_builder().min_length(4).build().is_err());
+        assert!(matches!(
+            list.sampler_builder().min_length(3).build(),
+            Err(super::word::WordListError::SingleWord)
+        ));
+    }
+
+    #[test]
+    fn split_phrase_round_trip() {
+        use super::charset::Charset;
+        use super::component::{phrase::CharsetPhraseBuilder, word::FixedWordSetProvider};
+
+        let words = list(&["correct", "horse", "battery", "staple"]);
+        let scheme = Scheme::new(
+            Box::new(FixedWordSetProvider::new(words.sampler(), 4)),
+            Vec::new(),
+            Box::new(CharsetPhraseBuilder::new(Charset::ShellSafe)),
+            Vec::new(),
+        );
+        for _ in 0..ITERS {
+            let phrase = scheme.generate();
+            let split = scheme.split_phrase(&phrase).unwrap();
+            assert_eq!(4, split.len());
+            assert!(split.iter().all(|w| words.words().contains(w)));
+        }
+    }
+
+    /// Batched index draws must be jointly uniform, and use a single draw for each batch.
+    #[test]
+    fn uniform_indices_batched() {
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        use super::rng::{uniform_indices, RecordingRng};
+
+        // Chi-squared test over all 105 joint outcomes, critical value for p = 0.001
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let samples = 105 * 400;
+        let mut counts = [0usize; 105];
+        for _ in 0..samples {
+            let i = uniform_indices(&mut rng, &[3, 5, 7]);
+            counts[i[0] * 35 + i[1] * 7 + i[2]] += 1;
+        }
+        let expected = samples as f64 / 105.0;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|c| (*c as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 153.2, "chi-squared {}", chi_squared);
+
+        let mut inner = ChaCha20Rng::seed_from_u64(0);
+        let mut rng = RecordingRng::new(&mut inner);
+        uniform_indices(&mut rng, &[7776; 4]);
+        assert_eq!(16, rng.into_recorded().len());
+
+        let mut rng = RecordingRng::new(&mut inner);
+        uniform_indices(&mut rng, &[7776; 5]);
+        assert_eq!(32, rng.into_recorded().len());
+
+        assert!(uniform_indices(&mut inner, &[usize::MAX, 1, 2])[0] < usize::MAX);
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn suggest_ranked() {
+        use super::score::{Memorability, Scorer, TypingEffort};
+
+        let scheme = BasicConfig::default().to_scheme();
+        for scorer in [&Memorability as &dyn Scorer, &TypingEffort].iter() {
+            let suggestions = scheme.suggest(ITERS, *scorer);
+            assert_eq!(ITERS, suggestions.len());
+            assert!(suggestions
+                .windows(2)
+                .all(|p| scorer.score(&p[0]) >= scorer.score(&p[1])));
+        }
+    }
+
+    #[test]
+    fn stylers_keep_graphemes() {
+        use super::component::{grapheme::random_grapheme_boundary, word::WordCapitalizer};
+        use super::prelude::*;
+        use super::probability::Probability;
+
+        let capitalizer = WordCapitalizer::new(Probability::Always, Probability::Never);
+        assert_eq!(
+            "\u{1f1f3}\u{1f1f1}x",
+            capitalizer.style_word("\u{1f1f3}\u{1f1f1}x".into())
+        );
+        assert_eq!("I\u{308}ris", capitalizer.style_word("i\u{308}ris".into()));
+
+        let mut rng = rand::thread_rng();
+        assert!((0..ITERS).all(|_| random_grapheme_boundary("e\u{301}", &mut rng) <= 1));
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn invalid_entropy_warning() {
+        use super::component::{phrase::BasicPhraseBuilder, word::FixedWordSetProvider};
+        use super::entropy::Entropy;
+        use super::prelude::*;
+        use super::validate::Warning;
+
+        #[derive(Debug)]
+        struct Broken;
+
+        impl HasEntropy for Broken {
+            fn entropy(&self) -> Entropy {
+                Entropy::one() - 2
+            }
+        }
+
+        impl WordStyler for Broken {
+            fn style_word(&self, word: String) -> String {
+                word
+            }
+        }
+
+        let scheme = Scheme::new(
+            Box::new(FixedWordSetProvider::new(WordList::default().sampler(), 5)),
+            vec![Box::new(Broken)],
+            Box::new(BasicPhraseBuilder::new(" ".into())),
+            Vec::new(),
+        );
+        assert!(scheme.validate().contains(&Warning::InvalidEntropy {
+            kind: super::component::ComponentKind::WordStyler,
+            index: 0,
+            entropy: Entropy::from_bits(-5),
+        }));
+    }
+
+    #[test]
+    fn template_schemes() {
+        use super::template::{Template, TemplateError};
+        use super::validate::Warning;
+
+        let words = list(&["alpha", "beta"]);
+        let scheme = Template::parse("{{{word:upper}}}.{word}-{digit}")
+            .unwrap()
+            .with_wordlist(words)
+            .to_scheme();
+        assert_eq!(2.0 + 10f64.log2(), scheme.entropy().bits());
+        for _ in 0..ITERS {
+            let phrase = scheme.generate();
+            assert!(phrase.starts_with("{ALPHA}.") || phrase.starts_with("{BETA}."));
+            assert_eq!(Some(3), scheme.split_phrase(&phrase).map(|w| w.len()));
+        }
+
+        assert_eq!(
+            Err(TemplateError::Unclosed { position: 6 }),
+            Template::parse("{word}{word").map(|_| ())
+        );
+        assert_eq!(
+            Err(TemplateError::UnmatchedClose { position: 4 }),
+            Template::parse("word}").map(|_| ())
+        );
+        assert_eq!(
+            Err(TemplateError::UnknownSlot("letter".into())),
+            Template::parse("{letter}").map(|_| ())
+        );
+        assert!(Template::parse("{digit:upper}").is_err());
+        assert_eq!(
+            Err(TemplateError::NoSlots),
+            Template::parse("{{}}").map(|_| ())
+        );
+
+        let words = list(&["for", "fort", "ress"]);
+        let template = Template::parse("{digit}{word}{word}")
+            .unwrap()
+            .with_wordlist(words.clone());
+        assert_eq!(
+            vec![Warning::AdjacentWords { slot: 1 }],
+            template.validate()
+        );
+        let template = Template::parse("{word}-{word}")
+            .unwrap()
+            .with_wordlist(words);
+        assert!(template.validate().is_empty());
+    }
+
+    #[test]
+    fn registry_components() {
+        use super::component::{word::FixedWordSetProvider, ComponentKind};
+        use super::registry::{ComponentSpec, Params, Registry, RegistryError, SchemeSpec};
+
+        let mut registry = Registry::with_builtins();
+        registry.register_word_set_provider("pin", |params| {
+            let digits = params.get_or("digits", 4)?;
+            Ok(Box::new(FixedWordSetProvider::new(
+                ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"],
+                digits,
+            )))
+        });
+        assert_eq!(
+            vec!["fixed", "pin"],
+            registry.names(ComponentKind::WordSetProvider)
+        );
+
+        let mut spec = SchemeSpec {
+            word_set_provider: ComponentSpec::new("pin", Params::new().with("digits", "6")),
+            word_filters: Vec::new(),
+            word_stylers: Vec::new(),
+            phrase_builder: ComponentSpec::new("basic", Params::new().with("separator", "")),
+            phrase_stylers: Vec::new(),
+            phrase_validators: Vec::new(),
+        };
+        let scheme = registry.build(&spec).unwrap();
+        assert_eq!(6, scheme.generate().len());
+        assert_eq!(
+            r#"Params({"a": "1", "b": "2", "c": "3"})"#,
+            format!(
+                "{:?}",
+                Params::new().with("c", "3").with("a", "1").with("b", "2")
+            )
+        );
+        assert!((scheme.entropy().bits() - 6.0 * 10f64.log2()).abs() < 1e-9);
+
+        spec.word_stylers.push(ComponentSpec::new(
+            "capitalize",
+            Params::new().with("first", "2"),
+        ));
+        assert_eq!(
+            Some(RegistryError::InvalidParam {
+                name: "first".into(),
+                value: "2".into(),
+            }),
+            registry.build(&spec).err()
+        );
+
+        spec.word_stylers = vec![ComponentSpec::new("shout", Params::new())];
+        assert_eq!(
+            Some(RegistryError::UnknownComponent {
+                kind: ComponentKind::WordStyler,
+                name: "shout".into(),
+            }),
+            registry.build(&spec).err()
+        );
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn scheme_builder_editing() {
+        use super::component::{
+            phrase::{BasicPhraseBuilder, YearAppender},
+            word::{FixedWordSetProvider, WordCapitalizer},
+        };
+
+        let capitalizer = || Box::new(WordCapitalizer::new(true.into(), false.into()));
+        let builder = Scheme::build()
+            .word_set_provider(Box::new(FixedWordSetProvider::new(
+                WordList::default().sampler(),
+                3,
+            )))
+            .add_word_styler(capitalizer())
+            .insert_word_styler(0, capitalizer())
+            .remove_word_styler(1)
+            .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+            .replace_phrase_builder(Box::new(BasicPhraseBuilder::new("-".into())))
+            .add_phrase_styler(Box::new(YearAppender::new(2000..=2031, " ".into())))
+            .remove_phrase_styler(0);
+        assert_eq!(1, builder.staged_word_stylers().count());
+        assert_eq!(0, builder.staged_phrase_stylers().count());
+        assert_eq!(
+            vec!["-"],
+            builder.staged_phrase_builder().unwrap().separators()
+        );
+
+        let scheme = builder.build().unwrap();
+        assert!(scheme.generate().starts_with(char::is_uppercase));
+    }
+
+    #[test]
+    fn year_appender_dates() {
+        use super::component::{
+            phrase::{DateFormat, YearAppender},
+            word::FixedWordSetProvider,
+        };
+        use super::prelude::*;
+        use super::registry::{ComponentSpec, Params, Registry, RegistryError, SchemeSpec};
+
+        let dates = |years| YearAppender::new(years, "-".into()).with_format(DateFormat::Date);
+        assert_eq!(365f64.log2(), dates(1900..=1900).entropy().bits());
+        assert_eq!(366f64.log2(), dates(2000..=2000).entropy().bits());
+        assert_eq!(731f64.log2(), dates(1999..=2000).entropy().bits());
+        assert_eq!(
+            (100.0 * 365.0 + 24.0f64).log2(),
+            dates(1801..=1900).entropy().bits()
+        );
+
+        let styler = dates(2024..=2024);
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..5000 {
+            let phrase = styler.style_phrase("x".into());
+            let date = phrase.strip_prefix("x-2024-").unwrap();
+            let (month, day) = date.split_at(2);
+            let month: usize = month.parse().unwrap();
+            let day: usize = day[1..].parse().unwrap();
+            let days = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+            assert!((1..=12).contains(&month) && (1..=days[month - 1]).contains(&day));
+            seen.insert(date.to_string());
+        }
+        assert!(seen.len() > 300);
+
+        let mut registry = Registry::with_builtins();
+        registry.register_word_set_provider("coin", |_| {
+            Ok(Box::new(FixedWordSetProvider::new(["heads", "tails"], 1)))
+        });
+        let mut spec = SchemeSpec {
+            word_set_provider: ComponentSpec::new("coin", Params::new()),
+            word_filters: Vec::new(),
+            word_stylers: Vec::new(),
+            phrase_builder: ComponentSpec::new("basic", Params::new()),
+            phrase_stylers: vec![ComponentSpec::new(
+                "year",
+                Params::new().with("format", "date"),
+            )],
+            phrase_validators: Vec::new(),
+        };
+        let bits = registry.build(&spec).unwrap().entropy().bits();
+        assert!((bits - 1.0 - (100.0 * 365.0 + 25.0f64).log2()).abs() < 1e-9);
+        spec.phrase_stylers = vec![ComponentSpec::new(
+            "year",
+            Params::new().with("format", "month"),
+        )];
+        assert_eq!(
+            Some(RegistryError::InvalidParam {
+                name: "format".into(),
+                value: "month".into(),
+            }),
+            registry.build(&spec).err()
+        );
+    }
+
+    #[test]
+    fn presets() {
+        use super::config::{preset, PRESETS};
+
+        // Presets using the default wordlist are unavailable without its feature
+        for config in PRESETS.iter().filter_map(|name| preset(name)) {
+            assert!(config.to_scheme().validate().is_empty());
+        }
+
+        let scheme = preset("PIN6").unwrap().to_scheme();
+        let pin = scheme.generate();
+        assert_eq!(6, pin.len());
+        assert!(pin.chars().all(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn themed_config_slots() {
+        use super::config::ThemedConfig;
+        use super::word::theme::Theme;
+
+        let scheme = ThemedConfig::default().to_scheme();
+        assert!(scheme.validate().is_empty());
+        assert_eq!(18.0, scheme.entropy().bits());
+
+        let custom = list(&["alpha", "beta"]);
+        let config = ThemedConfig::new(vec![Theme::Custom(custom), Theme::Number]);
+        for phrase in config.to_scheme().take(ITERS) {
+            let (word, number) = phrase.split_at(phrase.find('-').unwrap());
+            assert!(word == "alpha" || word == "beta");
+            assert!(number[1..].parse::<u8>().unwrap() < 100);
+        }
+    }
+
+    /// Word styler entropy must be accumulated for each word.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn word_styler_entropy_per_word() {
+        let scheme = BasicConfig::default().to_scheme();
+        let expected = 5.0 * 7776f64.log2() + 5.0;
+        assert!((scheme.entropy().bits() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tidy_edit_distance() {
+        use super::word::tidy::{TidyConfig, TidyReason};
+
+        let list = list(&["cat", "bat", "dog", "cart", "kitten", "sitting"]);
+        let config = TidyConfig {
+            min_edit_distance: 2,
+            ..TidyConfig::default()
+        };
+
+        let (list, report) = config.tidy(&list).unwrap();
+        assert_eq!(&["cat", "dog", "kitten", "sitting"], list.words());
+        assert_eq!(2, report.count(TidyReason::EditDistance));
+    }
+
+    /// Charset restricted builders must only produce passphrases within their charset.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn charset_builder_restricts_phrase() {
+        use super::charset::Charset;
+        use super::component::{
+            phrase::{CharsetPhraseBuilder, YearAppender},
+            word::FixedWordSetProvider,
+        };
+        use super::validate::Warning;
+
+        let scheme = Scheme::new(
+            Box::new(FixedWordSetProvider::new(
+                WordList::builtin_eff_large().sampler(),
+                5,
+            )),
+            Vec::new(),
+            Box::new(CharsetPhraseBuilder::new(Charset::UrlSafe)),
+            Vec::new(),
+        );
+        assert!(scheme
+            .take(ITERS)
+            .all(|phrase| Charset::UrlSafe.contains_all(&phrase)));
+
+        let scheme = Scheme::new(
+            Box::new(FixedWordSetProvider::new(["it's"], 2)),
+            Vec::new(),
+            Box::new(CharsetPhraseBuilder::new(Charset::UrlSafe)),
+            vec![Box::new(YearAppender::new(2000..=2031, " ".into()))],
+        );
+        let warnings = scheme.validate();
+        assert_eq!(2, warnings.len());
+        assert!(warnings.iter().all(|w| match w {
+            Warning::OutsideCharset { example, .. } => *example == '\'' || *example == ' ',
+            _ => false,
+        }));
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn generate_audited_replays() {
+        let config = BasicConfig::default().with_capitalize_first(false);
+        let scheme = config.to_scheme();
+        let list = WordList::default();
+
+        for _ in 0..ITERS {
+            let passphrase = scheme.generate_audited();
+            let audit = passphrase.audit().unwrap();
+            assert_eq!(
+                passphrase.phrase(),
+                scheme.generate_with_rng(&mut audit.replay())
+            );
+            for (word, index) in passphrase.words().iter().zip(audit.word_indices()) {
+                assert_eq!(word, &list.words()[index.unwrap()]);
+            }
+        }
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn generate_with_rng_all_components() {
+        use super::charset::Charset;
+        use super::component::{
+            phrase::{CharClass, CharsetPhraseBuilder, LeadingCharStyler, YearAppender},
+            word::{FixedWordSetProvider, NumberWordInjector, WordCapitalizer},
+        };
+        use super::probability::Probability;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let scheme = Scheme::new(
+            Box::new(NumberWordInjector::new(
+                FixedWordSetProvider::new(WordList::default().sampler(), 4),
+                1,
+            )),
+            vec![Box::new(WordCapitalizer::new(
+                Probability::half(),
+                Probability::from(0.1),
+            ))],
+            Box::new(CharsetPhraseBuilder::new(Charset::ShellSafe)),
+            vec![
+                Box::new(YearAppender::new(1950..=2049, "_".into())),
+                Box::new(LeadingCharStyler::new(CharClass::Uppercase)),
+            ],
+        );
+        for seed in 0..ITERS as u64 {
+            assert_eq!(
+                scheme.generate_with_rng(&mut StdRng::seed_from_u64(seed)),
+                scheme.generate_with_rng(&mut StdRng::seed_from_u64(seed)),
+            );
+        }
+    }
+
+    /// Generating with the same deterministic randomness source must produce the same passphrase.
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn generate_seeded_stable() {
+        let scheme = BasicConfig::default().to_scheme();
+        assert_eq!(
+            "Anemic Wieldable Tassel Crummy unabashed",
+            scheme.generate_seeded(0)
+        );
+        assert_ne!(scheme.generate_seeded(0), scheme.generate_seeded(1));
+    }
+
+    #[cfg(feature = "wordlist-eff-large")]
+    #[test]
+    fn generate_with_rng_deterministic() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let scheme = BasicConfig::default().to_scheme();
+        for seed in 0..ITERS as u64 {
+            assert_eq!(
+                scheme.generate_with_rng(&mut StdRng::seed_from_u64(seed)),
+                scheme.generate_with_rng(&mut StdRng::seed_from_u64(seed)),
+            );
+        }
+    }
+
+    /// Structured wordlists are loaded from the selected column, and reject invalid contents.
+    #[cfg(feature = "structured-formats")]
+    #[test]
+    fn structured_wordlists() {
+        use super::word::structured::{self, CsvColumn};
+        use super::word::WordListError;
+
+        let csv = "\u{feff}11111,abacus,3\n11112, abdomen ,1\n11113,,2\n";
+        let list = structured::from_csv(csv.as_bytes(), CsvColumn::Index(1)).unwrap();
+        assert_eq!(&["abacus", "abdomen"], list.words());
+        assert!(matches!(
+            structured::from_csv(csv.as_bytes(), CsvColumn::Index(3)),
+            Err(WordListError::MissingColumn(_)),
+        ));
+        assert!(matches!(
+            structured::from_csv(csv.as_bytes(), CsvColumn::Name("word".into())),
+            Err(WordListError::MissingColumn(_)),
+        ));
+
+        assert!(matches!(
+            structured::from_json(&b"[\"\", \" \"]"[..]),
+            Err(WordListError::Empty),
+        ));
+        assert!(matches!(
+            structured::from_json(&b"{\"words\": []}"[..]),
+            Err(WordListError::Json(_)),
+        ));
+    }
+
+    /// Mnemonics must match the BIP39 reference vectors, and reject invalid checksums.
+    #[cfg(feature = "bip39")]
+    #[test]
+    fn bip39_vectors() {
+        use super::bip39::{Mnemonic, MnemonicError};
+
+        let vectors: [(&[u8], &str); 3] = [
+            (
+                &[0x00; 16],
+                "abandon abandon abandon abandon abandon abandon \
+                 abandon abandon abandon abandon abandon about",
+            ),
+            (
+                &[0x7f; 16],
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            ),
+            (
+                &[0xff; 32],
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo \
+                 zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            ),
+        ];
+        for (entropy, phrase) in vectors.iter() {
+            let mnemonic = Mnemonic::from_entropy(entropy).unwrap();
+            assert_eq!(*phrase, mnemonic.phrase());
+            assert_eq!(mnemonic, Mnemonic::parse(&phrase.to_uppercase()).unwrap());
+            assert_eq!(*entropy, mnemonic.entropy_bytes());
+        }
+
+        let invalid = vectors[0].1.replace("about", "abandon");
+        assert_eq!(Err(MnemonicError::Checksum), Mnemonic::parse(&invalid));
+        assert_eq!(
+            Err(MnemonicError::WordCount(2)),
+            Mnemonic::parse("abandon about")
+        );
+        assert!(matches!(
+            Mnemonic::parse("abandon chbs"),
+            Err(MnemonicError::UnknownWord(_))
+        ));
+        assert_eq!(
+            Err(MnemonicError::EntropyLength(15)),
+            Mnemonic::from_entropy(&[0; 15])
+        );
+        assert_eq!(2048, WordList::builtin_bip39_english().words().len());
+    }
+
+    /// Generation and analysis results must serialize to JSON.
+    #[cfg(all(feature = "serde", feature = "wordlist-eff-large"))]
+    #[test]
+    fn serialize_configs() {
+        use super::config::ThemedConfig;
+        use super::word::WordSampler;
+
+        let json = serde_json::to_value(BasicConfig::default()).unwrap();
+        assert_eq!("eff_large", json["word_provider"]);
+        assert_eq!(0.5, json["capitalize_first"]);
+
+        let config: BasicConfig<WordSampler> = serde_json::from_str(
+            r#"{"words": 3, "word_provider": ["a", "b"], "separator": "-",
+                "capitalize_first": 1.0, "capitalize_words": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(3.0, config.to_scheme().entropy().bits());
+        assert!(config.to_scheme().generate().starts_with(['A', 'B']));
+
+        let invalid = r#"{"words": 3, "word_provider": "eff_large", "separator": " ",
+                "capitalize_first": 1.5, "capitalize_words": 0}"#;
+        assert!(serde_json::from_str::<BasicConfig<WordSampler>>(invalid).is_err());
+        let unknown = invalid.replace("eff_large", "unknown");
+        assert!(serde_json::from_str::<BasicConfig<WordSampler>>(&unknown).is_err());
+
+        let json = serde_json::to_string(&ThemedConfig::default()).unwrap();
+        let config: ThemedConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(ThemedConfig::default().slots.len(), config.slots.len());
+    }
+
+    #[cfg(all(feature = "serde", feature = "wordlist-eff-large"))]
+    #[test]
+    fn serialize_results() {
+        let scheme = BasicConfig::default().to_scheme();
+
+        let json = serde_json::to_value(scheme.generate_detailed()).unwrap();
+        assert_eq!(5, json["words"].as_array().unwrap().len());
+
+        let json = serde_json::to_value(scheme.entropy_report()).unwrap();
+        assert_eq!(scheme.entropy().bits(), json["total"].as_f64().unwrap());
+        assert_eq!("WordSetProvider", json["components"][0]["kind"]);
+    }
+
     #[test]
     fn threading() -> Result<(), RecvError> {
-        let scheme = Arc::new(BasicConfig::default().to_scheme());
+        let config = BasicConfig::new(["correct", "horse", "battery", "staple"]);
+        let scheme = Arc::new(config.to_scheme());
         let (tx, rx) = channel::<String>();
 
         let handle1 = spawn_thread(scheme.clone(), tx.clone());
@@ -219,4 +1989,27 @@ mod tests {
             tx.send(scheme.generate()).unwrap();
         })
     }
+
+    /// Browser tests, run with `wasm-pack test --headless --firefox -- --features wasm`.
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    mod wasm {
+        use wasm_bindgen_test::*;
+
+        use super::super::passphrase;
+        use super::super::word::WordList;
+
+        wasm_bindgen_test_configure!(run_in_browser);
+
+        #[wasm_bindgen_test]
+        fn passphrase_in_browser() {
+            assert!(passphrase().len() >= 10);
+        }
+
+        #[wasm_bindgen_test]
+        fn builtin_wordlists_in_browser() {
+            assert_eq!(7776, WordList::builtin_eff_large().words().len());
+            assert_eq!(1296, WordList::builtin_eff_short().words().len());
+            assert_eq!(1296, WordList::builtin_eff_general_short().words().len());
+        }
+    }
 }
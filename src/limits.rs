@@ -0,0 +1,182 @@
+//! Limits on regenerating rejected passphrases
+//!
+//! Passphrases rejected by a constraint are regenerated, such as by
+//! [phrase validators](crate::component::traits::PhraseValidator) of a
+//! [`Scheme`](crate::scheme::Scheme) or by the [guards](crate::guard). A constraint that rejects
+//! most passphrases could make this take very long, or never finish. [`GenerationLimits`]
+//! bounds the number of attempts and, optionally, the time spent. When a limit is exceeded, an
+//! error holding the [exceeded limit](LimitExceeded) is returned, naming the constraint that
+//! could not be met.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
+//! use std::time::Duration;
+//!
+//! use chbs::{config::BasicConfig, guard::UniqueGuard, limits::GenerationLimits, prelude::*};
+//!
+//! let limits = GenerationLimits::default()
+//!     .with_max_attempts(16)
+//!     .with_max_duration(Duration::from_millis(100));
+//! let mut guard = UniqueGuard::new(BasicConfig::default().to_scheme()).with_limits(limits);
+//! println!("Passphrase: {}", guard.try_next().unwrap());
+//! # }
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+/// The default maximum number of attempts to generate an accepted passphrase.
+const DEFAULT_MAX_ATTEMPTS: usize = 64;
+
+/// Limits on regenerating rejected passphrases.
+///
+/// At least one attempt is always made, even if the duration is exceeded before it. By default,
+/// 64 attempts are made and the duration is unlimited.
+///
+/// **Note:** the duration can't be limited on `wasm32-unknown-unknown`, as no clock is available
+/// there. Generating with a maximum duration panics on that target.
+///
+/// This struct is non-exhaustive, as fields may be added in future releases. Use
+/// [`new`](GenerationLimits::new) or [`default`](GenerationLimits::default) along with the
+/// `with_*` setters to construct it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GenerationLimits {
+    /// The maximum number of attempts.
+    pub max_attempts: usize,
+
+    /// The maximum time to spend on attempts, unlimited if `None`.
+    pub max_duration: Option<Duration>,
+}
+
+impl GenerationLimits {
+    /// Construct limits making at most the given number of attempts.
+    ///
+    /// # Panics
+    ///
+    /// `max_attempts` must be higher than zero.
+    pub fn new(max_attempts: usize) -> Self {
+        Self::default().with_max_attempts(max_attempts)
+    }
+
+    /// Set the maximum number of attempts.
+    ///
+    /// # Panics
+    ///
+    /// `max_attempts` must be higher than zero.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        if max_attempts == 0 {
+            panic!("cannot construct GenerationLimits with zero attempts");
+        }
+
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the maximum time to spend on attempts.
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Get these limits without a maximum duration.
+    ///
+    /// Used where attempts must be reproduced exactly, regardless of timing.
+    pub(crate) fn without_duration(mut self) -> Self {
+        self.max_duration = None;
+        self
+    }
+
+    /// Start tracking attempts against these limits.
+    pub(crate) fn start(self) -> Attempts {
+        Attempts {
+            limits: self,
+            started: self.max_duration.map(|_| Instant::now()),
+            made: 0,
+        }
+    }
+}
+
+impl Default for GenerationLimits {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            max_duration: None,
+        }
+    }
+}
+
+/// Attempts made against [`GenerationLimits`](GenerationLimits).
+#[derive(Debug)]
+pub(crate) struct Attempts {
+    /// The limits attempts are made against.
+    limits: GenerationLimits,
+
+    /// When the first attempt started, if the duration is limited.
+    started: Option<Instant>,
+
+    /// The number of attempts made.
+    made: usize,
+}
+
+impl Attempts {
+    /// Start the next attempt, or return the exceeded limit.
+    pub(crate) fn next(&mut self) -> Result<(), LimitExceeded> {
+        if self.made >= self.limits.max_attempts.max(1) {
+            return Err(LimitExceeded::Attempts(self.made));
+        }
+        if let (Some(started), Some(max_duration)) = (self.started, self.limits.max_duration) {
+            if self.made > 0 && started.elapsed() >= max_duration {
+                return Err(LimitExceeded::Duration {
+                    attempts: self.made,
+                    duration: max_duration,
+                });
+            }
+        }
+
+        self.made += 1;
+        Ok(())
+    }
+}
+
+/// A [`GenerationLimits`](GenerationLimits) limit that was exceeded.
+#[derive(Error, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The maximum number of attempts was made.
+    Attempts(usize),
+
+    /// The maximum duration passed.
+    Duration {
+        /// The number of attempts made.
+        attempts: usize,
+
+        /// The maximum duration.
+        duration: Duration,
+    },
+}
+
+impl LimitExceeded {
+    /// Get the number of attempts made.
+    pub fn attempts(self) -> usize {
+        match self {
+            LimitExceeded::Attempts(attempts) => attempts,
+            LimitExceeded::Duration { attempts, .. } => attempts,
+        }
+    }
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LimitExceeded::Attempts(attempts) => write!(f, "within {} attempts", attempts),
+            LimitExceeded::Duration { attempts, duration } => {
+                write!(f, "within {:?} ({} attempts)", duration, attempts)
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Locale-aware joining of passphrase words
+//!
+//! Not all languages separate words with spaces. Chinese, Japanese and Thai are written without
+//! separators, or use full-width punctuation instead. The [`Locale`](Locale) hint selects joining
+//! rules for such languages, so localized wordlists produce natural output. Use
+//! [`BasicPhraseBuilder::for_locale`](crate::component::phrase::BasicPhraseBuilder::for_locale) to
+//! build passphrases for a locale.
+//!
+//! Characters in these languages are often displayed twice as wide as latin characters. Use
+//! [`display_width`](display_width) to measure passphrases for layout, instead of the number of
+//! bytes or characters.
+
+/// The ideographic (full-width) space.
+pub const IDEOGRAPHIC_SPACE: &str = "\u{3000}";
+
+/// The ideographic (full-width) comma, used to separate list items in Chinese and Japanese.
+pub const IDEOGRAPHIC_COMMA: &str = "\u{3001}";
+
+/// The katakana middle dot, used to separate words in Japanese.
+pub const KATAKANA_MIDDLE_DOT: &str = "\u{30fb}";
+
+/// A locale hint, selecting how passphrase words are joined.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Locale {
+    /// Languages separating words with spaces, such as English or Korean.
+    Spaced,
+
+    /// Chinese, written without spaces between words.
+    Chinese,
+
+    /// Japanese, written without spaces between words.
+    Japanese,
+
+    /// Thai, written without spaces between words.
+    Thai,
+}
+
+impl Locale {
+    /// Get the locale hint for the given language tag, such as `en-US` or `zh-Hans`.
+    ///
+    /// Only the primary language subtag is considered. Unknown languages are assumed to use
+    /// spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::locale::Locale;
+    ///
+    /// assert_eq!(Locale::Japanese, Locale::from_tag("ja-JP"));
+    /// assert_eq!(Locale::Spaced, Locale::from_tag("ko"));
+    /// ```
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag.split(['-', '_']).next().unwrap_or("");
+        match language.to_ascii_lowercase().as_str() {
+            "zh" => Locale::Chinese,
+            "ja" => Locale::Japanese,
+            "th" => Locale::Thai,
+            _ => Locale::Spaced,
+        }
+    }
+
+    /// Get the natural separator to join words with in this locale.
+    ///
+    /// This is empty for languages written without spaces. Such passphrases can't always be split
+    /// into their words, consider one of the full-width [`separators`](Locale::separators) if
+    /// that is required.
+    pub fn separator(self) -> &'static str {
+        match self {
+            Locale::Spaced => " ",
+            Locale::Chinese | Locale::Japanese | Locale::Thai => "",
+        }
+    }
+
+    /// Get the separators suitable for this locale, starting with the natural
+    /// [`separator`](Locale::separator).
+    pub fn separators(self) -> &'static [&'static str] {
+        match self {
+            Locale::Spaced => &[" "],
+            Locale::Chinese => &["", IDEOGRAPHIC_SPACE, IDEOGRAPHIC_COMMA],
+            Locale::Japanese => &[
+                "",
+                IDEOGRAPHIC_SPACE,
+                KATAKANA_MIDDLE_DOT,
+                IDEOGRAPHIC_COMMA,
+            ],
+            Locale::Thai => &["", " "],
+        }
+    }
+}
+
+/// Get the number of terminal columns the given passphrase occupies when displayed.
+///
+/// Wide and full-width characters, such as CJK ideographs, kana, hangul and full-width
+/// punctuation, occupy two columns. Combining marks and zero-width characters occupy none. All
+/// other characters occupy one column. This is an approximation of the Unicode East Asian Width
+/// property, sufficient for laying out passphrases.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::locale::display_width;
+///
+/// assert_eq!(5, display_width("horse"));
+/// assert_eq!(4, display_width("馬電"));
+/// ```
+pub fn display_width(phrase: &str) -> usize {
+    phrase.chars().map(char_width).sum()
+}
+
+/// Get the number of terminal columns the given character occupies.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        // Combining marks, Thai vowel and tone marks, and zero-width characters
+        0x0300..=0x036F
+        | 0x0E31
+        | 0x0E34..=0x0E3A
+        | 0x0E47..=0x0E4E
+        | 0x200B..=0x200F
+        | 0xFE00..=0xFE0F => 0,
+
+        // Hangul jamo, CJK symbols, kana, ideographs, hangul syllables and full-width forms
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+
+        _ => 1,
+    }
+}
@@ -0,0 +1,309 @@
+//! Strength targets and constraints for passphrase configurations
+//!
+//! A [`Policy`](Policy) describes the strength a passphrase must have and the password rules it
+//! must comply with, while [`Constraints`](Constraints) describe the limits a passphrase must fit
+//! in, such as a maximum length imposed by a website. Use [`config::fit`](crate::config::fit) to
+//! find a configuration satisfying both.
+//!
+//! A policy is also a [`ToScheme`](ToScheme), producing a scheme generating compliant
+//! passphrases with the `wordlist-eff-large` feature. Common policies are available as
+//! [presets](preset).
+
+use crate::component::phrase::{CharClass, CharClassValidator};
+use crate::entropy::Entropy;
+use crate::prelude::*;
+use crate::word::WordList;
+#[cfg(feature = "wordlist-eff-large")]
+use crate::{
+    component::phrase::{BasicPhraseBuilder, CharClassAppender},
+    component::word::{FixedWordSetProvider, WordCapitalizer},
+    config::BasicConfig,
+    probability::Probability,
+    scheme::Scheme,
+};
+
+use super::DEFAULT_SEPARATOR;
+
+/// The default minimum entropy in bits a policy requires.
+const DEFAULT_MIN_ENTROPY: f64 = 64.0;
+
+/// The names of all built-in policy presets, see [`preset`](preset).
+pub const PRESETS: [&str; 3] = ["nist", "active-directory", "web"];
+
+/// A passphrase strength and compliance policy.
+///
+/// This describes the strength generated passphrases must have, and the rules they must comply
+/// with such as a minimum length and required character classes. The default policy requires at
+/// least 64 bits of entropy, and has no rules.
+///
+/// Use [`to_scheme`](ToScheme::to_scheme) to build a scheme generating compliant passphrases.
+///
+/// This struct is non-exhaustive, as fields may be added in future releases. Use
+/// [`new`](Policy::new) or [`default`](Policy::default) along with the `with_*` setters to
+/// construct it.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{component::phrase::CharClass, entropy::Entropy, policy::Policy, prelude::*};
+///
+/// let policy = Policy::new(Entropy::from_bits(80))
+///     .with_min_length(12)
+///     .with_required_classes(vec![CharClass::Uppercase, CharClass::Digit]);
+/// assert_eq!(80.0, policy.min_entropy.bits());
+///
+/// let scheme = policy.to_scheme();
+/// assert!(scheme.entropy() >= policy.min_entropy);
+/// assert!(policy.check(&scheme.generate()).is_ok());
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct Policy {
+    /// The minimum entropy passphrases must have.
+    pub min_entropy: Entropy,
+
+    /// The minimum length of passphrases in characters.
+    pub min_length: usize,
+
+    /// The character classes passphrases must contain a character of.
+    pub required_classes: Vec<CharClass>,
+}
+
+impl Policy {
+    /// Construct a policy requiring the given minimum entropy.
+    pub fn new(min_entropy: Entropy) -> Self {
+        Self {
+            min_entropy,
+            min_length: 0,
+            required_classes: Vec::new(),
+        }
+    }
+
+    /// Set the minimum entropy passphrases must have.
+    pub fn with_min_entropy(mut self, min_entropy: Entropy) -> Self {
+        self.min_entropy = min_entropy;
+        self
+    }
+
+    /// Set the minimum length of passphrases in characters.
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Set the character classes passphrases must contain a character of.
+    pub fn with_required_classes(mut self, required_classes: Vec<CharClass>) -> Self {
+        self.required_classes = required_classes;
+        self
+    }
+
+    /// Check whether the given passphrase complies with the rules of this policy.
+    ///
+    /// This checks the minimum length and required character classes. The entropy of a single
+    /// passphrase can't be checked, it is a property of the scheme generating it.
+    pub fn check(&self, phrase: &str) -> Result<(), String> {
+        let length = phrase.chars().count();
+        if length < self.min_length {
+            return Err(format!(
+                "passphrase has {} characters, below the minimum of {}",
+                length, self.min_length
+            ));
+        }
+        CharClassValidator::new(self.required_classes.clone()).validate_phrase(phrase)
+    }
+}
+
+#[cfg(feature = "wordlist-eff-large")]
+impl ToScheme for Policy {
+    /// Build a scheme generating passphrases complying with this policy.
+    ///
+    /// Passphrases consist of words from the default wordlist, separated by spaces. The first
+    /// word is always capitalized if an uppercase character is required, and a random character
+    /// is appended for each required digit or symbol class. The number of words is the minimum
+    /// reaching both the minimum entropy and minimum length.
+    ///
+    /// Only available with the `wordlist-eff-large` feature, enabled by default.
+    ///
+    /// # Panics
+    ///
+    /// The minimum entropy must be [valid](Entropy::is_valid).
+    fn to_scheme(&self) -> Scheme {
+        if !self.min_entropy.is_valid() {
+            panic!("cannot build scheme for policy with invalid minimum entropy");
+        }
+
+        let uppercase = self.required_classes.contains(&CharClass::Uppercase);
+        let appended: Vec<CharClass> = self
+            .required_classes
+            .iter()
+            .copied()
+            .filter(|class| matches!(class, CharClass::Digit | CharClass::Symbol))
+            .collect();
+        let appender = CharClassAppender::new(appended.clone());
+
+        let wordlist = WordList::default();
+        let word_length = wordlist
+            .words()
+            .iter()
+            .map(|w| w.chars().count())
+            .min()
+            .unwrap_or(0);
+        let capitalize_first = if uppercase {
+            Probability::Always
+        } else {
+            Probability::half()
+        };
+        let config = BasicConfig::new(wordlist.sampler()).with_capitalize_first(capitalize_first);
+
+        // Find the minimal word count reaching the entropy and the length of the shortest phrase
+        let separator_length = DEFAULT_SEPARATOR.chars().count();
+        let mut words = 1;
+        loop {
+            let scheme = config.clone().with_words(words).to_scheme();
+            let length = words * word_length + (words - 1) * separator_length + appended.len();
+            if scheme.entropy() + appender.entropy() >= self.min_entropy
+                && length >= self.min_length
+            {
+                break;
+            }
+            words += 1;
+        }
+
+        Scheme::build()
+            .word_set_provider(Box::new(FixedWordSetProvider::new(
+                config.word_provider.clone(),
+                words,
+            )))
+            .word_stylers(vec![Box::new(WordCapitalizer::new(
+                capitalize_first,
+                Probability::Never,
+            ))])
+            .phrase_builder(Box::new(BasicPhraseBuilder::new(DEFAULT_SEPARATOR.into())))
+            .phrase_stylers(vec![Box::new(appender)])
+            .add_phrase_validator(Box::new(CharClassValidator::new(
+                self.required_classes.clone(),
+            )))
+            .build()
+            .unwrap()
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new(Entropy::from_bits(DEFAULT_MIN_ENTROPY))
+    }
+}
+
+/// Constraints a passphrase configuration must fit in.
+///
+/// By default passphrases aren't limited in length, use the `" "` separator and may use any of
+/// the [built-in wordlists](WordList::BUILTIN_NAMES).
+///
+/// This struct is non-exhaustive, as fields may be added in future releases. Use
+/// [`default`](Constraints::default) along with the `with_*` setters to construct it.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-short")]
+/// # {
+/// use chbs::{policy::Constraints, word::WordList};
+///
+/// let constraints = Constraints::default()
+///     .with_max_length(32)
+///     .with_separator("-")
+///     .with_wordlists(vec![WordList::builtin_eff_short()]);
+/// assert_eq!(Some(32), constraints.max_length);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Constraints {
+    /// The maximum length of passphrases in characters, unlimited if `None`.
+    pub max_length: Option<usize>,
+
+    /// The separator to use between passphrase words.
+    pub separator: String,
+
+    /// The wordlists passphrase words may be sampled from.
+    pub wordlists: Vec<WordList>,
+}
+
+impl Constraints {
+    /// Set the maximum length of passphrases in characters.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Set the separator to use between passphrase words.
+    pub fn with_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Set the wordlists passphrase words may be sampled from.
+    pub fn with_wordlists(mut self, wordlists: Vec<WordList>) -> Self {
+        self.wordlists = wordlists;
+        self
+    }
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self {
+            max_length: None,
+            separator: DEFAULT_SEPARATOR.into(),
+            wordlists: WordList::BUILTIN_NAMES
+                .iter()
+                .filter_map(|name| WordList::builtin(name))
+                .collect(),
+        }
+    }
+}
+
+/// Get a built-in policy preset by name.
+///
+/// Names are matched case-insensitively. Returns `None` if there is no preset with the given
+/// name. See [`PRESETS`](PRESETS) for all available names:
+///
+/// - `nist`: following NIST SP 800-63B, at least 8 characters and no composition rules, as the
+///   guidelines advise against them.
+/// - `active-directory`: a typical corporate Active Directory policy, at least 8 characters with
+///   an uppercase, lowercase and digit character, satisfying its complexity requirements.
+/// - `web`: the rules common on websites, at least 8 characters with an uppercase, digit and
+///   symbol character.
+///
+/// All presets require the [default](Policy::default) minimum entropy of 64 bits.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{policy::{preset, PRESETS}, prelude::*};
+///
+/// for name in PRESETS.iter() {
+///     let policy = preset(name).unwrap();
+///     let phrase = policy.to_scheme().generate();
+///     assert!(policy.check(&phrase).is_ok());
+///     println!("{}: {}", name, phrase);
+/// }
+///
+/// assert!(preset("unknown").is_none());
+/// # }
+/// ```
+pub fn preset(name: &str) -> Option<Policy> {
+    let policy = Policy::default().with_min_length(8);
+    let classes = match name.to_ascii_lowercase().as_str() {
+        "nist" => vec![],
+        "active-directory" => vec![CharClass::Uppercase, CharClass::Lowercase, CharClass::Digit],
+        "web" => vec![CharClass::Uppercase, CharClass::Digit, CharClass::Symbol],
+        _ => return None,
+    };
+    Some(policy.with_required_classes(classes))
+}
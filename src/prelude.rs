@@ -6,14 +6,18 @@
 //!
 //! # Examples
 //! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
 //! use chbs::{config::BasicConfig, prelude::*};
 //!
 //! let config = BasicConfig::default();
 //!
 //! // This method requires the ToScheme trait, imported through prelude
 //! let scheme = config.to_scheme();
+//! # }
 //! ```
 
 pub use crate::component::traits::*;
 pub use crate::entropy::HasEntropy;
+pub use crate::rng::SecureRng;
 pub use crate::scheme::ToScheme;
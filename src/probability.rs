@@ -8,10 +8,14 @@
 //!
 //! See [`Probability`](Probability) for more details.
 
-use rand::{prelude::*, thread_rng};
+use std::convert::TryFrom;
+
+use rand::prelude::*;
+use thiserror::Error;
 
 use crate::entropy::Entropy;
 use crate::prelude::*;
+use crate::rng::default_rng;
 
 /// A probability definition.
 ///
@@ -20,7 +24,14 @@ use crate::prelude::*;
 /// The function [`gen_bool`](Probability::gen_bool) can be used to generate a boolean based on
 /// this probability. Depending on what randomness source is given, it may be cryptographically
 /// secure.
+///
+/// With the `serde` feature, this is serialized as probability value, such as `0.5`.
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "f64", try_from = "f64")
+)]
 pub enum Probability {
     /// This is always true.
     Always,
@@ -93,7 +104,7 @@ impl Probability {
     ///
     /// If the given randomness source to `rng` is cryptographically secure,
     /// the generated boolean can be considered cryptographically secure as well.
-    pub fn gen_bool<R: Rng>(self, rng: &mut R) -> bool {
+    pub fn gen_bool<R: Rng + ?Sized>(self, rng: &mut R) -> bool {
         match self {
             Probability::Always => true,
             Probability::Never => false,
@@ -110,16 +121,20 @@ impl Probability {
         match self {
             Probability::Always => true,
             Probability::Never => false,
-            Probability::Sometimes(_) => self.gen_bool(&mut thread_rng()),
+            Probability::Sometimes(_) => self.gen_bool(&mut default_rng()),
         }
     }
 }
 
 impl HasEntropy for Probability {
+    /// The entropy of a single random decision based on this probability.
+    ///
+    /// This is the binary Shannon entropy `H(p)`, which is `1` bit for a 50/50 chance and
+    /// approaches zero as the probability approaches `Always` or `Never`. For `N` independent
+    /// decisions the entropy is `N × H(p)`.
     fn entropy(&self) -> Entropy {
         match self {
-            // TODO: properly calculate entropy here
-            Probability::Sometimes(_p) => Entropy::one(),
+            Probability::Sometimes(p) => Entropy::from_distribution(vec![*p, 1.0 - *p]),
             _ => Entropy::zero(),
         }
     }
@@ -135,3 +150,29 @@ impl From<bool> for Probability {
         }
     }
 }
+
+impl From<Probability> for f64 {
+    fn from(probability: Probability) -> f64 {
+        probability.value()
+    }
+}
+
+/// Construct a probability from a probability value, which must be between `0` and `1`.
+///
+/// Unlike [`Probability::from`](Probability::from), values outside this range are rejected.
+impl TryFrom<f64> for Probability {
+    type Error = InvalidProbability;
+
+    fn try_from(probability: f64) -> Result<Self, Self::Error> {
+        if (0.0..=1.0).contains(&probability) {
+            Ok(Probability::from(probability))
+        } else {
+            Err(InvalidProbability(probability))
+        }
+    }
+}
+
+/// A probability value that is not between `0` and `1`.
+#[derive(Debug, Error)]
+#[error("probability {0} is not between 0 and 1")]
+pub struct InvalidProbability(pub f64);
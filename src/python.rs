@@ -0,0 +1,111 @@
+//! Python bindings
+//!
+//! This module exposes passphrase generation to Python through [PyO3](https://pyo3.rs), so
+//! scripts can use the same generation policy without spawning a subprocess. The following is
+//! available from the `chbs` Python module:
+//!
+//! - `passphrase()`: generate a passphrase with the default configuration, see
+//!   [`passphrase`](crate::passphrase).
+//! - `BasicConfig(words=5, separator=" ", capitalize_first=0.5, capitalize_words=0.0)`: a
+//!   [`BasicConfig`](crate::config::BasicConfig) using the default wordlist. Capitalization is
+//!   configured by probability, between `0` and `1`. Invalid arguments raise `ValueError`.
+//! - `BasicConfig.to_scheme()`: build a `Scheme`.
+//! - `Scheme.generate()` and `Scheme.entropy()`: generate a passphrase, and get the entropy of
+//!   passphrases in bits.
+//!
+//! Only available with the `pyo3` feature. Build the extension module as dynamic library, for
+//! example using `cargo rustc --release --features pyo3 --crate-type cdylib`, and rename it to
+//! `chbs.so` (or `chbs.pyd` on Windows).
+//!
+//! ```python
+//! import chbs
+//!
+//! scheme = chbs.BasicConfig(words=6, separator=" ").to_scheme()
+//! print(scheme.generate(), scheme.entropy())
+//! ```
+
+use std::convert::TryFrom;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::config::BasicConfig;
+use crate::prelude::*;
+use crate::probability::Probability;
+use crate::scheme::Scheme;
+use crate::word::WordSampler;
+
+/// Generate a passphrase with the default configuration.
+#[pyfunction]
+fn passphrase() -> String {
+    crate::passphrase()
+}
+
+/// A basic passphrase configuration, using the default wordlist.
+#[pyclass(name = "BasicConfig")]
+struct PyBasicConfig(BasicConfig<WordSampler>);
+
+#[pymethods]
+impl PyBasicConfig {
+    /// Raises `ValueError` if `words` is zero, or if a probability is not between `0` and `1`.
+    #[new]
+    #[pyo3(signature = (
+        words = crate::DEFAULT_WORDS,
+        separator = crate::DEFAULT_SEPARATOR,
+        capitalize_first = 0.5,
+        capitalize_words = 0.0,
+    ))]
+    fn new(
+        words: usize,
+        separator: &str,
+        capitalize_first: f64,
+        capitalize_words: f64,
+    ) -> PyResult<Self> {
+        if words == 0 {
+            return Err(PyValueError::new_err("words: must be at least 1"));
+        }
+        Ok(Self(
+            BasicConfig::default()
+                .with_words(words)
+                .with_separator(separator)
+                .with_capitalize_first(probability("capitalize_first", capitalize_first)?)
+                .with_capitalize_words(probability("capitalize_words", capitalize_words)?),
+        ))
+    }
+
+    /// Build a scheme based on this configuration.
+    fn to_scheme(&self) -> PyScheme {
+        PyScheme(self.0.to_scheme())
+    }
+}
+
+/// Convert a probability argument, raising `ValueError` if it is not between `0` and `1`.
+fn probability(name: &str, value: f64) -> PyResult<Probability> {
+    Probability::try_from(value).map_err(|err| PyValueError::new_err(format!("{}: {}", name, err)))
+}
+
+/// A passphrase generation scheme.
+#[pyclass(name = "Scheme")]
+struct PyScheme(Scheme);
+
+#[pymethods]
+impl PyScheme {
+    /// Generate a single passphrase.
+    fn generate(&self) -> String {
+        self.0.generate()
+    }
+
+    /// Get the entropy of passphrases generated by this scheme, in bits.
+    fn entropy(&self) -> f64 {
+        self.0.entropy().bits()
+    }
+}
+
+/// The `chbs` Python module.
+#[pymodule]
+fn chbs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(passphrase, m)?)?;
+    m.add_class::<PyBasicConfig>()?;
+    m.add_class::<PyScheme>()?;
+    Ok(())
+}
@@ -0,0 +1,603 @@
+//! Runtime registry of named components
+//!
+//! A [`Registry`](Registry) maps string names to factories constructing components from
+//! [parameters](Params). This allows building a [`Scheme`](crate::scheme::Scheme) from user
+//! configuration at runtime, such as settings in a graphical interface, instead of only through
+//! compile-time types. Custom components may be registered next to the
+//! [built-in](Registry::with_builtins) ones.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
+//! use chbs::{
+//!     prelude::*,
+//!     registry::{ComponentSpec, Params, Registry, SchemeSpec},
+//! };
+//!
+//! let registry = Registry::with_builtins();
+//! let spec = SchemeSpec {
+//!     word_set_provider: ComponentSpec::new("fixed", Params::new().with("words", "4")),
+//!     word_filters: vec![ComponentSpec::new("length", Params::new().with("min", "4"))],
+//!     word_stylers: vec![ComponentSpec::new("capitalize", Params::new().with("first", "1"))],
+//!     phrase_builder: ComponentSpec::new("basic", Params::new().with("separator", "-")),
+//!     phrase_stylers: vec![ComponentSpec::new("year", Params::new())],
+//!     phrase_validators: vec![ComponentSpec::new("length", Params::new().with("max", "48"))],
+//! };
+//!
+//! let scheme = registry.build(&spec).unwrap();
+//! println!("Passphrase: {}", scheme.generate());
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Formatter};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::charset::Charset;
+use crate::component::{
+    phrase::{
+        BannedSubstringValidator, BasicPhraseBuilder, CharClass, CharClassAppender,
+        CharClassValidator, CharsetPhraseBuilder, DateFormat, LeadingCharStyler,
+        MaxLengthValidator, YearAppender,
+    },
+    word::{
+        BlockedWordFilter, FixedWordSetProvider, InteriorCapitalizer, WordCapitalizer,
+        WordLengthFilter,
+    },
+    ComponentKind,
+};
+use crate::prelude::*;
+use crate::probability::Probability;
+use crate::scheme::Scheme;
+use crate::word::WordList;
+
+/// A factory constructing a component of type `T` from parameters.
+pub type Factory<T> = Box<dyn Fn(&Params) -> Result<T, RegistryError> + Send + Sync>;
+
+/// Parameters to construct a component with.
+///
+/// Parameters are given as strings, as they usually originate from user configuration, and are
+/// parsed by the component factory. They are kept ordered by name, so their debug and serialized
+/// forms are stable.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// Construct an empty set of parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the parameter `name` to the given `value`.
+    pub fn with<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.0.insert(name.into(), value.into());
+        self
+    }
+
+    /// Get the raw value of parameter `name`.
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Get and parse the value of parameter `name`, or `None` if it is not set.
+    ///
+    /// An error is returned if the value can't be parsed.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, RegistryError> {
+        self.get_str(name)
+            .map(|value| {
+                value.parse().map_err(|_| RegistryError::InvalidParam {
+                    name: name.into(),
+                    value: value.into(),
+                })
+            })
+            .transpose()
+    }
+
+    /// Get and parse the value of parameter `name`, or `default` if it is not set.
+    ///
+    /// An error is returned if the value can't be parsed.
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, RegistryError> {
+        Ok(self.get(name)?.unwrap_or(default))
+    }
+
+    /// Get and parse the probability parameter `name`, or `default` if it is not set.
+    ///
+    /// The value must be between `0` and `1`.
+    pub fn get_probability(
+        &self,
+        name: &str,
+        default: Probability,
+    ) -> Result<Probability, RegistryError> {
+        match self.get::<f64>(name)? {
+            Some(value) => Probability::try_from(value).map_err(|_| RegistryError::InvalidParam {
+                name: name.into(),
+                value: value.to_string(),
+            }),
+            None => Ok(default),
+        }
+    }
+}
+
+/// A named component with its parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentSpec {
+    /// The name the component is registered under.
+    pub name: String,
+
+    /// The parameters to construct the component with.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub params: Params,
+}
+
+impl ComponentSpec {
+    /// Construct a specification for the component `name` with the given parameters.
+    pub fn new<N: Into<String>>(name: N, params: Params) -> Self {
+        Self {
+            name: name.into(),
+            params,
+        }
+    }
+}
+
+/// A scheme defined by named components, built using a [`Registry`](Registry).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemeSpec {
+    /// The word set provider.
+    pub word_set_provider: ComponentSpec,
+
+    /// The word filters, in order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub word_filters: Vec<ComponentSpec>,
+
+    /// The word stylers, in order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub word_stylers: Vec<ComponentSpec>,
+
+    /// The phrase builder.
+    pub phrase_builder: ComponentSpec,
+
+    /// The phrase stylers, in order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub phrase_stylers: Vec<ComponentSpec>,
+
+    /// The phrase validators.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub phrase_validators: Vec<ComponentSpec>,
+}
+
+/// A registry of component factories by name.
+///
+/// Use [`with_builtins`](Registry::with_builtins) for a registry with the components of this
+/// crate, and the `register_` methods to add custom components. Registering a name that is
+/// already registered for the same kind of component replaces it.
+#[derive(Default)]
+pub struct Registry {
+    /// Word set provider factories by name.
+    word_set_providers: HashMap<String, Factory<Box<dyn WordSetProvider>>>,
+
+    /// Word filter factories by name.
+    word_filters: HashMap<String, Factory<Box<dyn WordFilter>>>,
+
+    /// Word styler factories by name.
+    word_stylers: HashMap<String, Factory<Box<dyn WordStyler>>>,
+
+    /// Phrase builder factories by name.
+    phrase_builders: HashMap<String, Factory<Box<dyn PhraseBuilder>>>,
+
+    /// Phrase styler factories by name.
+    phrase_stylers: HashMap<String, Factory<Box<dyn PhraseStyler>>>,
+
+    /// Phrase validator factories by name.
+    phrase_validators: HashMap<String, Factory<Box<dyn PhraseValidator>>>,
+}
+
+impl Registry {
+    /// Construct an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct a registry with the built-in components of this crate.
+    ///
+    /// The following components are registered, parameters are optional:
+    ///
+    /// - Word set provider `fixed`: words from a built-in wordlist, see
+    ///   [`FixedWordSetProvider`](FixedWordSetProvider). Parameters `words` (`5`) and `wordlist`
+    ///   (`eff_large`, see [`WordList::BUILTIN_NAMES`](WordList::BUILTIN_NAMES)).
+    /// - Word filter `length`: see [`WordLengthFilter`](WordLengthFilter). Parameters `min` (`0`)
+    ///   and `max` (unlimited).
+    /// - Word filter `blocked`: see [`BlockedWordFilter`](BlockedWordFilter). Parameter `words`, a
+    ///   comma separated list.
+    /// - Word styler `capitalize`: see [`WordCapitalizer`](WordCapitalizer). Parameters `first`
+    ///   (`0.5`) and `all` (`0`), as probability.
+    /// - Word styler `interior`: see [`InteriorCapitalizer`](InteriorCapitalizer). Parameters
+    ///   `probability` (`1`) and `min_length` (`3`).
+    /// - Phrase builder `basic`: see [`BasicPhraseBuilder`](BasicPhraseBuilder). Parameter
+    ///   `separator` (` `).
+    /// - Phrase builder `charset`: see [`CharsetPhraseBuilder`](CharsetPhraseBuilder). Parameter
+    ///   `charset` (`shell-safe`), or `url-safe`.
+    /// - Phrase styler `year`: see [`YearAppender`](YearAppender). Parameters `from` (`1950`),
+    ///   `to` (`2049`), `separator` (` `) and `format` (`year`), or `date`.
+    /// - Phrase styler `leading`: see [`LeadingCharStyler`](LeadingCharStyler). Parameter `class`
+    ///   (`letter`), or `lowercase`, `uppercase`, `alphanumeric`, `digit` or `symbol`.
+    /// - Phrase styler `append`: see [`CharClassAppender`](CharClassAppender). Parameter
+    ///   `classes`, a comma separated list of character classes as for `leading`.
+    /// - Phrase validator `length`: see [`MaxLengthValidator`](MaxLengthValidator). Parameter
+    ///   `max`, required.
+    /// - Phrase validator `classes`: see [`CharClassValidator`](CharClassValidator). Parameter
+    ///   `require`, a comma separated list of character classes as for `leading`.
+    /// - Phrase validator `banned`: see [`BannedSubstringValidator`](BannedSubstringValidator).
+    ///   Parameter `substrings`, a comma separated list.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+
+        registry.register_word_set_provider("fixed", |params| {
+            let words: usize = params.get_or("words", 5)?;
+            let name = params.get_str("wordlist").unwrap_or("eff_large");
+            let list = WordList::builtin(name).ok_or_else(|| RegistryError::InvalidParam {
+                name: "wordlist".into(),
+                value: name.into(),
+            })?;
+            if words == 0 {
+                return Err(RegistryError::InvalidParam {
+                    name: "words".into(),
+                    value: "0".into(),
+                });
+            }
+            Ok(Box::new(FixedWordSetProvider::new(list.sampler(), words)))
+        });
+        registry.register_word_filter("length", |params| {
+            let min = params.get_or("min", 0)?;
+            let max = params.get_or("max", usize::MAX)?;
+            Ok(Box::new(WordLengthFilter::new(min..=max)))
+        });
+        registry.register_word_filter("blocked", |params| {
+            let blocked = list(params.get_str("words").unwrap_or(""))
+                .map(Into::into)
+                .collect();
+            Ok(Box::new(BlockedWordFilter::new(blocked)))
+        });
+        registry.register_word_styler("capitalize", |params| {
+            Ok(Box::new(WordCapitalizer::new(
+                params.get_probability("first", Probability::half())?,
+                params.get_probability("all", Probability::Never)?,
+            )))
+        });
+        registry.register_word_styler("interior", |params| {
+            Ok(Box::new(InteriorCapitalizer::new(
+                params.get_probability("probability", Probability::Always)?,
+                params.get_or("min_length", 3)?,
+            )))
+        });
+        registry.register_phrase_builder("basic", |params| {
+            let separator = params.get_str("separator").unwrap_or(" ");
+            Ok(Box::new(BasicPhraseBuilder::new(separator.into())))
+        });
+        registry.register_phrase_builder("charset", |params| {
+            let charset = match params.get_str("charset").unwrap_or("shell-safe") {
+                "shell-safe" => Charset::ShellSafe,
+                "url-safe" => Charset::UrlSafe,
+                value => {
+                    return Err(RegistryError::InvalidParam {
+                        name: "charset".into(),
+                        value: value.into(),
+                    })
+                }
+            };
+            Ok(Box::new(CharsetPhraseBuilder::new(charset)))
+        });
+        registry.register_phrase_styler("year", |params| {
+            let from = params.get_or("from", 1950)?;
+            let to = params.get_or("to", 2049)?;
+            if from > to {
+                return Err(RegistryError::InvalidParam {
+                    name: "to".into(),
+                    value: to.to_string(),
+                });
+            }
+            let format = match params.get_str("format").unwrap_or("year") {
+                "year" => DateFormat::Year,
+                "date" => DateFormat::Date,
+                value => {
+                    return Err(RegistryError::InvalidParam {
+                        name: "format".into(),
+                        value: value.into(),
+                    })
+                }
+            };
+            let separator = params.get_str("separator").unwrap_or(" ");
+            Ok(Box::new(
+                YearAppender::new(from..=to, separator.into()).with_format(format),
+            ))
+        });
+        registry.register_phrase_styler("leading", |params| {
+            let class = char_class("class", params.get_str("class").unwrap_or("letter"))?;
+            Ok(Box::new(LeadingCharStyler::new(class)))
+        });
+        registry.register_phrase_styler("append", |params| {
+            let classes = list(params.get_str("classes").unwrap_or(""))
+                .map(|class| char_class("classes", class))
+                .collect::<Result<_, _>>()?;
+            Ok(Box::new(CharClassAppender::new(classes)))
+        });
+        registry.register_phrase_validator("length", |params| {
+            let max = params
+                .get("max")?
+                .ok_or_else(|| RegistryError::MissingParam("max".into()))?;
+            Ok(Box::new(MaxLengthValidator::new(max)))
+        });
+        registry.register_phrase_validator("classes", |params| {
+            let classes = list(params.get_str("require").unwrap_or(""))
+                .map(|class| char_class("require", class))
+                .collect::<Result<_, _>>()?;
+            Ok(Box::new(CharClassValidator::new(classes)))
+        });
+        registry.register_phrase_validator("banned", |params| {
+            let banned = list(params.get_str("substrings").unwrap_or(""))
+                .map(Into::into)
+                .collect();
+            Ok(Box::new(BannedSubstringValidator::new(banned)))
+        });
+
+        registry
+    }
+
+    /// Register a word set provider factory under the given name.
+    pub fn register_word_set_provider<N, F>(&mut self, name: N, factory: F)
+    where
+        N: Into<String>,
+        F: Fn(&Params) -> Result<Box<dyn WordSetProvider>, RegistryError> + Send + Sync + 'static,
+    {
+        self.word_set_providers
+            .insert(name.into(), Box::new(factory));
+    }
+
+    /// Register a word filter factory under the given name.
+    pub fn register_word_filter<N, F>(&mut self, name: N, factory: F)
+    where
+        N: Into<String>,
+        F: Fn(&Params) -> Result<Box<dyn WordFilter>, RegistryError> + Send + Sync + 'static,
+    {
+        self.word_filters.insert(name.into(), Box::new(factory));
+    }
+
+    /// Register a word styler factory under the given name.
+    pub fn register_word_styler<N, F>(&mut self, name: N, factory: F)
+    where
+        N: Into<String>,
+        F: Fn(&Params) -> Result<Box<dyn WordStyler>, RegistryError> + Send + Sync + 'static,
+    {
+        self.word_stylers.insert(name.into(), Box::new(factory));
+    }
+
+    /// Register a phrase builder factory under the given name.
+    pub fn register_phrase_builder<N, F>(&mut self, name: N, factory: F)
+    where
+        N: Into<String>,
+        F: Fn(&Params) -> Result<Box<dyn PhraseBuilder>, RegistryError> + Send + Sync + 'static,
+    {
+        self.phrase_builders.insert(name.into(), Box::new(factory));
+    }
+
+    /// Register a phrase styler factory under the given name.
+    pub fn register_phrase_styler<N, F>(&mut self, name: N, factory: F)
+    where
+        N: Into<String>,
+        F: Fn(&Params) -> Result<Box<dyn PhraseStyler>, RegistryError> + Send + Sync + 'static,
+    {
+        self.phrase_stylers.insert(name.into(), Box::new(factory));
+    }
+
+    /// Register a phrase validator factory under the given name.
+    pub fn register_phrase_validator<N, F>(&mut self, name: N, factory: F)
+    where
+        N: Into<String>,
+        F: Fn(&Params) -> Result<Box<dyn PhraseValidator>, RegistryError> + Send + Sync + 'static,
+    {
+        self.phrase_validators
+            .insert(name.into(), Box::new(factory));
+    }
+
+    /// Get the registered names for the given kind of component, sorted.
+    pub fn names(&self, kind: ComponentKind) -> Vec<&str> {
+        let mut names: Vec<&str> = match kind {
+            ComponentKind::WordSetProvider => keys(&self.word_set_providers),
+            ComponentKind::WordFilter => keys(&self.word_filters),
+            ComponentKind::WordStyler => keys(&self.word_stylers),
+            ComponentKind::PhraseBuilder => keys(&self.phrase_builders),
+            ComponentKind::PhraseStyler => keys(&self.phrase_stylers),
+            ComponentKind::PhraseValidator => keys(&self.phrase_validators),
+        };
+        names.sort_unstable();
+        names
+    }
+
+    /// Construct the word set provider registered under the given name.
+    pub fn word_set_provider(
+        &self,
+        spec: &ComponentSpec,
+    ) -> Result<Box<dyn WordSetProvider>, RegistryError> {
+        construct(
+            &self.word_set_providers,
+            ComponentKind::WordSetProvider,
+            spec,
+        )
+    }
+
+    /// Construct the word filter registered under the given name.
+    pub fn word_filter(&self, spec: &ComponentSpec) -> Result<Box<dyn WordFilter>, RegistryError> {
+        construct(&self.word_filters, ComponentKind::WordFilter, spec)
+    }
+
+    /// Construct the word styler registered under the given name.
+    pub fn word_styler(&self, spec: &ComponentSpec) -> Result<Box<dyn WordStyler>, RegistryError> {
+        construct(&self.word_stylers, ComponentKind::WordStyler, spec)
+    }
+
+    /// Construct the phrase builder registered under the given name.
+    pub fn phrase_builder(
+        &self,
+        spec: &ComponentSpec,
+    ) -> Result<Box<dyn PhraseBuilder>, RegistryError> {
+        construct(&self.phrase_builders, ComponentKind::PhraseBuilder, spec)
+    }
+
+    /// Construct the phrase styler registered under the given name.
+    pub fn phrase_styler(
+        &self,
+        spec: &ComponentSpec,
+    ) -> Result<Box<dyn PhraseStyler>, RegistryError> {
+        construct(&self.phrase_stylers, ComponentKind::PhraseStyler, spec)
+    }
+
+    /// Construct the phrase validator registered under the given name.
+    pub fn phrase_validator(
+        &self,
+        spec: &ComponentSpec,
+    ) -> Result<Box<dyn PhraseValidator>, RegistryError> {
+        construct(
+            &self.phrase_validators,
+            ComponentKind::PhraseValidator,
+            spec,
+        )
+    }
+
+    /// Build a scheme from the given specification, constructing all its components.
+    ///
+    /// An error is returned if any component is not registered, or can't be constructed with
+    /// its parameters.
+    pub fn build(&self, spec: &SchemeSpec) -> Result<Scheme, RegistryError> {
+        Scheme::build()
+            .word_set_provider(self.word_set_provider(&spec.word_set_provider)?)
+            .word_filters(
+                spec.word_filters
+                    .iter()
+                    .map(|spec| self.word_filter(spec))
+                    .collect::<Result<_, _>>()?,
+            )
+            .word_stylers(
+                spec.word_stylers
+                    .iter()
+                    .map(|spec| self.word_styler(spec))
+                    .collect::<Result<_, _>>()?,
+            )
+            .phrase_builder(self.phrase_builder(&spec.phrase_builder)?)
+            .phrase_stylers(
+                spec.phrase_stylers
+                    .iter()
+                    .map(|spec| self.phrase_styler(spec))
+                    .collect::<Result<_, _>>()?,
+            )
+            .phrase_validators(
+                spec.phrase_validators
+                    .iter()
+                    .map(|spec| self.phrase_validator(spec))
+                    .collect::<Result<_, _>>()?,
+            )
+            .build()
+            .map_err(|err| RegistryError::Build(err.to_string()))
+    }
+}
+
+impl Debug for Registry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("word_set_providers", &keys(&self.word_set_providers))
+            .field("word_filters", &keys(&self.word_filters))
+            .field("word_stylers", &keys(&self.word_stylers))
+            .field("phrase_builders", &keys(&self.phrase_builders))
+            .field("phrase_stylers", &keys(&self.phrase_stylers))
+            .field("phrase_validators", &keys(&self.phrase_validators))
+            .finish()
+    }
+}
+
+/// Get the names in the given factory map.
+fn keys<T>(factories: &HashMap<String, T>) -> Vec<&str> {
+    factories.keys().map(String::as_str).collect()
+}
+
+/// Parse the character class `value` of parameter `name`.
+fn char_class(name: &str, value: &str) -> Result<CharClass, RegistryError> {
+    match value {
+        "letter" => Ok(CharClass::Letter),
+        "lowercase" => Ok(CharClass::Lowercase),
+        "uppercase" => Ok(CharClass::Uppercase),
+        "alphanumeric" => Ok(CharClass::Alphanumeric),
+        "digit" => Ok(CharClass::Digit),
+        "symbol" => Ok(CharClass::Symbol),
+        value => Err(RegistryError::InvalidParam {
+            name: name.into(),
+            value: value.into(),
+        }),
+    }
+}
+
+/// Iterate over the non-empty items of a comma separated list.
+fn list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// Construct the component for the given specification using the given factories.
+fn construct<T>(
+    factories: &HashMap<String, Factory<T>>,
+    kind: ComponentKind,
+    spec: &ComponentSpec,
+) -> Result<T, RegistryError> {
+    let factory = factories
+        .get(&spec.name)
+        .ok_or_else(|| RegistryError::UnknownComponent {
+            kind,
+            name: spec.name.clone(),
+        })?;
+    factory(&spec.params)
+}
+
+/// A [`Registry`](Registry) error.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RegistryError {
+    /// No component is registered under the given name.
+    #[error("no {kind} registered as '{name}'")]
+    UnknownComponent {
+        /// The kind of component.
+        kind: ComponentKind,
+
+        /// The unknown name.
+        name: String,
+    },
+
+    /// The scheme can't be built from the constructed components.
+    ///
+    /// This happens when a word filter can't be applied to the word set provider.
+    #[error("failed to build scheme: {0}")]
+    Build(String),
+
+    /// A required parameter is not set.
+    #[error("missing required parameter '{0}'")]
+    MissingParam(String),
+
+    /// A parameter has an invalid value.
+    #[error("invalid value '{value}' for parameter '{name}'")]
+    InvalidParam {
+        /// The name of the parameter.
+        name: String,
+
+        /// The invalid value.
+        value: String,
+    },
+}
@@ -0,0 +1,198 @@
+//! Alternative renderings of generated passphrases
+//!
+//! This module provides functions to render a passphrase in a different form for specific
+//! scenarios, without modifying the underlying passphrase.
+//!
+//! - [`spell_out`](spell_out): a read-aloud form for helpdesk and dictation scenarios.
+//! - [`t9`](t9): phone keypad digits for legacy systems and voicemail PINs.
+
+/// Render a read-aloud form of the given passphrase.
+///
+/// This produces a comma separated list of spoken tokens describing the passphrase exactly, so it
+/// can be dictated or read over the phone. Capitalization, digits and symbols are spelled out:
+///
+/// - Lowercase words are kept as is: `horse`
+/// - Words starting with a capital are prefixed: `capital H, horse`
+/// - Fully capitalized words are prefixed: `all caps, horse`
+/// - Words with other capitalization are spelled letter by letter: `h, capital O, r, s, e`
+/// - Digits are spelled individually: `seven`
+/// - Symbols and whitespace are named: `dash`, `space`
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::render::spell_out;
+///
+/// assert_eq!(
+///     spell_out("Horse-7 staple"),
+///     "capital H, horse, dash, seven, space, staple",
+/// );
+/// ```
+pub fn spell_out(phrase: &str) -> String {
+    spell_out_tokens(phrase).join(", ")
+}
+
+/// Render a read-aloud form of the given passphrase as list of spoken tokens.
+///
+/// See [`spell_out`](spell_out) for details.
+pub fn spell_out_tokens(phrase: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = phrase.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        // Collect and spell full words
+        if c.is_alphabetic() {
+            let mut word = c.to_string();
+            while let Some(c) = chars.next_if(|c| c.is_alphabetic()) {
+                word.push(c);
+            }
+            spell_word(&word, &mut tokens);
+            continue;
+        }
+
+        tokens.push(match c.to_digit(10) {
+            Some(digit) => DIGITS[digit as usize].into(),
+            None => symbol_name(c),
+        });
+    }
+
+    tokens
+}
+
+/// Spell the given alphabetic word, and push the tokens to `tokens`.
+fn spell_word(word: &str, tokens: &mut Vec<String>) {
+    let lower = word.to_lowercase();
+    let mut chars = word.chars();
+    let first = chars.next().unwrap();
+
+    if !word.chars().any(char::is_uppercase) {
+        tokens.push(lower);
+    } else if first.is_uppercase() && !chars.clone().any(char::is_uppercase) {
+        tokens.push(format!("capital {}", first));
+        tokens.push(lower);
+    } else if word.chars().count() > 1 && !word.chars().any(char::is_lowercase) {
+        tokens.push("all caps".into());
+        tokens.push(lower);
+    } else {
+        tokens.extend(word.chars().map(|c| {
+            if c.is_uppercase() {
+                format!("capital {}", c)
+            } else {
+                c.to_string()
+            }
+        }));
+    }
+}
+
+/// Render the given passphrase as phone keypad digits.
+///
+/// Each letter is mapped to the digit of the keypad key it is printed on, as found on phones
+/// using the ITU E.161 layout:
+///
+/// | Key | Letters |
+/// |-----|---------|
+/// | 2   | abc     |
+/// | 3   | def     |
+/// | 4   | ghi     |
+/// | 5   | jkl     |
+/// | 6   | mno     |
+/// | 7   | pqrs    |
+/// | 8   | tuv     |
+/// | 9   | wxyz    |
+///
+/// Letters are mapped regardless of capitalization, and digits are kept as is. Any other character,
+/// such as word separators, symbols and non-ASCII letters, is replaced by `separator`. Use an
+/// empty separator to drop them.
+///
+/// # Ambiguity
+///
+/// This rendering is lossy, as three or four letters share each key, and capitalization and
+/// symbols are lost. Many different passphrases map to the same digits, for example `good` and
+/// `home` both map to `4663`. The entropy of the digits is therefore lower than the entropy of the
+/// passphrase, and is at most `log2(10)` bits per digit. Only use it where the keypad form is
+/// required, and don't use it as a replacement for the passphrase.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::render::t9;
+///
+/// assert_eq!(t9("Horse-7 staple", ""), "467737782753");
+/// assert_eq!(t9("correct horse", "0"), "2677328046773");
+/// ```
+pub fn t9(phrase: &str, separator: &str) -> String {
+    let mut digits = String::with_capacity(phrase.len());
+
+    for c in phrase.chars() {
+        match keypad_digit(c) {
+            Some(digit) => digits.push(digit),
+            None => digits.push_str(separator),
+        }
+    }
+
+    digits
+}
+
+/// Get the phone keypad digit for the given character, if it is on the keypad.
+fn keypad_digit(c: char) -> Option<char> {
+    let digit = match c.to_ascii_lowercase() {
+        c @ '0'..='9' => c,
+        'a'..='c' => '2',
+        'd'..='f' => '3',
+        'g'..='i' => '4',
+        'j'..='l' => '5',
+        'm'..='o' => '6',
+        'p'..='s' => '7',
+        't'..='v' => '8',
+        'w'..='z' => '9',
+        _ => return None,
+    };
+    Some(digit)
+}
+
+/// Spoken names of digits.
+const DIGITS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Get the spoken name of the given symbol or whitespace character.
+fn symbol_name(c: char) -> String {
+    let name = match c {
+        ' ' => "space",
+        '\t' => "tab",
+        '-' => "dash",
+        '_' => "underscore",
+        '.' => "dot",
+        ',' => "comma",
+        ':' => "colon",
+        ';' => "semicolon",
+        '!' => "exclamation mark",
+        '?' => "question mark",
+        '@' => "at sign",
+        '#' => "hash",
+        '$' => "dollar sign",
+        '%' => "percent sign",
+        '^' => "caret",
+        '&' => "ampersand",
+        '*' => "asterisk",
+        '+' => "plus",
+        '=' => "equals sign",
+        '~' => "tilde",
+        '`' => "backtick",
+        '|' => "vertical bar",
+        '/' => "slash",
+        '\\' => "backslash",
+        '\'' => "apostrophe",
+        '"' => "double quote",
+        '(' => "open parenthesis",
+        ')' => "close parenthesis",
+        '[' => "open bracket",
+        ']' => "close bracket",
+        '{' => "open brace",
+        '}' => "close brace",
+        '<' => "less than sign",
+        '>' => "greater than sign",
+        c => return format!("symbol U+{:04X}", c as u32),
+    };
+    name.into()
+}
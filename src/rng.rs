@@ -0,0 +1,262 @@
+//! Randomness sources used for passphrase generation
+//!
+//! Components obtain randomness through `rand::thread_rng()` by default, or directly from the
+//! operating system through `rand::rngs::OsRng` with the `wasm` feature. A custom randomness
+//! source may be injected instead through the `_with_rng` methods on components, such as
+//! [`Scheme::generate_with_rng`](crate::scheme::Scheme::generate_with_rng). Such a source must be
+//! cryptographically secure, which is enforced through the [`SecureRng`](SecureRng) trait.
+
+use rand::{CryptoRng, Rng, RngCore};
+
+/// Get the default randomness source, used when none is given.
+///
+/// This is `rand::thread_rng()`, or `rand::rngs::OsRng` with the `wasm` feature.
+#[cfg(not(feature = "wasm"))]
+pub(crate) fn default_rng() -> rand::rngs::ThreadRng {
+    rand::thread_rng()
+}
+
+/// Get the default randomness source, used when none is given.
+///
+/// This is `rand::thread_rng()`, or `rand::rngs::OsRng` with the `wasm` feature.
+#[cfg(feature = "wasm")]
+pub(crate) fn default_rng() -> rand::rngs::OsRng {
+    rand::rngs::OsRng
+}
+
+/// A cryptographically secure randomness source.
+///
+/// This combines `RngCore` and `CryptoRng` into a single trait, so it may be used as trait
+/// object. It is implemented for all cryptographically secure random number generators, such as
+/// `rand::thread_rng()`, `rand::rngs::OsRng` and `rand_chacha::ChaCha20Rng`.
+///
+/// The `rand::Rng` extension methods are available on `&mut dyn SecureRng`.
+pub trait SecureRng: RngCore + CryptoRng {}
+
+impl<R> SecureRng for R where R: RngCore + CryptoRng + ?Sized {}
+
+/// Draw a uniformly random index below `bound`.
+///
+/// Sampling a `usize` range draws 32 or 64 bits depending on the target, so a seeded randomness
+/// source would yield different indices on 32-bit and 64-bit platforms. This always samples a
+/// `u64` range instead, so seeded generation produces the same passphrase on every platform.
+///
+/// # Panics
+///
+/// Panics if `bound` is zero.
+pub(crate) fn gen_index<R: Rng + ?Sized>(rng: &mut R, bound: usize) -> usize {
+    rng.gen_range(0..bound as u64) as usize
+}
+
+/// The largest product of index bounds drawn from a single sample.
+///
+/// A sample is 128 bits, so a sample is rejected with a probability below `2^-64`.
+const MAX_BATCH: u128 = 1 << 64;
+
+/// Draw a uniformly random index below each of the given `bounds`, in batches.
+///
+/// Instead of drawing randomness for each index separately, this draws a single 128-bit sample
+/// for as many indices as fit, and splits it into indices using mixed radix decoding. The
+/// sample is uniform over the product of the bounds, so each index is exactly uniform and
+/// independent of the others. This minimizes the number of draws from `rng`, which is useful
+/// where obtaining randomness is expensive, such as on early boot or embedded targets where each
+/// draw is a system call.
+///
+/// # Panics
+///
+/// Panics if any bound is zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::rng::uniform_indices;
+///
+/// let indices = uniform_indices(&mut rand::rngs::OsRng, &[7776, 7776, 10]);
+/// assert!(indices[0] < 7776 && indices[2] < 10);
+/// ```
+pub fn uniform_indices(rng: &mut dyn SecureRng, bounds: &[usize]) -> Vec<usize> {
+    if bounds.contains(&0) {
+        panic!("cannot draw random index below zero");
+    }
+
+    let mut indices = Vec::with_capacity(bounds.len());
+    let mut rest = bounds;
+    while !rest.is_empty() {
+        // Batch as many bounds as fit, at least one
+        let mut product: u128 = 1;
+        let mut batch = 0;
+        for bound in rest {
+            let next = product * *bound as u128;
+            if batch > 0 && next > MAX_BATCH {
+                break;
+            }
+            product = next;
+            batch += 1;
+        }
+
+        // Reject the lowest samples, so the remaining range is a multiple of the product
+        let reject = (u128::MAX % product + 1) % product;
+        let mut sample = loop {
+            let mut bytes = [0; 16];
+            rng.fill_bytes(&mut bytes);
+            let sample = u128::from_le_bytes(bytes);
+            if sample >= reject {
+                break sample - reject;
+            }
+        };
+
+        for bound in &rest[..batch] {
+            indices.push((sample % *bound as u128) as usize);
+            sample /= *bound as u128;
+        }
+        rest = &rest[batch..];
+    }
+    indices
+}
+
+/// A randomness source recording all randomness obtained from a wrapped source.
+pub(crate) struct RecordingRng<'a> {
+    /// The wrapped randomness source.
+    inner: &'a mut dyn SecureRng,
+
+    /// All randomness obtained so far, in order.
+    recorded: Vec<u8>,
+}
+
+impl<'a> RecordingRng<'a> {
+    /// Wrap the given randomness source.
+    pub(crate) fn new(inner: &'a mut dyn SecureRng) -> Self {
+        Self {
+            inner,
+            recorded: Vec::new(),
+        }
+    }
+
+    /// Consume this source, returning all recorded randomness.
+    pub(crate) fn into_recorded(self) -> Vec<u8> {
+        self.recorded
+    }
+}
+
+impl RngCore for RecordingRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        let value = self.inner.next_u32();
+        self.recorded.extend_from_slice(&value.to_le_bytes());
+        value
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.inner.next_u64();
+        self.recorded.extend_from_slice(&value.to_le_bytes());
+        value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest);
+        self.recorded.extend_from_slice(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.inner.try_fill_bytes(dest)?;
+        self.recorded.extend_from_slice(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for RecordingRng<'_> {}
+
+/// A randomness source replaying previously recorded randomness.
+///
+/// This is used to verify an [`Audit`](crate::scheme::Audit) record, by regenerating a passphrase
+/// from the recorded randomness with
+/// [`Scheme::generate_with_rng`](crate::scheme::Scheme::generate_with_rng).
+///
+/// The replayed randomness is only as secure as the recorded source, and is known to whoever
+/// holds the record. This must never be used to generate passphrases that are issued.
+///
+/// # Panics
+///
+/// Obtaining more randomness than was recorded panics.
+#[derive(Clone, Debug)]
+pub struct ReplayRng {
+    /// The recorded randomness.
+    recorded: Vec<u8>,
+
+    /// The position of the next byte to replay.
+    position: usize,
+
+    /// Whether to replay zeroes instead of panicking when the recorded randomness is exhausted.
+    lenient: bool,
+
+    /// Whether more randomness was obtained than was recorded.
+    overrun: bool,
+}
+
+impl ReplayRng {
+    /// Construct a source replaying the given recorded randomness.
+    pub fn new(recorded: Vec<u8>) -> Self {
+        Self {
+            recorded,
+            position: 0,
+            lenient: false,
+            overrun: false,
+        }
+    }
+
+    /// Construct a source replaying the given untrusted recorded randomness.
+    ///
+    /// Instead of panicking, zeroes are replayed when the recorded randomness is exhausted, which
+    /// is reported by [`is_exact`](ReplayRng::is_exact).
+    pub(crate) fn lenient(recorded: Vec<u8>) -> Self {
+        Self {
+            lenient: true,
+            ..Self::new(recorded)
+        }
+    }
+
+    /// Check whether exactly all recorded randomness was replayed.
+    pub(crate) fn is_exact(&self) -> bool {
+        !self.overrun && self.position == self.recorded.len()
+    }
+
+    /// Take the next `len` recorded bytes.
+    fn take(&mut self, len: usize) -> Option<&[u8]> {
+        let bytes = self.recorded.get(self.position..self.position + len)?;
+        self.position += len;
+        Some(bytes)
+    }
+}
+
+impl RngCore for ReplayRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.try_fill_bytes(dest).is_err() {
+            if !self.lenient {
+                panic!("replayed more randomness than was recorded");
+            }
+            self.overrun = true;
+            dest.iter_mut().for_each(|b| *b = 0);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        let bytes = self
+            .take(dest.len())
+            .ok_or_else(|| rand::Error::new("recorded randomness exhausted"))?;
+        dest.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl CryptoRng for ReplayRng {}
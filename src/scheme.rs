@@ -6,8 +6,49 @@
 //! As both provided and custom structures may produce a [`Scheme`](Scheme) for passphrase
 //! generation, the [`ToScheme`](ToScheme) trait is used for a generic way of doing this.
 
-use crate::entropy::Entropy;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::iter;
+use std::mem;
+use std::ops::RangeInclusive;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use thiserror::Error;
+
+use crate::component::{phrase::BasicPhraseBuilder, word::FixedWordSetProvider, ComponentKind};
+use crate::entropy::{
+    Acceptance, AttackerReport, ComponentEntropy, DictionaryAttack, Entropy, EntropyReport,
+};
+#[cfg(feature = "hibp")]
+use crate::hibp::{BreachChecker, HibpError};
+use crate::limits::{GenerationLimits, LimitExceeded};
 use crate::prelude::*;
+use crate::rng::{default_rng, RecordingRng, ReplayRng};
+use crate::score::Scorer;
+use crate::validate::{check_separator, Warning, NEGLIGIBLE_BITS};
+use crate::word::WordList;
+
+/// The maximum number of passphrases generated by
+/// [`Scheme::generate_unpwned`](Scheme::generate_unpwned).
+#[cfg(feature = "hibp")]
+const UNPWNED_MAX_ATTEMPTS: usize = 16;
+
+/// A single audited generation attempt: the phrase, its words and their index in the known
+/// wordlist.
+type AuditedAttempt = (String, Vec<String>, Vec<Option<usize>>);
+
+/// The number of passphrases generated in a dry run by [`Scheme::estimate`](Scheme::estimate).
+const ESTIMATE_SAMPLES: usize = 64;
+
+/// The maximum expected number of attempts for a [`CostClass::Moderate`] cost.
+const MODERATE_MAX_ATTEMPTS: f64 = 4.0;
+
+/// Words used to sample the effect of styling components if the wordlist is not known.
+const SAMPLE_WORDS: [&str; 4] = ["correct", "horse", "battery", "staple"];
 
 /// A passphrase generation scheme.
 ///
@@ -30,6 +71,9 @@ use crate::prelude::*;
 /// - The word generator is used once for each passphrase to generate, and provides a set of words
 ///   to use for that specific phrase. The generator internally samples a known wordlist or
 ///   generates randomized strings depending on how it is configured.
+/// - A set of word filters vetoes or replaces words of the wordlist, such as profanity. Filters
+///   are applied to the wordlist of the word generator once when the scheme is built, see
+///   [`WordFilter`](WordFilter).
 /// - A set of word stylers is used to modify each passphrase word from the generated set, to
 ///   randomize capitalization, to add special characters and more depending on their
 ///   configuration. Each styler is applied once to each phrase word in the specified order.
@@ -40,25 +84,34 @@ use crate::prelude::*;
 /// - A set of phrase stylers is used to modify the full passphrase that is now combined. They
 ///   may be used for further modifications with full control over the phrase. If no phrase
 ///   styler is available, the phrase is kept intact.
+/// - A set of phrase validators checks the final passphrase, such as for a maximum length. If any
+///   validator rejects it, a new passphrase is generated, up to a maximum number of attempts.
 ///
 /// # Examples
 ///
 /// The scheme implements `Iterator`. You may easily generate many passphrases this way:
 ///
 /// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
 /// use chbs::{config::BasicConfig, prelude::*, scheme::Scheme};
 ///
 /// let scheme = BasicConfig::default().to_scheme();
 ///
 /// scheme.take(8)
 ///     .for_each(|passphrase| println!("{}", passphrase));
+/// # }
 /// ```
 #[derive(Builder, Debug)]
-#[builder(pattern = "owned")]
+#[builder(pattern = "owned", build_fn(private, name = "build_uncached"))]
 pub struct Scheme {
     /// A word set provider, which sources a set of random words to use in the passphrase.
     word_set_provider: Box<dyn WordSetProvider>,
 
+    /// A set of word filters, applied to the word set provider when building.
+    #[builder(default)]
+    word_filters: Vec<Box<dyn WordFilter>>,
+
     /// A set of word stylers to apply to each passphrase word.
     word_stylers: Vec<Box<dyn WordStyler>>,
 
@@ -67,6 +120,31 @@ pub struct Scheme {
 
     /// A set of phrase stylers to apply to each passphrase.
     phrase_stylers: Vec<Box<dyn PhraseStyler>>,
+
+    /// A set of phrase validators each passphrase must be accepted by.
+    #[builder(default)]
+    phrase_validators: Vec<Box<dyn PhraseValidator>>,
+
+    /// The limits on attempts to generate a passphrase accepted by all validators.
+    #[builder(default)]
+    limits: GenerationLimits,
+
+    /// The number of passphrases to sample on construction for measuring the acceptance rate of
+    /// the validators, which is folded into the entropy. Not measured if zero.
+    #[builder(default)]
+    acceptance_samples: usize,
+
+    /// The measured acceptance rate of the validators.
+    #[builder(setter(skip))]
+    acceptance: Option<Acceptance>,
+
+    /// The entropy each component contributes, calculated once on construction.
+    #[builder(setter(skip))]
+    component_entropies: Vec<ComponentEntropy>,
+
+    /// The total entropy of passphrases, calculated once on construction.
+    #[builder(setter(skip), default = "Entropy::zero()")]
+    entropy: Entropy,
 }
 
 impl Scheme {
@@ -83,12 +161,52 @@ impl Scheme {
         phrase_builder: Box<dyn PhraseBuilder>,
         phrase_stylers: Vec<Box<dyn PhraseStyler>>,
     ) -> Self {
-        Self {
+        let mut scheme = Self {
             word_set_provider,
+            word_filters: Vec::new(),
             word_stylers,
             phrase_builder,
             phrase_stylers,
-        }
+            phrase_validators: Vec::new(),
+            limits: GenerationLimits::default(),
+            acceptance_samples: 0,
+            acceptance: None,
+            component_entropies: Vec::new(),
+            entropy: Entropy::zero(),
+        };
+        scheme.component_entropies = scheme.calculate_component_entropies();
+        scheme.entropy = scheme.component_entropies.iter().map(|c| c.entropy).sum();
+        scheme
+    }
+
+    /// Construct a simple scheme with the common shape.
+    ///
+    /// Passphrases consist of a fixed number of `words` uniformly sampled from `wordlist`, joined
+    /// by `separator`, without any styling. Use a configuration struct such as
+    /// [`BasicConfig`](::config::BasicConfig) for more options.
+    ///
+    /// # Panics
+    ///
+    /// `words` must be higher than zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{scheme::Scheme, word::WordList};
+    ///
+    /// let scheme = Scheme::simple(WordList::default(), 5, " ");
+    /// assert_eq!(5, scheme.generate().split(' ').count());
+    /// # }
+    /// ```
+    pub fn simple(wordlist: WordList, words: usize, separator: &str) -> Self {
+        Self::new(
+            Box::new(FixedWordSetProvider::new(wordlist.sampler(), words)),
+            Vec::new(),
+            Box::new(BasicPhraseBuilder::new(separator.into())),
+            Vec::new(),
+        )
     }
 
     /// Build a configuration based on the given object.
@@ -102,9 +220,59 @@ impl Scheme {
     }
 
     /// Generate a single passphrase based on this scheme.
+    ///
+    /// If the word set provider supports providing words by reference, words are only allocated
+    /// when a word styler changes them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no passphrase accepted by the phrase validators is generated within the maximum
+    /// number of attempts. Use [`try_generate`](Scheme::try_generate) to handle this instead.
     pub fn generate(&self) -> String {
+        self.try_generate().expect("failed to generate passphrase")
+    }
+
+    /// Generate a single passphrase based on this scheme.
+    ///
+    /// This is like [`generate`](Scheme::generate), but returns an error if no passphrase
+    /// accepted by the phrase validators is generated within the [limits](GenerationLimits).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::component::{phrase::{BasicPhraseBuilder, MaxLengthValidator}, word::FixedWordSetProvider};
+    /// use chbs::{limits::{GenerationLimits, LimitExceeded}, scheme::Scheme, word::WordList};
+    ///
+    /// let scheme = Scheme::build()
+    ///     .word_set_provider(Box::new(FixedWordSetProvider::new(WordList::default().sampler(), 5)))
+    ///     .word_stylers(vec![])
+    ///     .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+    ///     .phrase_stylers(vec![])
+    ///     .phrase_validators(vec![Box::new(MaxLengthValidator::new(4))])
+    ///     .limits(GenerationLimits::new(8))
+    ///     .build()
+    ///     .unwrap();
+    /// let err = scheme.try_generate().unwrap_err();
+    /// assert_eq!(LimitExceeded::Attempts(8), err.limit);
+    /// # }
+    /// ```
+    pub fn try_generate(&self) -> Result<String, RejectedError> {
+        self.retry(|| self.generate_once(), |phrase| phrase)
+    }
+
+    /// Generate a single passphrase based on this scheme, without validating it.
+    ///
+    /// An error is returned if the word set provider exceeds its limits.
+    fn generate_once(&self) -> Result<String, LimitExceeded> {
+        // Use the non-allocating pipeline if words are provided by reference
+        if let Some(words) = self.word_set_provider.try_word_refs()? {
+            return Ok(self.generate_borrowed(words));
+        }
+
         // Generate the passphrase words
-        let mut words = self.word_set_provider.words();
+        let mut words = self.word_set_provider.try_words()?;
 
         // Run the passphrase words through the word stylers
         for p in &self.word_stylers {
@@ -119,26 +287,1103 @@ impl Scheme {
             phrase = p.style_phrase(phrase);
         }
 
+        Ok(phrase)
+    }
+
+    /// Generate a single passphrase, that is wiped from memory when dropped.
+    ///
+    /// This is like [`generate`](Scheme::generate), but returns a
+    /// [`Passphrase`](crate::secret::Passphrase) instead of a plain `String`.
+    ///
+    /// This is only available with the `zeroize` feature.
+    #[cfg(feature = "zeroize")]
+    pub fn generate_secret(&self) -> crate::secret::Passphrase {
+        self.generate().into()
+    }
+
+    /// Split a passphrase generated by this scheme back into its words.
+    ///
+    /// This reverses the phrase builder using its separators, see
+    /// [`PhraseBuilder::split_phrase`](PhraseBuilder::split_phrase), which is useful to show a
+    /// stored passphrase word by word for confirmation. Passphrases built with a random separator
+    /// are supported, but word stylers and phrase stylers are not reversed. `None` is returned if
+    /// the passphrase can't be split into the number of words this scheme generates.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let words = scheme.split_phrase("Correct horse battery staple lemon").unwrap();
+    /// assert_eq!(words, vec!["Correct", "horse", "battery", "staple", "lemon"]);
+    /// assert!(scheme.split_phrase("correct horse").is_none());
+    /// # }
+    /// ```
+    pub fn split_phrase(&self, phrase: &str) -> Option<Vec<String>> {
+        self.phrase_builder
+            .split_phrase(phrase, self.word_set_provider.word_count())
+    }
+
+    /// Generate a single passphrase that doesn't appear in a breach corpus.
+    ///
+    /// Passphrases are generated until the given `checker` reports one that is not breached, see
+    /// the [`hibp`](crate::hibp) module. An error is returned if checking fails, or if no
+    /// unbreached passphrase is generated within 16 attempts, which hints at a scheme with very
+    /// low entropy.
+    ///
+    /// Only available with the `hibp` feature.
+    #[cfg(feature = "hibp")]
+    pub fn generate_unpwned<C>(&self, checker: &C) -> Result<String, HibpError>
+    where
+        C: BreachChecker + ?Sized,
+    {
+        for _ in 0..UNPWNED_MAX_ATTEMPTS {
+            let phrase = self.generate();
+            if !checker.is_pwned(&phrase)? {
+                return Ok(phrase);
+            }
+        }
+        Err(HibpError::Exhausted(UNPWNED_MAX_ATTEMPTS))
+    }
+
+    /// Generate a single passphrase based on this scheme, with mutable access to its components.
+    ///
+    /// This uses the `_mut` variants of all component methods, such as
+    /// [`WordSetProvider::words_mut`](WordSetProvider::words_mut), allowing stateful components
+    /// to update their state without interior mutability or global state. Components that don't
+    /// implement these behave the same as with [`generate`](Scheme::generate). This is also used
+    /// when the scheme is used as `Iterator`.
+    ///
+    /// Stateful components must still implement the shared `&self` methods, which are used by
+    /// [`generate`](Scheme::generate). To share a scheme with stateful components between
+    /// threads, wrap it in a `Mutex` and generate through this method while holding the lock.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::component::{phrase::BasicPhraseBuilder, word::FixedWordSetProvider};
+    /// use chbs::{entropy::Entropy, prelude::*, scheme::Scheme, word::WordList};
+    ///
+    /// /// A phrase styler numbering the passphrases it styled.
+    /// #[derive(Debug, Default)]
+    /// struct Counter {
+    ///     count: usize,
+    /// }
+    ///
+    /// impl HasEntropy for Counter {
+    ///     fn entropy(&self) -> Entropy {
+    ///         Entropy::zero()
+    ///     }
+    /// }
+    ///
+    /// impl PhraseStyler for Counter {
+    ///     fn style_phrase(&self, phrase: String) -> String {
+    ///         phrase
+    ///     }
+    ///
+    ///     fn style_phrase_mut(&mut self, phrase: String) -> String {
+    ///         self.count += 1;
+    ///         format!("{} #{}", phrase, self.count)
+    ///     }
+    /// }
+    ///
+    /// let mut scheme = Scheme::build()
+    ///     .word_set_provider(Box::new(FixedWordSetProvider::new(WordList::default().sampler(), 5)))
+    ///     .word_stylers(vec![])
+    ///     .phrase_builder(Box::new(BasicPhraseBuilder::new(" ".into())))
+    ///     .phrase_stylers(vec![Box::new(Counter::default())])
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(scheme.generate_mut().ends_with(" #1"));
+    /// assert!(scheme.generate_mut().ends_with(" #2"));
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if no passphrase accepted by the phrase validators is generated within the maximum
+    /// number of attempts, see [`generate`](Scheme::generate).
+    pub fn generate_mut(&mut self) -> String {
+        // Borrow components separately, so attempts may mutate them while validators are used
+        let Scheme {
+            word_set_provider,
+            word_stylers,
+            phrase_builder,
+            phrase_stylers,
+            phrase_validators,
+            limits,
+            ..
+        } = self;
+
+        let attempt = || {
+            let mut words = word_set_provider.words_mut();
+
+            for p in word_stylers.iter_mut() {
+                words = words.into_iter().map(|w| p.style_word_mut(w)).collect();
+            }
+
+            let mut phrase = phrase_builder.build_phrase_mut(words);
+
+            for p in phrase_stylers.iter_mut() {
+                phrase = p.style_phrase_mut(phrase);
+            }
+
+            Ok(phrase)
+        };
+        retry_validated(phrase_validators, *limits, attempt, |phrase| phrase)
+            .expect("failed to generate passphrase")
+    }
+
+    /// Generate a single passphrase based on this scheme, using the given randomness source.
+    ///
+    /// This obtains all randomness from `rng` through the `_with_rng` methods of the components,
+    /// such as [`WordSetProvider::words_with_rng`](WordSetProvider::words_with_rng), instead of
+    /// the implicit thread-local source used by [`generate`](Scheme::generate). All components
+    /// provided by this crate support this. Custom components that don't implement these methods
+    /// fall back to their own randomness source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no passphrase accepted by the phrase validators is generated within the maximum
+    /// number of attempts, see [`generate`](Scheme::generate).
+    pub fn generate_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        self.retry(|| Ok(self.generate_with_rng_once(rng)), |phrase| phrase)
+            .expect("failed to generate passphrase")
+    }
+
+    /// Generate a single passphrase using the given randomness source, without validating it.
+    fn generate_with_rng_once(&self, rng: &mut dyn SecureRng) -> String {
+        let mut words = self.word_set_provider.words_with_rng(rng);
+
+        for p in &self.word_stylers {
+            words = words
+                .into_iter()
+                .map(|w| p.style_word_with_rng(w, rng))
+                .collect();
+        }
+
+        let mut phrase = self.phrase_builder.build_phrase_with_rng(words, rng);
+
+        for p in &self.phrase_stylers {
+            phrase = p.style_phrase_with_rng(phrase, rng);
+        }
+
         phrase
     }
 
+    /// Generate a single passphrase based on this scheme, deterministically for the given seed.
+    ///
+    /// The same scheme and seed always produce the same passphrase, on every platform. This is
+    /// useful for reproducible unit tests and documentation examples. All randomness is obtained
+    /// from a ChaCha20 generator seeded with `seed`, see
+    /// [`generate_with_rng`](Scheme::generate_with_rng). Built-in components never sample `usize`
+    /// ranges, which draw a different number of bits on 32-bit and 64-bit targets. Custom
+    /// components must do the same for their output to be identical on every platform.
+    ///
+    /// **Warning:** passphrases generated this way are only as secret as the seed, which has at
+    /// most 64 bits of entropy and is usually hardcoded. Never use this for passphrases that are
+    /// issued, use [`generate`](Scheme::generate) instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// assert_eq!(scheme.generate_seeded(42), scheme.generate_seeded(42));
+    /// # }
+    /// ```
+    pub fn generate_seeded(&self, seed: u64) -> String {
+        self.generate_with_rng(&mut ChaCha20Rng::seed_from_u64(seed))
+    }
+
+    /// Generate `n` passphrases based on this scheme, and write them to the given writer.
+    ///
+    /// Each passphrase is followed by the given `terminator`, such as `"\n"`. Passphrases are
+    /// written one at a time without collecting them first, so memory usage stays flat for large
+    /// batches. Wrap the writer in a [`BufWriter`](std::io::BufWriter) if it is unbuffered, such
+    /// as a file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    ///
+    /// let mut output = Vec::new();
+    /// scheme.write_many(&mut output, 3, "\n").unwrap();
+    /// assert_eq!(3, String::from_utf8(output).unwrap().lines().count());
+    /// # }
+    /// ```
+    pub fn write_many<W: io::Write>(&self, mut w: W, n: usize, terminator: &str) -> io::Result<()> {
+        for _ in 0..n {
+            w.write_all(self.generate().as_bytes())?;
+            w.write_all(terminator.as_bytes())?;
+        }
+        w.flush()
+    }
+
+    /// Generate a batch of `n` passphrases based on this scheme.
+    ///
+    /// To cap how often a word appears across the batch, use a
+    /// [`FrequencyGuard`](crate::guard::FrequencyGuard) instead.
+    pub fn generate_many(&self, n: usize) -> Vec<String> {
+        (0..n).map(|_| self.generate()).collect()
+    }
+
+    /// Generate a single passphrase based on this scheme, from the given borrowed words.
+    fn generate_borrowed(&self, words: Vec<&str>) -> String {
+        let mut words: Vec<Cow<str>> = words.into_iter().map(Cow::Borrowed).collect();
+        self.style_words(&mut words);
+        self.build_styled_phrase(&words)
+    }
+
+    /// Generate a single passphrase based on this scheme, retaining its words.
+    ///
+    /// This is like [`generate`](Scheme::generate), but returns a
+    /// [`GeneratedPassphrase`](GeneratedPassphrase) which also holds the styled passphrase words
+    /// the phrase was built from.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let passphrase = scheme.generate_detailed();
+    /// assert_eq!(5, passphrase.word_count());
+    /// assert_eq!(scheme.entropy(), passphrase.entropy());
+    /// println!("Passphrase: {} ({})", passphrase.phrase(), passphrase.acronym());
+    /// println!("Strength: {}", passphrase.entropy());
+    /// # }
+    /// ```
+    pub fn generate_detailed(&self) -> GeneratedPassphrase {
+        self.try_generate_detailed()
+            .expect("failed to generate passphrase")
+    }
+
+    /// Try to generate a single passphrase based on this scheme, retaining its words.
+    ///
+    /// This is like [`generate_detailed`](Scheme::generate_detailed), but returns an error if no
+    /// passphrase accepted by the phrase validators is generated within the
+    /// [limits](GenerationLimits), see [`try_generate`](Scheme::try_generate).
+    pub fn try_generate_detailed(&self) -> Result<GeneratedPassphrase, RejectedError> {
+        self.retry(|| self.generate_detailed_once(), |p| &p.phrase)
+    }
+
+    /// Generate a single passphrase retaining its words, without validating it.
+    ///
+    /// An error is returned if the word set provider exceeds its limits.
+    fn generate_detailed_once(&self) -> Result<GeneratedPassphrase, LimitExceeded> {
+        let mut words: Vec<Cow<str>> = match self.word_set_provider.try_word_refs()? {
+            Some(words) => words.into_iter().map(Cow::Borrowed).collect(),
+            None => self
+                .word_set_provider
+                .try_words()?
+                .into_iter()
+                .map(Cow::Owned)
+                .collect(),
+        };
+        self.style_words(&mut words);
+        let phrase = self.build_styled_phrase(&words);
+
+        Ok(GeneratedPassphrase {
+            phrase,
+            words: words.into_iter().map(Cow::into_owned).collect(),
+            component_entropies: self.component_entropies.clone(),
+            entropy: self.entropy,
+            audit: None,
+        })
+    }
+
+    /// Generate `n` candidate passphrases, ranked by the given scorer.
+    ///
+    /// Candidates are sorted from the highest to the lowest score, so user interfaces can offer a
+    /// pick-list instead of a single passphrase. See the [`score`](crate::score) module for
+    /// scorers, or pass a closure.
+    ///
+    /// Picking a passphrase from a ranked list is not uniformly random. When the top candidate is
+    /// always picked, an attacker knowing the scorer gains up to `log2(n)` bits on the
+    /// [entropy](Scheme::entropy), so keep `n` small.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*, scheme::GeneratedPassphrase, score::TypingEffort};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let suggestions = scheme.suggest(4, &TypingEffort);
+    /// assert_eq!(4, suggestions.len());
+    ///
+    /// // Rank by a custom score, preferring short passphrases
+    /// let suggestions = scheme.suggest(4, &|p: &GeneratedPassphrase| -(p.phrase().len() as f64));
+    /// assert!(suggestions[0].phrase().len() <= suggestions[3].phrase().len());
+    /// # }
+    /// ```
+    pub fn suggest<S>(&self, n: usize, scorer: &S) -> Vec<GeneratedPassphrase>
+    where
+        S: Scorer + ?Sized,
+    {
+        let mut candidates: Vec<(f64, GeneratedPassphrase)> = (0..n)
+            .map(|_| {
+                let passphrase = self.generate_detailed();
+                (scorer.score(&passphrase), passphrase)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        candidates.into_iter().map(|(_, p)| p).collect()
+    }
+
+    /// Generate a single passphrase based on this scheme, retaining its words and an
+    /// [`Audit`](Audit) record.
+    ///
+    /// This is like [`generate_detailed`](Scheme::generate_detailed), but also records all
+    /// randomness used to build the passphrase, and the index of each sampled word in the known
+    /// wordlist. This allows external auditors to reproduce the mapping from randomness to
+    /// output, and to verify uniformity offline. See [`Audit`](Audit).
+    ///
+    /// Randomness is obtained like [`generate_with_rng`](Scheme::generate_with_rng) from a
+    /// thread-local source. Custom components that don't implement the `_with_rng` methods use
+    /// their own randomness source, which is not recorded.
+    ///
+    /// The audit record allows anyone holding it to reproduce the passphrase. Treat it as secret
+    /// as the passphrase itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let passphrase = scheme.generate_audited();
+    /// let audit = passphrase.audit().unwrap();
+    ///
+    /// // Reproduce the passphrase from the recorded randomness
+    /// assert_eq!(passphrase.phrase(), scheme.generate_with_rng(&mut audit.replay()));
+    /// assert_eq!(5, audit.word_indices().len());
+    /// # }
+    /// ```
+    pub fn generate_audited(&self) -> GeneratedPassphrase {
+        let mut default_rng = default_rng();
+        let mut rng = RecordingRng::new(&mut default_rng);
+        let known = self.known_word_indices();
+
+        // Rejected attempts are recorded as well, so replaying reproduces the same retries
+        let (phrase, words, word_indices) = self
+            .retry(
+                || Ok(self.generate_audited_once(&mut rng, known.as_ref())),
+                |(phrase, _, _)| phrase,
+            )
+            .expect("failed to generate passphrase");
+
+        GeneratedPassphrase {
+            phrase,
+            words,
+            component_entropies: self.component_entropies.clone(),
+            entropy: self.entropy,
+            audit: Some(Audit {
+                randomness: rng.into_recorded(),
+                word_indices,
+            }),
+        }
+    }
+
+    /// Reconstruct an audited passphrase from the given, untrusted, recorded randomness.
+    ///
+    /// Returns `None` if the randomness doesn't reconstruct a passphrase exactly.
+    pub(crate) fn replay_audited(&self, randomness: &[u8]) -> Option<GeneratedPassphrase> {
+        let mut replay = ReplayRng::lenient(randomness.to_vec());
+        let mut rng = RecordingRng::new(&mut replay);
+        let known = self.known_word_indices();
+        // Retries are reproduced regardless of how long they took originally
+        let (phrase, words, word_indices) = self
+            .retry_within(
+                self.limits.without_duration(),
+                || Ok(self.generate_audited_once(&mut rng, known.as_ref())),
+                |(phrase, _, _)| phrase,
+            )
+            .ok()?;
+        let randomness = rng.into_recorded();
+        if !replay.is_exact() {
+            return None;
+        }
+
+        Some(self.passphrase_from_parts(
+            phrase,
+            words,
+            Some(Audit {
+                randomness,
+                word_indices,
+            }),
+        ))
+    }
+
+    /// Construct a passphrase generated by this scheme from its parts.
+    pub(crate) fn passphrase_from_parts(
+        &self,
+        phrase: String,
+        words: Vec<String>,
+        audit: Option<Audit>,
+    ) -> GeneratedPassphrase {
+        GeneratedPassphrase {
+            phrase,
+            words,
+            component_entropies: self.component_entropies.clone(),
+            entropy: self.entropy,
+            audit,
+        }
+    }
+
+    /// Get the known words of the word set provider, see
+    /// [`WordSetProvider::known_words`](WordSetProvider::known_words).
+    pub(crate) fn known_words(&self) -> Option<Vec<&str>> {
+        self.word_set_provider.known_words()
+    }
+
+    /// Map each known word of the word set provider to its first index in the known wordlist.
+    ///
+    /// Returns `None` if the provider doesn't know its words.
+    fn known_word_indices(&self) -> Option<HashMap<&str, usize>> {
+        let known = self.word_set_provider.known_words()?;
+        let mut indices = HashMap::with_capacity(known.len());
+        for (i, word) in known.into_iter().enumerate() {
+            indices.entry(word).or_insert(i);
+        }
+        Some(indices)
+    }
+
+    /// Generate a single passphrase using the given recording randomness source, without
+    /// validating it. Returns the phrase, its words and their index in the `known` wordlist, see
+    /// [`known_word_indices`](Scheme::known_word_indices).
+    fn generate_audited_once(
+        &self,
+        rng: &mut RecordingRng,
+        known: Option<&HashMap<&str, usize>>,
+    ) -> AuditedAttempt {
+        let mut words = self.word_set_provider.words_with_rng(rng);
+
+        let word_indices = words
+            .iter()
+            .map(|word| known.and_then(|known| known.get(word.as_str()).copied()))
+            .collect();
+
+        for p in &self.word_stylers {
+            words = words
+                .into_iter()
+                .map(|w| p.style_word_with_rng(w, rng))
+                .collect();
+        }
+
+        let mut phrase = self
+            .phrase_builder
+            .build_phrase_with_rng(words.clone(), rng);
+
+        for p in &self.phrase_stylers {
+            phrase = p.style_phrase_with_rng(phrase, rng);
+        }
+
+        (phrase, words, word_indices)
+    }
+
+    /// Validate the given passphrase with all phrase validators.
+    ///
+    /// Returns the reason of the first validator rejecting it.
+    fn validate_phrase(&self, phrase: &str) -> Result<(), String> {
+        validate_phrase(&self.phrase_validators, phrase)
+    }
+
+    /// Run the given generation `attempt` until its result is accepted by all phrase validators.
+    ///
+    /// `phrase` gets the passphrase from the result of an attempt. At least one attempt is made.
+    fn retry<T, A, P>(&self, attempt: A, phrase: P) -> Result<T, RejectedError>
+    where
+        A: FnMut() -> Result<T, LimitExceeded>,
+        P: Fn(&T) -> &str,
+    {
+        self.retry_within(self.limits, attempt, phrase)
+    }
+
+    /// Run the given generation `attempt` until its result is accepted by all phrase validators,
+    /// within the given `limits`.
+    fn retry_within<T, A, P>(
+        &self,
+        limits: GenerationLimits,
+        attempt: A,
+        phrase: P,
+    ) -> Result<T, RejectedError>
+    where
+        A: FnMut() -> Result<T, LimitExceeded>,
+        P: Fn(&T) -> &str,
+    {
+        retry_validated(&self.phrase_validators, limits, attempt, phrase)
+    }
+
+    /// Reproduce the passphrase of the given audit record from its recorded randomness.
+    ///
+    /// This is a shorthand for passing [`Audit::replay`](Audit::replay) to
+    /// [`generate_with_rng`](Scheme::generate_with_rng). It must be called on the scheme the
+    /// record was generated with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if generation requires more randomness than was recorded, which happens if the
+    /// record was generated with a different scheme.
+    pub fn replay(&self, audit: &Audit) -> String {
+        self.generate_with_rng(&mut audit.replay())
+    }
+
+    /// Run the given passphrase words through the word stylers.
+    fn style_words(&self, words: &mut [Cow<str>]) {
+        for p in &self.word_stylers {
+            for word in words.iter_mut() {
+                *word = p.style_word_cow(mem::take(word));
+            }
+        }
+    }
+
+    /// Build a passphrase from the given styled words, and run it through the phrase stylers.
+    fn build_styled_phrase(&self, words: &[Cow<str>]) -> String {
+        let mut phrase = self.phrase_builder.build_phrase_ref(words);
+        for p in &self.phrase_stylers {
+            phrase = p.style_phrase(phrase);
+        }
+        phrase
+    }
+
+    /// Validate this scheme.
+    ///
+    /// This checks the scheme for configurations that likely don't behave as intended, such as a
+    /// phrase separator occurring inside wordlist entries, or styling that adds visual complexity
+    /// without adding entropy. A list of found problems is returned, which is empty if no problems
+    /// were found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// assert!(scheme.validate().is_empty());
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+
+        // Components must report valid entropy
+        warnings.extend(
+            self.component_entropies
+                .iter()
+                .filter(|c| !c.entropy.is_valid())
+                .map(|c| Warning::InvalidEntropy {
+                    kind: c.kind,
+                    index: c.index,
+                    entropy: c.entropy,
+                }),
+        );
+
+        // Separators must not occur inside words
+        if let Some(words) = self.word_set_provider.known_words() {
+            warnings.extend(
+                self.phrase_builder
+                    .separators()
+                    .into_iter()
+                    .filter_map(|sep| check_separator(sep, &words).err())
+                    .map(Warning::from),
+            );
+        }
+
+        // Word stylers must not credit more entropy than known words allow
+        if let Some(known) = self.word_set_provider.known_words() {
+            for (index, styler) in self.word_stylers.iter().enumerate() {
+                let entropy = styler.entropy();
+                let weakest = known
+                    .iter()
+                    .map(|word| (styler.word_entropy(word), *word))
+                    .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                if let Some((actual, word)) = weakest.filter(|(actual, _)| *actual < entropy) {
+                    warnings.push(Warning::OverstatedEntropy {
+                        kind: ComponentKind::WordStyler,
+                        index,
+                        entropy,
+                        word: word.into(),
+                        actual,
+                    });
+                }
+            }
+        }
+
+        // Styling must not add complexity without adding entropy
+        let words: Vec<String> = match self.word_set_provider.known_words() {
+            Some(words) => words
+                .into_iter()
+                .take(SAMPLE_WORDS.len())
+                .map(Into::into)
+                .collect(),
+            None => SAMPLE_WORDS.iter().map(|w| (*w).to_owned()).collect(),
+        };
+        for (index, styler) in self.word_stylers.iter().enumerate() {
+            let entropy = styler.entropy();
+            if entropy.bits() < NEGLIGIBLE_BITS
+                && words.iter().any(|w| &styler.style_word(w.clone()) != w)
+            {
+                warnings.push(Warning::ComplexityWithoutEntropy {
+                    kind: ComponentKind::WordStyler,
+                    index,
+                    entropy,
+                });
+            }
+        }
+        let phrase = self.phrase_builder.build_phrase(words.clone());
+        for (index, styler) in self.phrase_stylers.iter().enumerate() {
+            let entropy = styler.entropy();
+            if entropy.bits() < NEGLIGIBLE_BITS && styler.style_phrase(phrase.clone()) != phrase {
+                warnings.push(Warning::ComplexityWithoutEntropy {
+                    kind: ComponentKind::PhraseStyler,
+                    index,
+                    entropy,
+                });
+            }
+        }
+
+        // Components must not produce characters outside the character set of the builder
+        if let Some(charset) = self.phrase_builder.charset() {
+            let outside = |s: &str| s.chars().find(|c| !charset.contains(*c));
+            let known = self.word_set_provider.known_words();
+            let example = known
+                .as_ref()
+                .and_then(|words| words.iter().find_map(|w| outside(w)));
+            if let Some(example) = example {
+                warnings.push(Warning::OutsideCharset {
+                    kind: ComponentKind::WordSetProvider,
+                    index: 0,
+                    charset,
+                    example,
+                });
+            }
+            for (index, styler) in self.word_stylers.iter().enumerate() {
+                let example = words
+                    .iter()
+                    .find_map(|w| outside(&styler.style_word(w.clone())));
+                if let Some(example) = example {
+                    warnings.push(Warning::OutsideCharset {
+                        kind: ComponentKind::WordStyler,
+                        index,
+                        charset,
+                        example,
+                    });
+                }
+            }
+            for (index, styler) in self.phrase_stylers.iter().enumerate() {
+                if let Some(example) = outside(&styler.style_phrase(phrase.clone())) {
+                    warnings.push(Warning::OutsideCharset {
+                        kind: ComponentKind::PhraseStyler,
+                        index,
+                        charset,
+                        example,
+                    });
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// Build an entropy report for passphrases based on this scheme.
+    ///
+    /// The report contains the entropy each component contributes, the total entropy, and any
+    /// [warnings](Scheme::validate) found for this scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let report = scheme.entropy_report();
+    /// for component in &report.components {
+    ///     println!("{} #{}: {}", component.kind, component.index, component.entropy);
+    /// }
+    /// assert_eq!(scheme.entropy(), report.total);
+    /// # }
+    /// ```
+    pub fn entropy_report(&self) -> EntropyReport {
+        EntropyReport {
+            total: self.entropy,
+            components: self.component_entropies.clone(),
+            warnings: self.validate(),
+        }
+    }
+
+    /// Build a report of guess counts under multiple attacker models for the given `phrase`.
+    ///
+    /// The phrase should be generated with this scheme. See
+    /// [`AttackerReport`](AttackerReport) for the compared models, the
+    /// [default](DictionaryAttack::default) dictionary attack is assumed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let report = scheme.attacker_report(&scheme.generate());
+    /// println!("Knows scheme: {}", report.scheme_aware);
+    /// println!("Brute force: {}", report.brute_force);
+    /// println!("Dictionary: {}", report.dictionary);
+    /// assert_eq!(report.scheme_aware, report.weakest());
+    /// # }
+    /// ```
+    pub fn attacker_report(&self, phrase: &str) -> AttackerReport {
+        self.attacker_report_with(phrase, &DictionaryAttack::default())
+    }
+
+    /// Build a report of guess counts under multiple attacker models for the given `phrase`,
+    /// assuming the given dictionary `attack`.
+    ///
+    /// See [`attacker_report`](Scheme::attacker_report).
+    pub fn attacker_report_with(&self, phrase: &str, attack: &DictionaryAttack) -> AttackerReport {
+        AttackerReport::new(
+            phrase,
+            self.entropy(),
+            self.word_set_provider.word_count(),
+            attack,
+        )
+    }
+
+    /// Measure the acceptance rate of a rejection-based constraint on this scheme.
+    ///
+    /// This generates the given number of passphrases, and counts how many are accepted by the
+    /// `accept` predicate. Fold the result into the entropy of this scheme using
+    /// [`Entropy::with_acceptance`](Entropy::with_acceptance) to account for passphrases that are
+    /// rejected and regenerated, such as those exceeding a maximum length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let acceptance = scheme.measure_acceptance(1000, |phrase| phrase.len() <= 40);
+    /// let entropy = scheme.entropy().with_acceptance(&acceptance);
+    /// assert!(entropy <= scheme.entropy());
+    /// # }
+    /// ```
+    pub fn measure_acceptance<F>(&self, samples: usize, mut accept: F) -> Acceptance
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let accepted = (0..samples).filter(|_| accept(&self.generate())).count();
+        Acceptance::new(accepted, samples)
+    }
+
     /// Calculate the entropy that passphrases based on this scheme have.
     ///
     /// See the documentation on [Entropy](Entropy) for details on what entropy is and how it
     /// should be calculated.
+    ///
+    /// The entropy of word stylers is accumulated for each styled word, as they make a random
+    /// decision for each word. If the number of words is not known, see
+    /// [`WordSetProvider::word_count`](WordSetProvider::word_count), it is accumulated once.
+    ///
+    /// The entropy is calculated once when the scheme is constructed, so this is cheap to call
+    /// for each generated passphrase.
+    ///
+    /// If the scheme was built with
+    /// [`acceptance_samples`](SchemeBuilder::acceptance_samples), the measured acceptance rate of
+    /// the phrase validators is folded into the entropy, see
+    /// [`Entropy::with_acceptance`](Entropy::with_acceptance). Otherwise, rejected passphrases
+    /// are not accounted for, and the entropy is overestimated when validators reject many
+    /// passphrases.
+    ///
+    /// If any component reports entropy that is negative or not finite, see
+    /// [`Entropy::is_valid`](Entropy::is_valid), the total can't be trusted.
+    /// [`validate`](Scheme::validate) reports such components with
+    /// [`Warning::InvalidEntropy`](Warning::InvalidEntropy).
     pub fn entropy(&self) -> Entropy {
-        self.word_set_provider.entropy()
-            + self
-                .word_stylers
+        self.entropy
+    }
+
+    /// Get a fingerprint identifying the definition of this scheme.
+    ///
+    /// The fingerprint covers the structural definition of the scheme: the known words of the word
+    /// set provider, the number of words, the separators of the phrase builder, and the kind and
+    /// configuration of all other components. Computed values such as the entropy are not
+    /// covered, so the fingerprint is the same on every platform and for every construction of
+    /// the same scheme. It is used to check whether an [escrow](crate::escrow) record belongs to a
+    /// scheme. This is not a cryptographic hash, and must not be relied upon to detect
+    /// deliberate tampering.
+    ///
+    /// The configuration of components is taken from their `Debug` representation, so the
+    /// fingerprint may change between releases changing a component definition.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*};
+    ///
+    /// let config = BasicConfig::default();
+    /// assert_eq!(config.to_scheme().fingerprint(), config.to_scheme().fingerprint());
+    /// assert_ne!(config.to_scheme().fingerprint(), config.with_words(6).to_scheme().fingerprint());
+    /// # }
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        // 64-bit FNV-1a, length prefixed fields to prevent ambiguity
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut write = |bytes: &[u8]| {
+            for b in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+                hash ^= u64::from(*b);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+
+        match self.word_set_provider.known_words() {
+            Some(words) => words.iter().for_each(|w| write(w.as_bytes())),
+            None => write(&[]),
+        }
+        write(&(self.word_set_provider.word_count().unwrap_or(0) as u64).to_le_bytes());
+        for separator in self.phrase_builder.separators() {
+            write(separator.as_bytes());
+        }
+
+        // Word filters are covered by the known words they were applied to
+        let components = self
+            .word_stylers
+            .iter()
+            .map(|c| (ComponentKind::WordStyler, format!("{:?}", c)))
+            .chain(iter::once((
+                ComponentKind::PhraseBuilder,
+                format!("{:?}", self.phrase_builder),
+            )))
+            .chain(
+                self.phrase_stylers
+                    .iter()
+                    .map(|c| (ComponentKind::PhraseStyler, format!("{:?}", c))),
+            )
+            .chain(
+                self.phrase_validators
+                    .iter()
+                    .map(|c| (ComponentKind::PhraseValidator, format!("{:?}", c))),
+            );
+        for (kind, definition) in components {
+            write(kind.to_string().as_bytes());
+            write(definition.as_bytes());
+        }
+
+        hash
+    }
+
+    /// Get the measured acceptance rate of the phrase validators.
+    ///
+    /// This is only measured if the scheme was built with
+    /// [`acceptance_samples`](SchemeBuilder::acceptance_samples).
+    pub fn acceptance(&self) -> Option<Acceptance> {
+        self.acceptance
+    }
+
+    /// Estimate the cost of generating passphrases with this scheme.
+    ///
+    /// This reports the expected passphrase length range, the expected number of attempts and
+    /// allocations, and a coarse [cost class](CostClass), to compare configurations against a
+    /// latency budget without benchmarking them. See [`CostEstimate`](CostEstimate).
+    ///
+    /// The estimate is based on the components of this scheme and a dry run generating a small
+    /// number of passphrases, so calling this is much more expensive than generating a single
+    /// passphrase. Estimate once and cache the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{config::BasicConfig, prelude::*, scheme::CostClass};
+    ///
+    /// let scheme = BasicConfig::default().to_scheme();
+    /// let estimate = scheme.estimate();
+    /// assert_eq!(CostClass::Low, estimate.cost);
+    /// assert!(estimate.length.contains(&scheme.generate().chars().count()));
+    /// # }
+    /// ```
+    pub fn estimate(&self) -> CostEstimate {
+        let words = self.word_set_provider.word_count();
+
+        // Dry run without retrying, measuring lengths and how many phrases are accepted
+        let (mut min, mut max, mut accepted) = (usize::MAX, 0, 0);
+        for _ in 0..ESTIMATE_SAMPLES {
+            // Attempts failing to obtain words are never accepted
+            let phrase = match self.generate_once() {
+                Ok(phrase) => phrase,
+                Err(_) => continue,
+            };
+            let len = phrase.chars().count();
+            min = min.min(len);
+            max = max.max(len);
+            if self.validate_phrase(&phrase).is_ok() {
+                accepted += 1;
+            }
+        }
+
+        // Widen the range to the bounds of the known words, which the dry run may not hit
+        let known = self.word_set_provider.known_words();
+        if let (Some(known), Some(count)) = (known, words.filter(|&count| count > 0)) {
+            let lengths = known.iter().map(|w| w.chars().count());
+            let separators = self.phrase_builder.separators();
+            let separators = separators.iter().map(|s| s.chars().count());
+            let bounds = |words: Option<usize>, separators: Option<usize>| {
+                words.unwrap_or(0) * count + separators.unwrap_or(0) * (count - 1)
+            };
+            min = min.min(bounds(lengths.clone().min(), separators.clone().min()));
+            max = max.max(bounds(lengths.max(), separators.max()));
+        }
+
+        // Expected attempts until a phrase is accepted, bounded by the attempt limit
+        let max_attempts = self.limits.max_attempts as f64;
+        let rate = self
+            .acceptance
+            .unwrap_or_else(|| Acceptance::new(accepted, ESTIMATE_SAMPLES))
+            .rate();
+        let attempts = if rate > 0.0 {
+            (1.0 / rate).min(max_attempts)
+        } else {
+            max_attempts
+        };
+
+        // The provided words and their vector, each styled word, the phrase and each styled phrase
+        let words = words.unwrap_or(1) as f64;
+        let provided = if self.word_set_provider.word_refs().is_some() {
+            1.0
+        } else {
+            words + 1.0
+        };
+        let allocations = provided
+            + self.word_stylers.len() as f64 * words
+            + 1.0
+            + self.phrase_stylers.len() as f64;
+
+        let cost = if rate <= 0.0 || attempts > MODERATE_MAX_ATTEMPTS {
+            CostClass::High
+        } else if attempts > 1.0 {
+            CostClass::Moderate
+        } else {
+            CostClass::Low
+        };
+
+        CostEstimate {
+            length: min..=max,
+            attempts,
+            allocations: allocations * attempts,
+            cost,
+        }
+    }
+
+    /// Calculate the entropy each component contributes, in pipeline order.
+    fn calculate_component_entropies(&self) -> Vec<ComponentEntropy> {
+        let component = |kind, index, entropy| ComponentEntropy {
+            kind,
+            index,
+            entropy,
+        };
+
+        // Word stylers are applied to each word
+        let words = self.word_set_provider.word_count().unwrap_or(1);
+
+        let mut components = vec![component(
+            ComponentKind::WordSetProvider,
+            0,
+            self.word_set_provider.entropy(),
+        )];
+        components.extend(
+            self.word_stylers
                 .iter()
-                .map(|p| p.entropy())
-                .sum::<Entropy>()
-            + self.phrase_builder.entropy()
-            + self
-                .phrase_stylers
+                .enumerate()
+                .map(|(i, p)| component(ComponentKind::WordStyler, i, p.entropy() * words as f64)),
+        );
+        components.push(component(
+            ComponentKind::PhraseBuilder,
+            0,
+            self.phrase_builder.entropy(),
+        ));
+        components.extend(
+            self.phrase_stylers
                 .iter()
-                .map(|p| p.entropy())
-                .sum::<Entropy>()
+                .enumerate()
+                .map(|(i, p)| component(ComponentKind::PhraseStyler, i, p.entropy())),
+        );
+        components
+    }
+}
+
+/// Check whether the given passphrase is accepted by all `validators`.
+///
+/// Returns the reason of the first validator rejecting it.
+fn validate_phrase(validators: &[Box<dyn PhraseValidator>], phrase: &str) -> Result<(), String> {
+    validators
+        .iter()
+        .try_for_each(|v| v.validate_phrase(phrase))
+}
+
+/// Run the given generation `attempt` until its result is accepted by all `validators`, within
+/// the given `limits`.
+///
+/// An attempt fails if a component exceeds its own limits, such as a word set provider sampling
+/// unique words. That limit is returned right away, as retrying is unlikely to help.
+///
+/// This doesn't borrow the scheme, so attempts may mutate its other components. See
+/// [`Scheme::retry_within`](Scheme::retry_within).
+fn retry_validated<T, A, P>(
+    validators: &[Box<dyn PhraseValidator>],
+    limits: GenerationLimits,
+    mut attempt: A,
+    phrase: P,
+) -> Result<T, RejectedError>
+where
+    A: FnMut() -> Result<T, LimitExceeded>,
+    P: Fn(&T) -> &str,
+{
+    let mut attempts = limits.start();
+    let mut reason = String::new();
+    loop {
+        if let Err(limit) = attempts.next() {
+            return Err(RejectedError { limit, reason });
+        }
+
+        let result = attempt().map_err(|limit| RejectedError {
+            limit,
+            reason: "no unique passphrase word was obtained".into(),
+        })?;
+        match validate_phrase(validators, phrase(&result)) {
+            Ok(()) => return Ok(result),
+            Err(rejection) => reason = rejection,
+        }
     }
 }
 
@@ -148,12 +1393,69 @@ impl Iterator for Scheme {
     /// Generate a new passphrase based on this scheme.
     ///
     /// This method always returns `Some` holding a passphrase.
+    /// See [`generate_mut`](Scheme::generate_mut).
     fn next(&mut self) -> Option<String> {
-        Some(self.generate())
+        Some(self.generate_mut())
     }
 }
 
 impl SchemeBuilder {
+    /// Build the scheme.
+    ///
+    /// An error is returned if a component was not set, or if a word filter can't be applied to
+    /// the word set provider, see [`WordSetProvider::filter_words`](WordSetProvider::filter_words).
+    pub fn build(self) -> Result<Scheme, SchemeBuilderError> {
+        self.build_uncached().and_then(|built| {
+            // Apply the word filters to the underlying wordlist
+            let mut provider = built.word_set_provider;
+            for filter in &built.word_filters {
+                provider = provider
+                    .filter_words(filter.as_ref())
+                    .map_err(|err| SchemeBuilderError::ValidationError(err.to_string()))?;
+            }
+
+            let mut scheme = Scheme::new(
+                provider,
+                built.word_stylers,
+                built.phrase_builder,
+                built.phrase_stylers,
+            );
+            scheme.word_filters = built.word_filters;
+            scheme.phrase_validators = built.phrase_validators;
+            scheme.limits = built.limits;
+            scheme.acceptance_samples = built.acceptance_samples;
+
+            // Fold the acceptance rate of the validators into the entropy
+            if built.acceptance_samples > 0 && !scheme.phrase_validators.is_empty() {
+                let accepted = (0..built.acceptance_samples)
+                    .filter(|_| match scheme.generate_once() {
+                        Ok(phrase) => scheme.validate_phrase(&phrase).is_ok(),
+                        Err(_) => false,
+                    })
+                    .count();
+                let acceptance = Acceptance::new(accepted, built.acceptance_samples);
+                scheme.entropy = scheme.entropy.with_acceptance(&acceptance);
+                scheme.acceptance = Some(acceptance);
+            }
+
+            Ok(scheme)
+        })
+    }
+
+    /// Add a single word filter to the scheme.
+    pub fn add_word_filter(mut self, filter: Box<dyn WordFilter>) -> Self {
+        self.word_filters.get_or_insert_with(Vec::new).push(filter);
+        self
+    }
+
+    /// Add a single phrase validator to the scheme.
+    pub fn add_phrase_validator(mut self, validator: Box<dyn PhraseValidator>) -> Self {
+        self.phrase_validators
+            .get_or_insert_with(Vec::new)
+            .push(validator);
+        self
+    }
+
     /// Add a single word styler to the scheme.
     pub fn add_word_styler(mut self, styler: Box<dyn WordStyler>) -> Self {
         match self.word_stylers {
@@ -173,6 +1475,282 @@ impl SchemeBuilder {
 
         self
     }
+
+    /// Insert a single word styler at the given position in the pipeline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is higher than the number of staged word stylers.
+    pub fn insert_word_styler(mut self, index: usize, styler: Box<dyn WordStyler>) -> Self {
+        self.word_stylers
+            .get_or_insert_with(Vec::new)
+            .insert(index, styler);
+        self
+    }
+
+    /// Remove the word styler at the given position in the pipeline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no staged word styler at `index`.
+    pub fn remove_word_styler(mut self, index: usize) -> Self {
+        self.word_stylers.get_or_insert_with(Vec::new).remove(index);
+        self
+    }
+
+    /// Insert a single phrase styler at the given position in the pipeline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is higher than the number of staged phrase stylers.
+    pub fn insert_phrase_styler(mut self, index: usize, styler: Box<dyn PhraseStyler>) -> Self {
+        self.phrase_stylers
+            .get_or_insert_with(Vec::new)
+            .insert(index, styler);
+        self
+    }
+
+    /// Remove the phrase styler at the given position in the pipeline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no staged phrase styler at `index`.
+    pub fn remove_phrase_styler(mut self, index: usize) -> Self {
+        self.phrase_stylers
+            .get_or_insert_with(Vec::new)
+            .remove(index);
+        self
+    }
+
+    /// Replace the phrase builder, dropping any previously staged builder.
+    pub fn replace_phrase_builder(mut self, builder: Box<dyn PhraseBuilder>) -> Self {
+        self.phrase_builder = Some(builder);
+        self
+    }
+
+    /// Get the staged word set provider, if set.
+    pub fn staged_word_set_provider(&self) -> Option<&dyn WordSetProvider> {
+        self.word_set_provider.as_deref()
+    }
+
+    /// Iterate over the staged word filters, in pipeline order.
+    pub fn staged_word_filters(&self) -> impl Iterator<Item = &dyn WordFilter> {
+        self.word_filters.iter().flatten().map(AsRef::as_ref)
+    }
+
+    /// Iterate over the staged word stylers, in pipeline order.
+    pub fn staged_word_stylers(&self) -> impl Iterator<Item = &dyn WordStyler> {
+        self.word_stylers.iter().flatten().map(AsRef::as_ref)
+    }
+
+    /// Get the staged phrase builder, if set.
+    pub fn staged_phrase_builder(&self) -> Option<&dyn PhraseBuilder> {
+        self.phrase_builder.as_deref()
+    }
+
+    /// Iterate over the staged phrase stylers, in pipeline order.
+    pub fn staged_phrase_stylers(&self) -> impl Iterator<Item = &dyn PhraseStyler> {
+        self.phrase_stylers.iter().flatten().map(AsRef::as_ref)
+    }
+
+    /// Iterate over the staged phrase validators.
+    pub fn staged_phrase_validators(&self) -> impl Iterator<Item = &dyn PhraseValidator> {
+        self.phrase_validators.iter().flatten().map(AsRef::as_ref)
+    }
+}
+
+/// An error for a scheme not generating a passphrase accepted by its phrase validators.
+///
+/// This is returned by [`Scheme::try_generate`](Scheme::try_generate) when all attempts are
+/// rejected, which hints at validators rejecting most passphrases of the scheme.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error("no generated passphrase was accepted {limit}, last rejected because {reason}")]
+pub struct RejectedError {
+    /// The limit that was exceeded.
+    pub limit: LimitExceeded,
+
+    /// The reason the last attempt was rejected.
+    pub reason: String,
+}
+
+/// An estimate of the cost of generating passphrases with a [`Scheme`](Scheme).
+///
+/// Construct an estimate using [`Scheme::estimate`](Scheme::estimate).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CostEstimate {
+    /// The expected range of passphrase lengths in characters.
+    ///
+    /// This covers the passphrases generated in the dry run and, if the wordlist is known, the
+    /// shortest and longest words joined by the separators. Stylers may rarely produce
+    /// passphrases outside this range.
+    pub length: RangeInclusive<usize>,
+
+    /// The expected number of attempts to generate a passphrase accepted by the phrase
+    /// validators, at most the maximum number of attempts.
+    pub attempts: f64,
+
+    /// The expected number of allocations to generate a passphrase, over all attempts.
+    ///
+    /// This counts the allocations of the generation pipeline, assuming each word styler changes
+    /// each word. Components may allocate more internally.
+    pub allocations: f64,
+
+    /// The cost class of generating a passphrase.
+    pub cost: CostClass,
+}
+
+/// A coarse class of the cost of generating a passphrase, see [`CostEstimate`](CostEstimate).
+///
+/// Classes are ordered from cheapest to most expensive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CostClass {
+    /// A single attempt is expected, each passphrase is generated once.
+    Low,
+
+    /// A few attempts are expected, as phrase validators reject some passphrases.
+    Moderate,
+
+    /// Many attempts are expected, as phrase validators reject most passphrases. Generating may
+    /// exceed the [limits](GenerationLimits) and fail.
+    High,
+}
+
+/// A generated passphrase, along with the words it was built from and its strength.
+///
+/// This is produced by [`Scheme::generate_detailed`](Scheme::generate_detailed). The words are
+/// retained after word styling, but before the phrase was built and phrase stylers were applied,
+/// so word boundaries are known regardless of the separator that is used.
+///
+/// The entropy is that of the scheme the passphrase was generated with, so user interfaces can
+/// show strength information without access to the scheme.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GeneratedPassphrase {
+    /// The generated passphrase.
+    phrase: String,
+
+    /// The styled words the passphrase was built from.
+    words: Vec<String>,
+
+    /// The entropy each scheme component contributes, in pipeline order.
+    component_entropies: Vec<ComponentEntropy>,
+
+    /// The total entropy of the scheme.
+    entropy: Entropy,
+
+    /// The audit record, if generated through [`Scheme::generate_audited`].
+    audit: Option<Audit>,
+}
+
+impl GeneratedPassphrase {
+    /// The generated passphrase.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// The styled words the passphrase was built from, in order.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// The number of words the passphrase was built from.
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    /// The entropy each component of the scheme contributes, in pipeline order.
+    ///
+    /// See [`Scheme::entropy_report`](Scheme::entropy_report).
+    pub fn component_entropies(&self) -> &[ComponentEntropy] {
+        &self.component_entropies
+    }
+
+    /// The total entropy of the scheme the passphrase was generated with.
+    ///
+    /// See [`Scheme::entropy`](Scheme::entropy).
+    pub fn entropy(&self) -> Entropy {
+        self.entropy
+    }
+
+    /// The audit record of this passphrase.
+    ///
+    /// This is only available for passphrases generated through
+    /// [`Scheme::generate_audited`](Scheme::generate_audited).
+    pub fn audit(&self) -> Option<&Audit> {
+        self.audit.as_ref()
+    }
+
+    /// Consume this result, returning the generated passphrase.
+    pub fn into_phrase(self) -> String {
+        self.phrase
+    }
+
+    /// The first character of each passphrase word.
+    ///
+    /// This may be shown as memorization hint, for example `chbs` for the passphrase
+    /// `correct horse battery staple`. Characters are kept as styled, so capitalized words produce
+    /// a capital letter.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::{config::BasicConfig, prelude::*, probability::Probability};
+    ///
+    /// let config = BasicConfig::new(["correct"])
+    ///     .with_words(4)
+    ///     .with_capitalize_first(Probability::Never);
+    /// assert_eq!("cccc", config.to_scheme().generate_detailed().acronym());
+    /// ```
+    pub fn acronym(&self) -> String {
+        self.words.iter().filter_map(|w| w.chars().next()).collect()
+    }
+}
+
+/// An audit record of the randomness used to generate a passphrase.
+///
+/// This is produced by [`Scheme::generate_audited`](Scheme::generate_audited). It holds all
+/// randomness obtained during generation, in order, and the index of each sampled word in the
+/// known wordlist. The passphrase may be reproduced from the randomness by passing
+/// [`replay`](Audit::replay) to [`Scheme::generate_with_rng`](Scheme::generate_with_rng).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Audit {
+    /// All randomness obtained during generation, in order.
+    pub(crate) randomness: Vec<u8>,
+
+    /// The index of each sampled word in the known wordlist.
+    pub(crate) word_indices: Vec<Option<usize>>,
+}
+
+impl Audit {
+    /// All randomness obtained during generation, in order.
+    ///
+    /// Each `u32` or `u64` drawn from the randomness source is recorded in little-endian byte
+    /// order.
+    pub fn randomness(&self) -> &[u8] {
+        &self.randomness
+    }
+
+    /// The index of each sampled word in the known wordlist, before word styling.
+    ///
+    /// An index is `None` if the word is not in the wordlist, such as an injected number, or if
+    /// the wordlist is not [known](WordSetProvider::known_words).
+    pub fn word_indices(&self) -> &[Option<usize>] {
+        &self.word_indices
+    }
+
+    /// Get a randomness source replaying the recorded randomness.
+    pub fn replay(&self) -> ReplayRng {
+        ReplayRng::new(self.randomness.clone())
+    }
+}
+
+impl fmt::Display for GeneratedPassphrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.phrase)
+    }
 }
 
 /// A trait providing an interface to build a password scheme based on some sort of configuration.
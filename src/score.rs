@@ -0,0 +1,81 @@
+//! Scoring passphrases for ranked suggestions
+//!
+//! This module provides the [`Scorer`](Scorer) trait, used by
+//! [`Scheme::suggest`](crate::scheme::Scheme::suggest) to rank candidate passphrases so users can
+//! pick one from a list. Scorers are provided for:
+//!
+//! - [`Memorability`](Memorability): prefers short words and few symbols.
+//! - [`TypingEffort`](TypingEffort): prefers passphrases needing few keystrokes.
+//!
+//! Any closure taking a [`GeneratedPassphrase`](GeneratedPassphrase) and returning an `f64` is a
+//! scorer as well.
+
+use crate::scheme::GeneratedPassphrase;
+
+/// Something that scores a passphrase, where a higher score is better.
+pub trait Scorer {
+    /// Score the given passphrase. A higher score ranks the passphrase higher.
+    fn score(&self, passphrase: &GeneratedPassphrase) -> f64;
+}
+
+impl<F> Scorer for F
+where
+    F: Fn(&GeneratedPassphrase) -> f64,
+{
+    fn score(&self, passphrase: &GeneratedPassphrase) -> f64 {
+        self(passphrase)
+    }
+}
+
+/// A scorer preferring passphrases that are easy to remember.
+///
+/// Passphrases with fewer characters in their words score higher. Each character in the phrase
+/// that is not a letter or space, such as a digit or symbol, weighs as much as three letters.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Memorability;
+
+impl Scorer for Memorability {
+    fn score(&self, passphrase: &GeneratedPassphrase) -> f64 {
+        let letters: usize = passphrase.words().iter().map(|w| w.chars().count()).sum();
+        let symbols = passphrase
+            .phrase()
+            .chars()
+            .filter(|c| !c.is_alphabetic() && *c != ' ')
+            .count();
+        -((letters + symbols * 3) as f64)
+    }
+}
+
+/// A scorer preferring passphrases that are quick to type.
+///
+/// This counts keystrokes on a US QWERTY keyboard, where a higher score means fewer keystrokes.
+/// Each character takes one keystroke, plus one for shift if it is a capital or shifted symbol.
+/// Characters outside ASCII, which need compose sequences or a different layout, take four.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TypingEffort;
+
+impl TypingEffort {
+    /// Symbols typed with shift on a US QWERTY keyboard.
+    const SHIFTED: &'static str = "~!@#$%^&*()_+{}|:\"<>?";
+
+    /// The number of keystrokes to type the given character.
+    fn keystrokes(c: char) -> usize {
+        if !c.is_ascii() {
+            4
+        } else if c.is_ascii_uppercase() || Self::SHIFTED.contains(c) {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+impl Scorer for TypingEffort {
+    fn score(&self, passphrase: &GeneratedPassphrase) -> f64 {
+        -(passphrase
+            .phrase()
+            .chars()
+            .map(Self::keystrokes)
+            .sum::<usize>() as f64)
+    }
+}
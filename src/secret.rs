@@ -0,0 +1,63 @@
+//! Passphrases wiped from memory on drop
+//!
+//! Passphrases are generated as ordinary `String`s, which may linger in memory after they are
+//! dropped. This module provides [`Passphrase`](Passphrase), a wrapper that overwrites its
+//! contents with zeroes when dropped, and only exposes them through an explicit accessor.
+//!
+//! Use [`Scheme::generate_secret`](crate::scheme::Scheme::generate_secret) to generate one.
+//!
+//! This is only available with the `zeroize` feature.
+
+use std::fmt;
+
+use zeroize::Zeroize;
+
+/// A passphrase that is wiped from memory when dropped.
+///
+/// The passphrase is only exposed through [`expose`](Passphrase::expose). It is not printed by
+/// its `Debug` implementation, to prevent leaking it into logs.
+///
+/// Only the final passphrase is wiped. Intermediate values produced while generating it, such as
+/// styled words, are not.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{config::BasicConfig, prelude::*};
+///
+/// let passphrase = BasicConfig::default().to_scheme().generate_secret();
+/// assert!(!passphrase.expose().is_empty());
+/// assert_eq!(format!("{:?}", passphrase), "Passphrase(***)");
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    /// Expose the passphrase.
+    ///
+    /// Copies of the returned string are not wiped, so don't keep them around longer than needed.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Passphrase {
+    fn from(passphrase: String) -> Self {
+        Self(passphrase)
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Passphrase(***)")
+    }
+}
+
+impl Drop for Passphrase {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
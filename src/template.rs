@@ -0,0 +1,342 @@
+//! Schemes defined by template strings
+//!
+//! A [`Template`](Template) describes a passphrase format as a string, so command line tools
+//! and configuration files can define schemes without writing Rust code. Slots in braces are
+//! replaced with random values, everything else is kept literally:
+//!
+//! ```txt
+//! {word:capitalize}-{word}-{word}{digit}{symbol}
+//! ```
+//!
+//! The following slots are available:
+//!
+//! - `{word}`: a word from the wordlist, see [`with_wordlist`](Template::with_wordlist).
+//! - `{digit}`: a digit from `0` to `9`.
+//! - `{symbol}`: a symbol from [`SHELL_SAFE_SYMBOLS`](crate::charset::SHELL_SAFE_SYMBOLS).
+//!
+//! Words may be styled with the `capitalize`, `upper` or `lower` modifiers, given after a colon.
+//! Modifiers are applied to every word in the slot, so they don't add entropy. Use `{{` and `}}`
+//! for literal braces.
+//!
+//! The [scheme](crate::scheme::Scheme) built from a template reports the entropy of all slots.
+//! That entropy is only reached if words can't run into each other, so adjacent `{word}` slots
+//! need a prefix-free wordlist. Use [`validate`](Template::validate) to check a template.
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::charset::SHELL_SAFE_SYMBOLS;
+use crate::component::{
+    grapheme::uppercase_first_grapheme, phrase::TemplatePhraseBuilder,
+    word::PositionalWordSetProvider,
+};
+use crate::prelude::*;
+use crate::scheme::Scheme;
+use crate::validate::Warning;
+use crate::word::WordList;
+
+/// The digits sampled for a `{digit}` slot.
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// A scheme defined by a template string.
+///
+/// See the [module](self) documentation for the template syntax.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{prelude::*, template::Template};
+///
+/// let template = Template::parse("{word:capitalize}-{word}-{word}{digit}{symbol}").unwrap();
+/// let scheme = template.to_scheme();
+/// println!("Passphrase: {}", scheme.generate());
+///
+/// // Three words from the 7776 word EFF list, a digit and one of 10 symbols
+/// let bits = 3.0 * 7776f64.log2() + 10f64.log2() + 10f64.log2();
+/// assert!((scheme.entropy().bits() - bits).abs() < 1e-9);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Template {
+    /// The literal text before, between and after slots, one more than the number of slots.
+    literals: Vec<String>,
+
+    /// The slots, in order.
+    slots: Vec<Slot>,
+
+    /// The wordlist to sample words from, if set.
+    wordlist: Option<WordList>,
+}
+
+/// A slot in a template, replaced with a random value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Slot {
+    /// A word from the wordlist, with the given style.
+    Word(WordStyle),
+
+    /// A digit.
+    Digit,
+
+    /// A symbol.
+    Symbol,
+}
+
+/// A style applied to every word in a slot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum WordStyle {
+    /// Keep words as they are.
+    Keep,
+
+    /// Uppercase the first character.
+    Capitalize,
+
+    /// Uppercase all characters.
+    Upper,
+
+    /// Lowercase all characters.
+    Lower,
+}
+
+impl Template {
+    /// Parse the given template string.
+    ///
+    /// Words are sampled from the default wordlist, use
+    /// [`with_wordlist`](Template::with_wordlist) to change this. Without the
+    /// `wordlist-eff-large` feature there is no default wordlist, and one must be set for
+    /// templates with word slots. An error is returned if the template is invalid, or if it
+    /// doesn't contain any slot.
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let mut literals = vec![String::new()];
+        let mut slots = Vec::new();
+
+        let mut chars = template.char_indices().peekable();
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                    chars.next();
+                    literals.last_mut().unwrap().push('{');
+                }
+                '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                    chars.next();
+                    literals.last_mut().unwrap().push('}');
+                }
+                '{' => {
+                    let slot: String = chars
+                        .by_ref()
+                        .map(|(_, c)| c)
+                        .take_while(|c| *c != '}')
+                        .collect();
+                    if !template[position..].contains('}') {
+                        return Err(TemplateError::Unclosed { position });
+                    }
+                    slots.push(Slot::parse(&slot)?);
+                    literals.push(String::new());
+                }
+                '}' => return Err(TemplateError::UnmatchedClose { position }),
+                c => literals.last_mut().unwrap().push(c),
+            }
+        }
+
+        if slots.is_empty() {
+            return Err(TemplateError::NoSlots);
+        }
+        Ok(Self {
+            literals,
+            slots,
+            #[cfg(feature = "wordlist-eff-large")]
+            wordlist: Some(WordList::default()),
+            #[cfg(not(feature = "wordlist-eff-large"))]
+            wordlist: None,
+        })
+    }
+
+    /// Set the wordlist to sample `{word}` slots from.
+    pub fn with_wordlist(mut self, wordlist: WordList) -> Self {
+        self.wordlist = Some(wordlist);
+        self
+    }
+
+    /// Validate this template.
+    ///
+    /// This checks the [scheme](Scheme::validate) built from this template, and checks that
+    /// adjacent word slots without literal text in between, such as in `{word}{word}`, can't
+    /// produce the same passphrase from different words. That requires a
+    /// [prefix-free](crate::word::analysis::WordListReport::prefix_free) wordlist. A list of found
+    /// problems is returned, which is empty if no problems were found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::{template::Template, word::WordList};
+    ///
+    /// let words = vec!["for", "fort", "tress", "ress"];
+    /// let wordlist = WordList::try_new(words.into_iter().map(String::from).collect()).unwrap();
+    /// let template = Template::parse("{word}{word}").unwrap().with_wordlist(wordlist);
+    /// assert_eq!(1, template.validate().len());
+    ///
+    /// let words = vec!["fort", "ress"];
+    /// let wordlist = WordList::try_new(words.into_iter().map(String::from).collect()).unwrap();
+    /// let template = Template::parse("{word}{word}").unwrap().with_wordlist(wordlist);
+    /// assert!(template.validate().is_empty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the template has word slots, but no wordlist is set, see
+    /// [`to_scheme`](Template::to_scheme).
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings = self.to_scheme().validate();
+
+        // Literals between slots are offset by one, the first literal precedes all slots
+        let adjacent =
+            self.slots
+                .windows(2)
+                .zip(&self.literals[1..])
+                .position(|(pair, literal)| {
+                    literal.is_empty() && matches!(pair, [Slot::Word(_), Slot::Word(_)])
+                });
+        if let (Some(slot), Some(wordlist)) = (adjacent, &self.wordlist) {
+            if !wordlist.analyze().prefix_free {
+                warnings.push(Warning::AdjacentWords { slot });
+            }
+        }
+
+        warnings
+    }
+}
+
+impl Slot {
+    /// Parse a slot from its contents between braces, such as `word:capitalize`.
+    fn parse(slot: &str) -> Result<Self, TemplateError> {
+        let mut parts = slot.splitn(2, ':');
+        let name = parts.next().unwrap_or_default().trim();
+        let modifier = parts.next().map(str::trim);
+        let unknown_modifier = |modifier: &str| TemplateError::UnknownModifier {
+            slot: name.to_owned(),
+            modifier: modifier.to_owned(),
+        };
+
+        match (name, modifier) {
+            ("word", None) => Ok(Slot::Word(WordStyle::Keep)),
+            ("word", Some("capitalize")) => Ok(Slot::Word(WordStyle::Capitalize)),
+            ("word", Some("upper")) => Ok(Slot::Word(WordStyle::Upper)),
+            ("word", Some("lower")) => Ok(Slot::Word(WordStyle::Lower)),
+            ("digit", None) => Ok(Slot::Digit),
+            ("symbol", None) => Ok(Slot::Symbol),
+            ("word", Some(modifier)) | ("digit", Some(modifier)) | ("symbol", Some(modifier)) => {
+                Err(unknown_modifier(modifier))
+            }
+            (name, _) => Err(TemplateError::UnknownSlot(name.to_owned())),
+        }
+    }
+
+    /// Build a word provider for this slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is a word slot, and no wordlist is given.
+    fn provider(self, wordlist: Option<&WordList>) -> Box<dyn DynWordProvider> {
+        let sampler = || {
+            wordlist
+                .expect("cannot build scheme for template with word slots, no wordlist set")
+                .sampler()
+        };
+        match self {
+            Slot::Word(WordStyle::Keep) => Box::new(sampler()),
+            Slot::Word(WordStyle::Capitalize) => Box::new(sampler().map(capitalize as fn(_) -> _)),
+            Slot::Word(WordStyle::Upper) => Box::new(sampler().map(upper as fn(_) -> _)),
+            Slot::Word(WordStyle::Lower) => Box::new(sampler().map(lower as fn(_) -> _)),
+            Slot::Digit => Box::new(DIGITS),
+            Slot::Symbol => Box::new(
+                SHELL_SAFE_SYMBOLS
+                    .chars()
+                    .map(String::from)
+                    .collect::<Vec<String>>(),
+            ),
+        }
+    }
+}
+
+/// Uppercase the first character of the given word.
+fn capitalize(word: String) -> String {
+    uppercase_first_grapheme(&word)
+}
+
+/// Uppercase all characters of the given word.
+fn upper(word: String) -> String {
+    word.to_uppercase()
+}
+
+/// Lowercase all characters of the given word.
+fn lower(word: String) -> String {
+    word.to_lowercase()
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::parse(template)
+    }
+}
+
+impl ToScheme for Template {
+    /// Build a scheme generating passphrases of this template.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the template has word slots, but no wordlist is set. This only happens without
+    /// the `wordlist-eff-large` feature, see [`parse`](Template::parse).
+    fn to_scheme(&self) -> Scheme {
+        Scheme::new(
+            Box::new(PositionalWordSetProvider::new(
+                self.slots
+                    .iter()
+                    .map(|slot| slot.provider(self.wordlist.as_ref()))
+                    .collect(),
+            )),
+            Vec::new(),
+            Box::new(TemplatePhraseBuilder::new(self.literals.clone())),
+            Vec::new(),
+        )
+    }
+}
+
+/// A [`Template`](Template) parsing error.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A slot is opened but never closed.
+    #[error("unclosed slot at position {position}, use '{{{{' for a literal brace")]
+    Unclosed {
+        /// The byte position of the opening brace.
+        position: usize,
+    },
+
+    /// A closing brace doesn't close a slot.
+    #[error("unmatched '}}' at position {position}, use '}}}}' for a literal brace")]
+    UnmatchedClose {
+        /// The byte position of the closing brace.
+        position: usize,
+    },
+
+    /// A slot has an unknown name.
+    #[error("unknown slot '{0}', expected 'word', 'digit' or 'symbol'")]
+    UnknownSlot(String),
+
+    /// A slot has an unknown modifier.
+    #[error("unknown modifier '{modifier}' for slot '{slot}'")]
+    UnknownModifier {
+        /// The name of the slot.
+        slot: String,
+
+        /// The unknown modifier.
+        modifier: String,
+    },
+
+    /// The template doesn't contain any slot.
+    #[error("template doesn't contain any slot")]
+    NoSlots,
+}
@@ -0,0 +1,141 @@
+//! Known-answer test vectors for seeded passphrase generation
+//!
+//! Passphrases [generated from a seed](Scheme::generate_seeded) are deterministic, the same
+//! scheme and seed produce the same passphrase on every platform, whether it is 32-bit or 64-bit.
+//! This module generates known-answer [`TestVectors`](TestVectors) for a
+//! [scheme specification](SchemeSpec), and verifies them later. Packagers may use this to prove a
+//! build of this crate on their platform produces the expected outputs.
+//!
+//! With the `serde` feature, test vectors can be serialized along with their scheme
+//! specification, to store them next to a build.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "wordlist-eff-large")]
+//! # {
+//! use chbs::{
+//!     registry::{ComponentSpec, Params, Registry, SchemeSpec},
+//!     testvectors::TestVectors,
+//! };
+//!
+//! let registry = Registry::with_builtins();
+//! let spec = SchemeSpec {
+//!     word_set_provider: ComponentSpec::new("fixed", Params::new().with("words", "4")),
+//!     word_filters: vec![],
+//!     word_stylers: vec![],
+//!     phrase_builder: ComponentSpec::new("basic", Params::new().with("separator", "-")),
+//!     phrase_stylers: vec![],
+//!     phrase_validators: vec![],
+//! };
+//!
+//! let vectors = TestVectors::generate(&registry, spec, 0..16).unwrap();
+//! assert!(vectors.verify(&registry).is_ok());
+//! # }
+//! ```
+
+use thiserror::Error;
+
+use crate::registry::{Registry, RegistryError, SchemeSpec};
+use crate::scheme::Scheme;
+
+/// A single known-answer test vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestVector {
+    /// The seed the passphrase is generated with.
+    pub seed: u64,
+
+    /// The expected passphrase.
+    pub phrase: String,
+}
+
+/// A set of known-answer test vectors for a scheme specification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestVectors {
+    /// The version of this crate the vectors were generated with.
+    ///
+    /// This is informational, vectors are verified regardless of the version.
+    pub version: String,
+
+    /// The specification of the scheme the vectors were generated with.
+    pub spec: SchemeSpec,
+
+    /// The test vectors.
+    pub vectors: Vec<TestVector>,
+}
+
+impl TestVectors {
+    /// Generate test vectors for the given scheme specification, one for each of the `seeds`.
+    ///
+    /// An error is returned if the scheme can't be built using the `registry`.
+    pub fn generate<I>(
+        registry: &Registry,
+        spec: SchemeSpec,
+        seeds: I,
+    ) -> Result<Self, RegistryError>
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        let scheme = registry.build(&spec)?;
+        let vectors = seeds
+            .into_iter()
+            .map(|seed| TestVector {
+                seed,
+                phrase: scheme.generate_seeded(seed),
+            })
+            .collect();
+
+        Ok(Self {
+            version: env!("CARGO_PKG_VERSION").into(),
+            spec,
+            vectors,
+        })
+    }
+
+    /// Verify the test vectors, using the given `registry` to build the scheme.
+    ///
+    /// An error is returned for the first vector producing a different passphrase.
+    pub fn verify(&self, registry: &Registry) -> Result<(), TestVectorError> {
+        self.verify_scheme(&registry.build(&self.spec)?)
+    }
+
+    /// Verify the test vectors against an already built scheme.
+    ///
+    /// The scheme should be built from the [specification](TestVectors::spec) of these vectors.
+    pub fn verify_scheme(&self, scheme: &Scheme) -> Result<(), TestVectorError> {
+        for vector in &self.vectors {
+            let actual = scheme.generate_seeded(vector.seed);
+            if actual != vector.phrase {
+                return Err(TestVectorError::Mismatch {
+                    seed: vector.seed,
+                    expected: vector.phrase.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`TestVectors`](TestVectors) verification error.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TestVectorError {
+    /// The scheme could not be built from its specification.
+    #[error("failed to build scheme for test vectors: {0}")]
+    Registry(#[from] RegistryError),
+
+    /// A test vector produced a different passphrase.
+    #[error("test vector with seed {seed} produced '{actual}', expected '{expected}'")]
+    Mismatch {
+        /// The seed of the failing vector.
+        seed: u64,
+
+        /// The expected passphrase.
+        expected: String,
+
+        /// The passphrase actually produced.
+        actual: String,
+    },
+}
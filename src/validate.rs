@@ -0,0 +1,197 @@
+//! Scheme and configuration validation
+//!
+//! This module provides the [`Warning`](Warning) type describing problems found when validating
+//! a [`Scheme`](crate::scheme::Scheme) or configuration structure, see
+//! [`Scheme::validate`](crate::scheme::Scheme::validate).
+//!
+//! Helpers for specific checks are provided as well, such as
+//! [`check_separator`](check_separator) to ensure a separator never occurs inside wordlist
+//! entries.
+
+use thiserror::Error;
+
+use crate::charset::Charset;
+use crate::component::ComponentKind;
+use crate::entropy::Entropy;
+
+/// Styling components adding less entropy than this number of bits are considered negligible.
+pub(crate) const NEGLIGIBLE_BITS: f64 = 0.1;
+
+/// Candidate separators suggested when a separator collides with wordlist contents.
+const SEPARATOR_CANDIDATES: [&str; 12] =
+    [" ", "-", "_", ".", "+", "=", "/", ":", ",", "~", "*", "#"];
+
+/// A problem found while validating a scheme or configuration.
+///
+/// Warnings don't prevent passphrase generation, but indicate a configuration that likely doesn't
+/// behave as intended.
+#[derive(Error, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Warning {
+    /// The phrase separator occurs inside wordlist entries.
+    #[error(transparent)]
+    Separator(#[from] SeparatorError),
+
+    /// A styling component visibly changes passphrases, but adds negligible entropy.
+    ///
+    /// Such a component adds complexity to passphrases without making them stronger, for example
+    /// when always capitalizing words or appending a fixed suffix. Users commonly mistake such
+    /// passphrases for being stronger than they are.
+    #[error("{kind} #{index} changes passphrases but only adds {entropy}, it does not make them stronger")]
+    ComplexityWithoutEntropy {
+        /// The kind of the styling component.
+        kind: ComponentKind,
+
+        /// The index of the component within its kind.
+        index: usize,
+
+        /// The entropy the component adds.
+        entropy: Entropy,
+    },
+
+    /// A component reports entropy that is negative or not finite.
+    ///
+    /// This is a bug in the component, often caused by unchecked entropy arithmetic. The invalid
+    /// value propagates into the total [entropy](crate::scheme::Scheme::entropy) of the scheme,
+    /// which can't be trusted. See [`Entropy::is_valid`](Entropy::is_valid).
+    #[error("{kind} #{index} reports invalid entropy of {entropy}")]
+    InvalidEntropy {
+        /// The kind of the component.
+        kind: ComponentKind,
+
+        /// The index of the component within its kind.
+        index: usize,
+
+        /// The invalid entropy the component reports.
+        entropy: Entropy,
+    },
+
+    /// A word styler credits more entropy than it adds to some wordlist entries.
+    ///
+    /// Entropy is credited for every word, so it is overstated for passphrases containing such an
+    /// entry. For example, an interior capitalizer configured for a minimum word length longer
+    /// than the shortest word in the wordlist.
+    #[error("{kind} #{index} credits {entropy} per word, but only adds {actual} to {word:?}")]
+    OverstatedEntropy {
+        /// The kind of the styling component.
+        kind: ComponentKind,
+
+        /// The index of the component within its kind.
+        index: usize,
+
+        /// The entropy the component credits for each word.
+        entropy: Entropy,
+
+        /// The wordlist entry the component adds the least entropy to.
+        word: String,
+
+        /// The entropy the component adds to that entry.
+        actual: Entropy,
+    },
+
+    /// Two word slots of a template are adjacent without literal text in between, while some
+    /// wordlist entry is a prefix of another.
+    ///
+    /// Different words may then form the same passphrase, such as `for` and `tress` or `fort` and
+    /// `ress`. The entropy is overstated, and passphrases can't be split into their words. See
+    /// [`Template::validate`](crate::template::Template::validate).
+    #[error(
+        "word slots #{slot} and #{} are adjacent, but the wordlist isn't prefix-free",
+        slot + 1
+    )]
+    AdjacentWords {
+        /// The index of the first of the adjacent slots.
+        slot: usize,
+    },
+
+    /// A component produces characters outside the character set of the phrase builder.
+    ///
+    /// Characters in words are replaced, which may lower entropy. Characters added by phrase
+    /// stylers break the character set restriction.
+    #[error("{kind} #{index} produces {example:?}, which is not {charset}")]
+    OutsideCharset {
+        /// The kind of the component.
+        kind: ComponentKind,
+
+        /// The index of the component within its kind.
+        index: usize,
+
+        /// The character set passphrases are restricted to.
+        charset: Charset,
+
+        /// An example of a produced character outside the character set.
+        example: char,
+    },
+}
+
+/// A separator occurs inside wordlist entries.
+///
+/// When a separator occurs inside words, a passphrase can't be split into its words reliably,
+/// which breaks round-tripping and analysis. A list of safe separators not occurring in any word
+/// is suggested.
+#[derive(Error, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[error("separator {separator:?} occurs in {count} wordlist entries, such as {example:?}")]
+pub struct SeparatorError {
+    /// The colliding separator.
+    pub separator: String,
+
+    /// The number of wordlist entries containing the separator.
+    pub count: usize,
+
+    /// An example of a wordlist entry containing the separator.
+    pub example: String,
+
+    /// Suggested separators that don't occur in any wordlist entry.
+    pub suggestions: Vec<String>,
+}
+
+/// Check whether the given separator occurs inside any of the given words.
+///
+/// An empty separator is never considered to collide.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{validate::check_separator, word::BUILTIN_EFF_LARGE};
+///
+/// let words: Vec<&str> = BUILTIN_EFF_LARGE.lines().collect();
+///
+/// // The EFF large list contains hyphenated words such as `t-shirt`
+/// let err = check_separator("-", &words).unwrap_err();
+/// assert!(!err.suggestions.contains(&"-".into()));
+/// assert!(check_separator(" ", &words).is_ok());
+/// # }
+/// ```
+pub fn check_separator<S: AsRef<str>>(separator: &str, words: &[S]) -> Result<(), SeparatorError> {
+    if separator.is_empty() {
+        return Ok(());
+    }
+
+    let mut colliding = words
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|w| w.contains(separator));
+    let example = match colliding.next() {
+        Some(example) => example.to_owned(),
+        None => return Ok(()),
+    };
+
+    Err(SeparatorError {
+        separator: separator.into(),
+        count: colliding.count() + 1,
+        example,
+        suggestions: suggest_separators(words),
+    })
+}
+
+/// Suggest separators that don't occur inside any of the given words.
+pub fn suggest_separators<S: AsRef<str>>(words: &[S]) -> Vec<String> {
+    SEPARATOR_CANDIDATES
+        .iter()
+        .filter(|sep| !words.iter().any(|w| w.as_ref().contains(*sep)))
+        .map(|sep| (*sep).to_owned())
+        .collect()
+}
@@ -0,0 +1,128 @@
+//! Wordlist analysis
+//!
+//! This module provides [`WordListReport`](WordListReport), a summary of the properties of a
+//! [`WordList`](super::WordList) relevant to its security and usability. Use it to vet a custom
+//! wordlist before deploying it, see [`WordList::analyze`](super::WordList::analyze).
+//!
+//! To fix issues found in a wordlist, such as duplicates or prefix words, see the
+//! [`tidy`](super::tidy) module.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::entropy::Entropy;
+
+/// A report of the properties of a wordlist.
+///
+/// Word lengths are measured in characters.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::word::WordList;
+///
+/// let words = vec!["abacus", "abacus", "ab", "robust"];
+/// let report = WordList::try_new(words.into_iter().map(Into::into).collect()).unwrap().analyze();
+///
+/// assert_eq!(4, report.words);
+/// assert_eq!(3, report.distinct);
+/// assert_eq!(vec!["abacus"], report.duplicates);
+/// assert_eq!((2, 6), (report.min_length, report.max_length));
+/// assert!(!report.prefix_free);
+/// assert!(report.entropy < report.nominal_entropy);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WordListReport {
+    /// The number of words, including duplicates.
+    pub words: usize,
+
+    /// The number of distinct words.
+    pub distinct: usize,
+
+    /// Words occurring more than once, in order of their first repetition.
+    pub duplicates: Vec<String>,
+
+    /// The length of the shortest word.
+    pub min_length: usize,
+
+    /// The length of the longest word.
+    pub max_length: usize,
+
+    /// The average length of all words, including duplicates.
+    pub average_length: f64,
+
+    /// All distinct characters used in words, sorted.
+    pub characters: Vec<char>,
+
+    /// Whether no distinct word is a prefix of another word.
+    ///
+    /// Words of a prefix-free list may be concatenated without separator while still being
+    /// unambiguous.
+    pub prefix_free: bool,
+
+    /// The entropy per uniformly sampled word, `log2(words)`.
+    ///
+    /// This is the [entropy](crate::prelude::HasEntropy::entropy) reported for the wordlist.
+    pub nominal_entropy: Entropy,
+
+    /// The effective entropy per uniformly sampled word.
+    ///
+    /// This is the Shannon entropy over distinct words, which is lower than the nominal entropy
+    /// if the list contains duplicates, as duplicated words are sampled more often.
+    pub entropy: Entropy,
+}
+
+impl WordListReport {
+    /// Analyze the given words.
+    pub(super) fn new(words: &[String]) -> Self {
+        let mut counts: HashMap<&str, usize> = HashMap::with_capacity(words.len());
+        let mut duplicates = Vec::new();
+        for word in words {
+            let count = counts.entry(word).or_insert(0);
+            *count += 1;
+            if *count == 2 {
+                duplicates.push(word.clone());
+            }
+        }
+
+        let lengths: Vec<usize> = words.iter().map(|w| w.chars().count()).collect();
+        let characters: BTreeSet<char> = words.iter().flat_map(|w| w.chars()).collect();
+
+        // A word is a prefix of another word if it is a prefix of its sorted successor
+        let mut sorted: Vec<&str> = counts.keys().cloned().collect();
+        sorted.sort_unstable();
+        let prefix_free = !sorted.windows(2).any(|pair| pair[1].starts_with(pair[0]));
+
+        // Without duplicates words are sampled uniformly, avoid rounding errors of the sum
+        let total = words.len() as f64;
+        let nominal_entropy = Entropy::from_real(total);
+        let entropy = if counts.len() == words.len() {
+            nominal_entropy
+        } else {
+            Entropy::from_distribution(counts.values().map(|&c| c as f64 / total))
+        };
+
+        Self {
+            words: words.len(),
+            distinct: counts.len(),
+            duplicates,
+            min_length: lengths.iter().cloned().min().unwrap_or(0),
+            max_length: lengths.iter().cloned().max().unwrap_or(0),
+            average_length: lengths.iter().sum::<usize>() as f64 / total,
+            characters: characters.into_iter().collect(),
+            prefix_free,
+            nominal_entropy,
+            entropy,
+        }
+    }
+
+    /// Get the entropy lost through duplicates, per uniformly sampled word.
+    pub fn entropy_loss(&self) -> Entropy {
+        self.nominal_entropy - self.entropy
+    }
+
+    /// Check whether all words are distinct.
+    pub fn is_distinct(&self) -> bool {
+        self.duplicates.is_empty()
+    }
+}
@@ -0,0 +1,187 @@
+//! Compact probabilistic wordlist membership
+//!
+//! This module provides [`BloomFilter`](BloomFilter), a compact set of words that answers
+//! membership checks with a configurable false positive rate, but never with false negatives.
+//! A filter is much smaller than the wordlist it is built from, and can be
+//! [serialized](BloomFilter::to_bytes) to ship to memory-constrained validators, such as embedded
+//! or WASM targets, that can't carry the full list.
+//!
+//! The hash function and serialized format are fixed, so a filter built on one platform may be
+//! used on any other.
+
+use std::convert::TryInto;
+use std::f64::consts::LN_2;
+
+use thiserror::Error;
+
+/// Magic bytes at the start of a serialized filter.
+const MAGIC: &[u8; 4] = b"CHBF";
+
+/// The version of the serialized filter format.
+const VERSION: u8 = 1;
+
+/// The length of the serialized filter header.
+const HEADER_LEN: usize = 4 + 1 + 4 + 8;
+
+/// A Bloom filter over a set of words.
+///
+/// Build a filter from a wordlist using [`WordList::bloom_filter`](super::WordList::bloom_filter)
+/// or [`from_words`](BloomFilter::from_words).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::word::{bloom::BloomFilter, WordList};
+///
+/// let filter = WordList::builtin_eff_large().bloom_filter(0.001);
+/// assert!(filter.contains_maybe("abacus"));
+///
+/// // Serialize the filter, and load it elsewhere
+/// let bytes = filter.to_bytes();
+/// let filter = BloomFilter::from_bytes(&bytes).unwrap();
+/// assert!(filter.contains_maybe("zoom"));
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BloomFilter {
+    /// The filter bits.
+    bits: Vec<u64>,
+
+    /// The number of filter bits, a multiple of 64.
+    len: u64,
+
+    /// The number of hashes for each word.
+    hashes: u32,
+}
+
+impl BloomFilter {
+    /// Build a filter over the given words, with the given false positive rate.
+    ///
+    /// The filter is sized to the smallest number of bits meeting the `false_positive_rate` for
+    /// the number of words.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not between `0` and `1`, exclusive.
+    pub fn from_words<S: AsRef<str>>(words: &[S], false_positive_rate: f64) -> Self {
+        if !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            panic!("bloom filter false positive rate must be between 0 and 1");
+        }
+
+        let n = words.len().max(1) as f64;
+        // Round the number of bits up to whole blocks, which only lowers the false positive rate
+        let blocks = (-n * false_positive_rate.ln() / (LN_2 * LN_2) / 64.0)
+            .ceil()
+            .max(1.0) as usize;
+        let len = blocks as u64 * 64;
+        let hashes = ((len as f64 / n) * LN_2).round().max(1.0) as u32;
+
+        let mut filter = Self {
+            bits: vec![0; blocks],
+            len,
+            hashes,
+        };
+        for word in words {
+            filter.insert(word.as_ref());
+        }
+        filter
+    }
+
+    /// Add a word to this filter.
+    fn insert(&mut self, word: &str) {
+        for bit in self.bit_indices(word) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Check whether the given word may be in this filter.
+    ///
+    /// If this returns `false`, the word is definitely not in the set of words the filter was
+    /// built from. If this returns `true`, it most likely is, but may be a false positive.
+    pub fn contains_maybe(&self, word: &str) -> bool {
+        self.bit_indices(word)
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Get the bit indices for the given word, using double hashing.
+    fn bit_indices(&self, word: &str) -> impl Iterator<Item = u64> {
+        let hash = fnv1a(word.as_bytes());
+        let h1 = mix(hash);
+        let h2 = mix(hash ^ 0x9e37_79b9_7f4a_7c15) | 1;
+        let len = self.len;
+        (0..self.hashes as u64).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % len)
+    }
+
+    /// Serialize this filter into bytes.
+    ///
+    /// Use [`from_bytes`](BloomFilter::from_bytes) to load it again.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.bits.len() * 8);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.hashes.to_le_bytes());
+        bytes.extend_from_slice(&self.len.to_le_bytes());
+        for block in &self.bits {
+            bytes.extend_from_slice(&block.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Load a filter serialized with [`to_bytes`](BloomFilter::to_bytes).
+    ///
+    /// An error is returned if the bytes are not a valid serialized filter.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BloomFilterError> {
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+            return Err(BloomFilterError::Invalid);
+        }
+        if bytes[4] != VERSION {
+            return Err(BloomFilterError::UnsupportedVersion(bytes[4]));
+        }
+
+        let hashes = u32::from_le_bytes(bytes[5..9].try_into().unwrap());
+        let len = u64::from_le_bytes(bytes[9..17].try_into().unwrap());
+        let body = &bytes[HEADER_LEN..];
+        if hashes == 0 || len == 0 || len % 64 != 0 || body.len() as u64 * 8 != len {
+            return Err(BloomFilterError::Invalid);
+        }
+
+        let bits = body
+            .chunks(8)
+            .map(|block| u64::from_le_bytes(block.try_into().unwrap()))
+            .collect();
+        Ok(Self { bits, len, hashes })
+    }
+
+    /// Get the size of this filter in bytes, excluding the serialized header.
+    pub fn size(&self) -> usize {
+        self.bits.len() * 8
+    }
+}
+
+/// Hash the given bytes with 64-bit FNV-1a.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Mix the bits of the given hash, using the SplitMix64 finalizer.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// A [`BloomFilter`](BloomFilter) error.
+#[derive(Debug, Error)]
+pub enum BloomFilterError {
+    /// The bytes are not a valid serialized filter.
+    #[error("invalid serialized bloom filter")]
+    Invalid,
+
+    /// The serialized filter has a format version that is not supported.
+    #[error("unsupported bloom filter format version {0}")]
+    UnsupportedVersion(u8),
+}
@@ -0,0 +1,150 @@
+//! Wordlist building from a text corpus
+//!
+//! This module provides [`CorpusConfig`](CorpusConfig) to build a [`WordList`](super::WordList)
+//! from an arbitrary text corpus. This allows generating domain or language specific wordlists
+//! entirely within this crate.
+//!
+//! The corpus is tokenized into words, which are lowercased and ranked by frequency. Words not
+//! matching the configured length and character filters are discarded, and the most frequent
+//! unique words are kept.
+
+use std::collections::HashMap;
+
+use super::{WordList, WordListError};
+
+/// The default number of words to keep in a corpus based wordlist.
+const DEFAULT_WORDS: usize = 7776;
+
+/// A configuration for building a wordlist from a text corpus.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::word::corpus::CorpusConfig;
+///
+/// let corpus = "The quick brown fox jumps over the lazy dog. The dog sleeps.";
+///
+/// let config = CorpusConfig::default().with_words(3).with_min_length(3);
+///
+/// let ranked: Vec<String> = config.rank(corpus).into_iter().map(|(w, _)| w).collect();
+/// assert_eq!(vec!["the", "dog", "brown"], ranked[..3].to_vec());
+///
+/// let wordlist = config.to_wordlist(corpus).unwrap();
+/// ```
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into))]
+#[non_exhaustive]
+pub struct CorpusConfig {
+    /// The maximum number of words to keep, the most frequent words are kept.
+    pub words: usize,
+
+    /// The minimum number of characters a word must have.
+    pub min_length: usize,
+
+    /// The maximum number of characters a word may have.
+    pub max_length: usize,
+
+    /// The minimum number of times a word must occur in the corpus.
+    pub min_occurrences: usize,
+
+    /// Whether to only keep words consisting of ASCII characters.
+    pub ascii_only: bool,
+}
+
+impl CorpusConfig {
+    /// Set the maximum number of words to keep.
+    pub fn with_words(mut self, words: usize) -> Self {
+        self.words = words;
+        self
+    }
+
+    /// Set the minimum number of characters a word must have.
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Set the maximum number of characters a word may have.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Set the minimum number of times a word must occur in the corpus.
+    pub fn with_min_occurrences(mut self, min_occurrences: usize) -> Self {
+        self.min_occurrences = min_occurrences;
+        self
+    }
+
+    /// Set whether to only keep words consisting of ASCII characters.
+    pub fn with_ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Rank the words in the given corpus by frequency.
+    ///
+    /// This tokenizes the corpus on any non-alphabetic character and lowercases each word. Words
+    /// not matching the configured filters are discarded. The remaining unique words are returned
+    /// along with their number of occurrences, most frequent first. Words occurring equally often
+    /// are sorted alphabetically.
+    ///
+    /// All ranked words are returned, the configured number of [`words`](CorpusConfig::words) is
+    /// not applied.
+    pub fn rank(&self, corpus: &str) -> Vec<(String, usize)> {
+        // Count all words matching the filters
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        corpus
+            .split(|c: char| !c.is_alphabetic())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .filter(|w| self.matches(w))
+            .for_each(|w| *counts.entry(w).or_insert(0) += 1);
+
+        let mut ranked: Vec<(String, usize)> = counts
+            .into_iter()
+            .filter(|(_, count)| *count >= self.min_occurrences)
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Build a wordlist from the given corpus.
+    ///
+    /// This keeps the most frequent words in the corpus, see [`rank`](CorpusConfig::rank). The
+    /// wordlist may contain fewer than the configured number of words if the corpus doesn't
+    /// contain enough unique words.
+    ///
+    /// An error is returned if fewer than two words were left.
+    pub fn to_wordlist(&self, corpus: &str) -> Result<WordList, WordListError> {
+        let words: Vec<String> = self
+            .rank(corpus)
+            .into_iter()
+            .take(self.words)
+            .map(|(word, _)| word)
+            .collect();
+
+        WordList::try_new(words)
+    }
+
+    /// Check whether the given word matches the length and character filters.
+    fn matches(&self, word: &str) -> bool {
+        let len = word.chars().count();
+        len >= self.min_length && len <= self.max_length && (!self.ascii_only || word.is_ascii())
+    }
+}
+
+impl Default for CorpusConfig {
+    /// Build a default corpus configuration.
+    ///
+    /// This keeps the 7776 (6<sup>5</sup>) most frequent ASCII words with 3 to 9 characters.
+    fn default() -> CorpusConfig {
+        CorpusConfig {
+            words: DEFAULT_WORDS,
+            min_length: 3,
+            max_length: 9,
+            min_occurrences: 1,
+            ascii_only: true,
+        }
+    }
+}
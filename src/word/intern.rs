@@ -0,0 +1,60 @@
+//! Interning of identical wordlists
+//!
+//! Every [`WordList`](super::WordList) holds its own set of words. When many schemes are built
+//! from the same file or built-in list, this may keep many copies of thousands of words in
+//! memory. Interning a wordlist using [`WordList::intern`](super::WordList::intern) looks it up
+//! in a process-wide registry keyed by a hash of its contents, and shares the words of an
+//! identical list that was interned before. Samplers built from interned lists share the same
+//! words as well.
+//!
+//! Interning is opt-in. The registry only holds weak references, so interned words are freed
+//! once no list or sampler uses them anymore.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, Weak};
+
+use once_cell::sync::Lazy;
+
+/// Interned word sets, keyed by content hash.
+type Registry = HashMap<u64, Vec<Weak<Vec<String>>>>;
+
+/// The process-wide registry of interned word sets.
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Intern the given set of words.
+///
+/// Returns the words of an identical set interned before if still in use, or registers the given
+/// set otherwise.
+pub(super) fn intern(words: Arc<Vec<String>>) -> Arc<Vec<String>> {
+    let mut hasher = DefaultHasher::new();
+    words.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let mut registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+    let entries = registry.entry(hash).or_default();
+    entries.retain(|entry| entry.strong_count() > 0);
+
+    // Content hashes may collide, compare the words to find an identical set
+    if let Some(interned) = entries
+        .iter()
+        .filter_map(Weak::upgrade)
+        .find(|interned| interned == &words)
+    {
+        return interned;
+    }
+
+    entries.push(Arc::downgrade(&words));
+    words
+}
+
+/// Get the number of distinct word sets currently interned and in use.
+pub fn interned_count() -> usize {
+    let registry = REGISTRY.lock().unwrap_or_else(|err| err.into_inner());
+    registry
+        .values()
+        .flatten()
+        .filter(|entry| entry.strong_count() > 0)
+        .count()
+}
@@ -0,0 +1,2007 @@
+//! Utilities for collecting and generating words for in a passphrase
+//!
+//! This module provides various constructs for collecting and/or generating words to use in a
+//! passphrase.
+//!
+//! The [`WordList`](WordList) structure is used for static wordlists, which may be uniformly
+//! sampled using a [`WordSampler`](WordSampler).
+//!
+//! Constants holding a static built-in wordlist are included so providing your own wordlist is not
+//! required, see the [`BUILTIN_`](#constants) constants.
+//! These lists can easily be loaded using the [`buildin_`](WordList) methods on
+//! [`WordList`](WordList).
+//!
+//! For the built-in wordlists a [`StaticWordList`](StaticWordList) is available as well, which
+//! borrows the words from static memory. It doesn't require any parsing on construction nor any
+//! allocation when sampling words by reference.
+//!
+//! # Other wordlists
+//!
+//! Only wordlists of which the exact contents are vetted in this crate are built in. Other
+//! published lists can be loaded from their original files instead:
+//!
+//! - The EFF fandom wordlists (Star Wars, Star Trek, Harry Potter and Game of Thrones) are
+//!   distributed in the diced format, load them with [`load_diced`](WordList::load_diced).
+//! - The original diceware wordlist by Arnold Reinhold has 7776 words for five dice, providing
+//!   about 12.9 bits of entropy per word. Its published file is PGP signed, strip the signature
+//!   before loading it with [`load_diced`](WordList::load_diced).
+//! - The Beale variant of the diceware wordlist, avoiding americanisms and some offensive words,
+//!   is loaded the same way.
+//! - The diceware translations, such as the German, French, Spanish, Italian and Dutch lists, are
+//!   loaded the same way. They contain non-ASCII words, use a
+//!   [`CharsetPhraseBuilder`](crate::component::phrase::CharsetPhraseBuilder) to keep passphrases
+//!   within a character set accepted by the target system.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use std::fs::{read_to_string, File};
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use std::io::BufWriter;
+use std::io::{self, Read, Write};
+use std::ops::RangeInclusive;
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use std::path::Path;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use rand::{distributions::Uniform, prelude::*};
+use thiserror::Error;
+
+use crate::entropy::Entropy;
+use crate::prelude::*;
+use crate::rng::{default_rng, gen_index, uniform_indices};
+
+pub mod analysis;
+pub mod bloom;
+pub mod corpus;
+pub mod intern;
+pub mod provider;
+pub mod source;
+#[cfg(feature = "structured-formats")]
+pub mod structured;
+#[cfg(feature = "system-dict")]
+pub mod system;
+pub mod theme;
+pub mod tidy;
+pub mod weighted;
+
+/// Static word arrays for the built-in wordlists, generated by the build script.
+mod builtin {
+    include!(concat!(env!("OUT_DIR"), "/builtin.rs"));
+}
+
+/// The built-in EFF large wordlist words.
+///
+/// Construct a [`WordList`](WordList) from this list using
+/// [`WordList::builtin_eff_large()`](WordList::builtin_eff_large).
+///
+/// This wordlist contains 7776 (6<sup>5</sup>) words,
+/// and has an entropy of about 12.9 bits when uniformly sampling words from it.
+///
+/// The list is slightly modified to discard the dice numbers,
+/// [source](https://www.eff.org/deeplinks/2016/07/new-wordlists-random-passphrases).
+///
+/// Only available with the `wordlist-eff-large` feature, enabled by default.
+#[cfg(feature = "wordlist-eff-large")]
+pub const BUILTIN_EFF_LARGE: &str = include_str!("../../res/eff/large.txt");
+
+/// The built-in EFF short wordlist words.
+///
+/// Construct a [`WordList`](WordList) from this list using
+/// [`WordList::builtin_eff_short()`](WordList::builtin_eff_short).
+///
+/// **Note:** this wordlist is considered short, as it only contains 1296 (6<sup>4</sup>) words.
+/// The list has an entropy of about 10.3 bits when uniformly sampling words from it.  
+/// It is recommended to use a larger wordlist such as [`BUILTIN_EFF_LARGE`](BUILTIN_EFF_LARGE).
+///
+/// The list is slightly modified to discard the dice numbers,
+/// [source](https://www.eff.org/deeplinks/2016/07/new-wordlists-random-passphrases).
+///
+/// Only available with the `wordlist-eff-short` feature, enabled by default.
+#[cfg(feature = "wordlist-eff-short")]
+pub const BUILTIN_EFF_SHORT: &str = include_str!("../../res/eff/short.txt");
+
+/// The built-in EFF general short wordlist words.
+///
+/// Construct a [`WordList`](WordList) from this list using
+/// [`WordList::builtin_eff_general_short()`](WordList::builtin_eff_general_short).
+///
+/// **Note:** this wordlist is considered short, as it only contains 1296 (6<sup>4</sup>) words.
+/// The list has an entropy of about 10.3 bits when uniformly sampling words from it.  
+/// It is recommended to use a larger wordlist such as [`BUILTIN_EFF_LARGE`](BUILTIN_EFF_LARGE).
+///
+/// The list is slightly modified to discard the dice numbers,
+/// [source](https://www.eff.org/deeplinks/2016/07/new-wordlists-random-passphrases).
+///
+/// Only available with the `wordlist-eff-general-short` feature, enabled by default.
+#[cfg(feature = "wordlist-eff-general-short")]
+pub const BUILTIN_EFF_GENERAL_SHORT: &str = include_str!("../../res/eff/general_short.txt");
+
+/// The built-in BIP39 English wordlist words.
+///
+/// Construct a [`WordList`](WordList) from this list using
+/// [`WordList::builtin_bip39_english()`](WordList::builtin_bip39_english).
+///
+/// This is the wordlist of cryptocurrency seed phrases, used by the `bip39` module, available with
+/// the `bip39` feature. It contains 2048 (2<sup>11</sup>) words, and has an entropy of exactly 11
+/// bits when uniformly sampling words from it. Each word is uniquely identified by its first four
+/// letters.
+///
+/// [source](https://github.com/bitcoin/bips/blob/master/bip-0039/english.txt).
+pub const BUILTIN_BIP39_ENGLISH: &str = include_str!("../../res/bip39/english.txt");
+
+/// The built-in PGP word list even words.
+///
+/// Construct a [`WordList`](WordList) from this list using
+/// [`WordList::builtin_pgp_even()`](WordList::builtin_pgp_even).
+///
+/// The PGP word list encodes bytes to read key fingerprints aloud, alternating between the two
+/// syllable even words and the three syllable [odd](BUILTIN_PGP_ODD) words by byte position, see
+/// [`encode_pgp`](crate::encode::encode_pgp). It contains 256 (2<sup>8</sup>) words, one for each
+/// byte value.
+///
+/// [source](https://en.wikipedia.org/wiki/PGP_word_list).
+pub const BUILTIN_PGP_EVEN: &str = include_str!("../../res/pgp/even.txt");
+
+/// The built-in PGP word list odd words.
+///
+/// Construct a [`WordList`](WordList) from this list using
+/// [`WordList::builtin_pgp_odd()`](WordList::builtin_pgp_odd).
+///
+/// These are the three syllable words of the PGP word list, used for bytes at odd positions. See
+/// [`BUILTIN_PGP_EVEN`](BUILTIN_PGP_EVEN).
+///
+/// [source](https://en.wikipedia.org/wiki/PGP_word_list).
+pub const BUILTIN_PGP_ODD: &str = include_str!("../../res/pgp/odd.txt");
+
+/// The built-in wordlists, parsed once on first use.
+///
+/// Built-in wordlist constructors clone these, which only clones a reference to the shared words.
+#[cfg(feature = "wordlist-eff-large")]
+static CACHED_EFF_LARGE: Lazy<WordList> = Lazy::new(|| WordList::parse_builtin(BUILTIN_EFF_LARGE));
+#[cfg(feature = "wordlist-eff-short")]
+static CACHED_EFF_SHORT: Lazy<WordList> = Lazy::new(|| WordList::parse_builtin(BUILTIN_EFF_SHORT));
+#[cfg(feature = "wordlist-eff-general-short")]
+static CACHED_EFF_GENERAL_SHORT: Lazy<WordList> =
+    Lazy::new(|| WordList::parse_builtin(BUILTIN_EFF_GENERAL_SHORT));
+static CACHED_BIP39_ENGLISH: Lazy<WordList> =
+    Lazy::new(|| WordList::parse_builtin(BUILTIN_BIP39_ENGLISH));
+static CACHED_PGP_EVEN: Lazy<WordList> = Lazy::new(|| WordList::parse_builtin(BUILTIN_PGP_EVEN));
+static CACHED_PGP_ODD: Lazy<WordList> = Lazy::new(|| WordList::parse_builtin(BUILTIN_PGP_ODD));
+
+/// A wordlist.
+///
+/// To load a built-in wordlist, checkout the methods on this struct prefixed with `builtin_`.  
+/// The default wordlist loaded when using `default()` uses
+/// [`builtin_eff_large()`](WordList::builtin_eff_large). Built-in wordlists are parsed once per
+/// process, constructing them again is cheap as their words are shared.
+///
+/// A loaded fixed wordlist which may be used as word provider for passphrase generation by
+/// constructing a sampler using [`sampler`](WordList::sampler).
+///
+/// It is highly recommended that the worlist contains at least 7776 (6<sup>5</sup>) words to
+/// provide enough entropy when uniformly sampling words from it.
+#[derive(Clone, Debug)]
+pub struct WordList {
+    /// A fixed set of words, shared with samplers built from this list.
+    words: Arc<Vec<String>>,
+}
+
+impl WordList {
+    /// Construct a new word list with the given words.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the given set of words is empty.
+    #[deprecated(note = "use `try_new`, which returns an error instead of panicking")]
+    pub fn new(words: Vec<String>) -> Self {
+        if words.is_empty() {
+            panic!("cannot construct wordlist, given list of words is empty");
+        }
+
+        Self::from_words(words)
+    }
+
+    /// Construct a new word list with the given words.
+    ///
+    /// To load a wordlist from a file, use [`load`](WordList::load) instead.  
+    /// To load a built-in wordlist, use the methods on this struct prefixed with `builtin_`.
+    ///
+    /// An error is returned if the given set of words is empty, or if it has a single word.
+    /// Passphrases from a single word list are fully predictable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::{WordList, WordListError};
+    ///
+    /// let list = WordList::try_new(vec!["correct".into(), "horse".into()]).unwrap();
+    /// assert_eq!(2, list.words().len());
+    ///
+    /// assert!(matches!(WordList::try_new(vec![]), Err(WordListError::Empty)));
+    /// assert!(matches!(
+    ///     WordList::try_new(vec!["horse".into()]),
+    ///     Err(WordListError::SingleWord),
+    /// ));
+    /// ```
+    pub fn try_new(words: Vec<String>) -> Result<Self, WordListError> {
+        Self::check_len(words.len())?;
+        Ok(Self::from_words(words))
+    }
+
+    /// Check whether a wordlist with `len` words is valid, see [`try_new`](WordList::try_new).
+    fn check_len(len: usize) -> Result<(), WordListError> {
+        match len {
+            0 => Err(WordListError::Empty),
+            1 => Err(WordListError::SingleWord),
+            _ => Ok(()),
+        }
+    }
+
+    /// Construct a word list with the given words, without validating them.
+    ///
+    /// Callers must ensure there are at least two words, see [`try_new`](WordList::try_new).
+    fn from_words(words: Vec<String>) -> Self {
+        WordList {
+            words: Arc::new(words),
+        }
+    }
+
+    /// Load a wordlist from a file.
+    ///
+    /// This loads a wordlist from a file at the given path, and constructs a `WordList`.
+    ///
+    /// - Words are splitted by any whitespace (including newlines),
+    ///   see `char::is_whitespace`
+    /// - It assumes any non-whitespace character is part of a word
+    /// - Whitespaces are omitted the final wordlist
+    /// - Emtpy items are omitted
+    /// - Comment lines are omitted, see [below](#comments)
+    /// - The file must not include dice numbers
+    ///
+    /// For wordlists that include dice numbers, the [`load_diced`](WordList::load_diced) method
+    /// may be used instead.  
+    /// If words are separated in a different manner, manually load each word and use the
+    /// [`try_new`](WordList::try_new) constructor instead.
+    ///
+    /// An error is returned if loading the wordlist failed, or if the loaded file didn't contain
+    /// at least two words.
+    ///
+    /// Not available with the `wasm` feature on `wasm32` targets, which have no file system. Use
+    /// [`load_from`](WordList::load_from) with a [`MemorySource`](source::MemorySource) or
+    /// [`StaticSource`](source::StaticSource) instead.
+    ///
+    /// # File examples
+    /// ```txt
+    /// abacus abdomen abdominal abide abiding
+    /// ```
+    /// or
+    /// ```txt
+    /// abacus
+    /// abdomen
+    /// ```
+    ///
+    /// # Comments
+    ///
+    /// Lines starting with `#` are comments, and are skipped. A `#` directly followed by a digit
+    /// starts a numbered entry instead, such as `#1 (1,1,1,1,1) abacus`. A leading UTF-8 byte
+    /// order mark is stripped, and both `\n` and `\r\n` line endings are supported. This applies
+    /// to all loaders.
+    ///
+    /// ```txt
+    /// # EFF large wordlist
+    /// abacus
+    /// abdomen
+    /// ```
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    pub fn load<P>(path: P) -> Result<Self, WordListError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(&read_to_string(path)?, WordListFormat::Plain)
+    }
+
+    /// Load a diced wordlist from a file.
+    ///
+    /// This loads a diced wordlist from a file at the given path, and constructs a `WordList`.
+    /// Many diceware wordlists include dice numbers for each word, these should be omitted when
+    /// using this crate. This method helps with that.
+    ///
+    /// - Words are splitted by the newline character (`\n`).
+    /// - Only the last word on each line is kept, terminated by any whitespace such as spaces or
+    ///   tabs, see `char::is_whitespace`
+    /// - It assumes any non-whitespace character is part of a word
+    /// - Prefixed words do not have to be dice numbers
+    /// - Lines having a single word with no dice number prefix are included
+    /// - Emtpy lines and [comments](WordList::load#comments) are omitted
+    ///
+    /// For wordlists that do not include dice numbers, the the regular [`load`](WordList::load)
+    /// method instead.  
+    /// If words are separated in a different manner, manually load each word and use the
+    /// [`try_new`](WordList::try_new) constructor instead.
+    ///
+    /// An error is returned if loading the wordlist failed, or if the loaded file didn't contain
+    /// at least two words.
+    ///
+    /// # File examples
+    /// ```txt
+    /// 11111 abacus
+    /// 11112 abdomen
+    /// 11113 abdominal
+    /// ```
+    /// or
+    /// ```txt
+    /// #1 (1,1,1,1,2)    abacus
+    /// #2 (1,1,1,1,2)    abdomen
+    /// ```
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    pub fn load_diced<P>(path: P) -> Result<Self, WordListError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::parse(&read_to_string(path)?, WordListFormat::Diced)
+    }
+
+    /// Load a wordlist from a file, detecting whether it is diced.
+    ///
+    /// This inspects the file at the given path and chooses between the parsing strategies of
+    /// [`load`](WordList::load) and [`load_diced`](WordList::load_diced). Wordlists are
+    /// considered diced if every line has a word prefixed with a dice number or index, and plain
+    /// if no line contains any digits.
+    ///
+    /// An error is returned if loading the wordlist failed, if the loaded file didn't contain any
+    /// words, or if the format is ambiguous. For example, a file with only some lines prefixed
+    /// with dice numbers is ambiguous. Use the specific loader in that case.
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    pub fn load_auto<P>(path: P) -> Result<Self, WordListError>
+    where
+        P: AsRef<Path>,
+    {
+        let contents = read_to_string(path)?;
+        Self::parse(&contents, WordListFormat::detect(&contents)?)
+    }
+
+    /// Load a wordlist with the given name from a [`WordSource`](source::WordSource).
+    ///
+    /// The file is parsed like [`load`](WordList::load). See the [`source`](source) module for
+    /// available sources.
+    ///
+    /// An error is returned if reading the wordlist failed, or if it didn't contain any words.
+    pub fn load_from<S>(source: &S, name: &str) -> Result<Self, WordListError>
+    where
+        S: source::WordSource + ?Sized,
+    {
+        Self::parse(
+            &source::read_to_string(source, name)?,
+            WordListFormat::Plain,
+        )
+    }
+
+    /// Load a diced wordlist with the given name from a [`WordSource`](source::WordSource).
+    ///
+    /// The file is parsed like [`load_diced`](WordList::load_diced).
+    pub fn load_diced_from<S>(source: &S, name: &str) -> Result<Self, WordListError>
+    where
+        S: source::WordSource + ?Sized,
+    {
+        Self::parse(
+            &source::read_to_string(source, name)?,
+            WordListFormat::Diced,
+        )
+    }
+
+    /// Load a wordlist with the given name from a [`WordSource`](source::WordSource), detecting
+    /// whether it is diced.
+    ///
+    /// The file is parsed like [`load_auto`](WordList::load_auto).
+    pub fn load_auto_from<S>(source: &S, name: &str) -> Result<Self, WordListError>
+    where
+        S: source::WordSource + ?Sized,
+    {
+        let contents = source::read_to_string(source, name)?;
+        Self::parse(&contents, WordListFormat::detect(&contents)?)
+    }
+
+    /// Read a wordlist from the given reader.
+    ///
+    /// The contents are parsed like [`load`](WordList::load). This reads wordlists from any
+    /// source without touching the filesystem, such as embedded assets, network streams,
+    /// decompressed archives or standard input.
+    ///
+    /// An error is returned if reading failed, if the contents aren't valid UTF-8, or if they
+    /// didn't contain any words.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::WordList;
+    ///
+    /// let list = WordList::from_reader(&b"abacus abdomen\nabdominal\n"[..]).unwrap();
+    /// assert_eq!(&["abacus", "abdomen", "abdominal"], list.words());
+    ///
+    /// let list = WordList::from_reader_diced(&b"11111 abacus\n11112 abdomen\n"[..]).unwrap();
+    /// assert_eq!(&["abacus", "abdomen"], list.words());
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, WordListError> {
+        Self::parse(&read_all(reader)?, WordListFormat::Plain)
+    }
+
+    /// Read a diced wordlist from the given reader.
+    ///
+    /// The contents are parsed like [`load_diced`](WordList::load_diced), see
+    /// [`from_reader`](WordList::from_reader).
+    pub fn from_reader_diced<R: Read>(reader: R) -> Result<Self, WordListError> {
+        Self::parse(&read_all(reader)?, WordListFormat::Diced)
+    }
+
+    /// Read a wordlist from the given reader, detecting whether it is diced.
+    ///
+    /// The contents are parsed like [`load_auto`](WordList::load_auto), see
+    /// [`from_reader`](WordList::from_reader).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::{WordList, WordListError};
+    ///
+    /// let list = WordList::from_reader_auto(&b"11111 abacus\n11112 abdomen\n"[..]).unwrap();
+    /// assert_eq!(&["abacus", "abdomen"], list.words());
+    ///
+    /// let mixed = WordList::from_reader_auto(&b"11111 abacus\nabdomen\n"[..]);
+    /// assert!(matches!(mixed, Err(WordListError::AmbiguousFormat(_))));
+    /// ```
+    pub fn from_reader_auto<R: Read>(reader: R) -> Result<Self, WordListError> {
+        let contents = read_all(reader)?;
+        Self::parse(&contents, WordListFormat::detect(&contents)?)
+    }
+
+    /// Load a wordlist from a file, verifying its SHA-256 checksum.
+    ///
+    /// This hashes the contents of the file at the given path, and refuses to load it if the
+    /// hash doesn't match `expected_sha256`, given as hexadecimal string. This protects against
+    /// tampered or truncated wordlist files silently weakening the entropy of passphrases. The
+    /// file is parsed like [`load`](WordList::load).
+    ///
+    /// An error is returned if loading the wordlist failed, if the checksum doesn't match, or if
+    /// the loaded file didn't contain any words.
+    ///
+    /// Only available with the `verify` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::WordList;
+    ///
+    /// let path = std::env::temp_dir().join("chbs-verified-wordlist.txt");
+    /// std::fs::write(&path, "abacus\nabdomen\n").unwrap();
+    ///
+    /// let checksum = "6fe6450d51a2df6a57e5687f37d8d220d757ad54423a1f9421036368a171795c";
+    /// let list = WordList::load_verified(&path, checksum).unwrap();
+    /// assert_eq!(2, list.words().len());
+    ///
+    /// std::fs::write(&path, "abacus\n").unwrap();
+    /// assert!(WordList::load_verified(&path, checksum).is_err());
+    /// ```
+    #[cfg(all(feature = "verify", not(all(feature = "wasm", target_arch = "wasm32"))))]
+    pub fn load_verified<P>(path: P, expected_sha256: &str) -> Result<Self, WordListError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_verified(&std::fs::read(path)?, expected_sha256)
+    }
+
+    /// Parse a wordlist from the given contents, verifying their SHA-256 checksum.
+    ///
+    /// This is [`load_verified`](WordList::load_verified) for contents obtained elsewhere, such as
+    /// a wordlist fetched remotely. This crate doesn't fetch wordlists itself, download them with
+    /// any HTTP client and verify the response body with this before use.
+    ///
+    /// An error is returned if the checksum doesn't match, if the contents aren't valid UTF-8, or
+    /// if they didn't contain any words.
+    ///
+    /// Only available with the `verify` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::WordList;
+    ///
+    /// let checksum = "6fe6450d51a2df6a57e5687f37d8d220d757ad54423a1f9421036368a171795c";
+    /// let list = WordList::from_verified(b"abacus\nabdomen\n", checksum).unwrap();
+    /// assert_eq!(2, list.words().len());
+    /// assert!(WordList::from_verified(b"abacus\n", checksum).is_err());
+    /// ```
+    #[cfg(feature = "verify")]
+    pub fn from_verified(contents: &[u8], expected_sha256: &str) -> Result<Self, WordListError> {
+        use sha2::{Digest, Sha256};
+
+        let actual = format!("{:x}", Sha256::digest(contents));
+        if !actual.eq_ignore_ascii_case(expected_sha256.trim()) {
+            return Err(WordListError::ChecksumMismatch {
+                expected: expected_sha256.trim().to_lowercase(),
+                actual,
+            });
+        }
+
+        let contents = std::str::from_utf8(contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Self::parse(contents, WordListFormat::Plain)
+    }
+
+    /// Load a wordlist from a file with an explicit entry delimiter.
+    ///
+    /// See [`from_delimited`](WordList::from_delimited). Use a newline (`"\n"`) delimiter to load
+    /// a wordlist with one entry per line, such as one [saved](WordList::save) in the
+    /// [`Plain`](WordListFormat::Plain) format that contains multi-word entries.
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    pub fn load_delimited<P>(path: P, delimiter: &str) -> Result<Self, WordListError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_delimited(&read_to_string(path)?, delimiter)
+    }
+
+    /// Parse a wordlist from the given contents with an explicit entry delimiter.
+    ///
+    /// Unlike [`load`](WordList::load), entries are only split on the given delimiter, so
+    /// multi-word entries such as `ice cream` are kept intact as a single unit.
+    ///
+    /// - Entries are splitted by `delimiter`
+    /// - Leading and trailing whitespace of each entry is trimmed, including `\r` of Windows
+    ///   line endings
+    /// - Empty entries and [comments](WordList::load#comments) are omitted
+    ///
+    /// When using a wordlist with multi-word entries, the passphrase separator must not occur
+    /// inside entries, or word boundaries become ambiguous and entropy is lost. The default space
+    /// separator is therefore unsuitable. Such collisions are reported by
+    /// [`Scheme::validate`](crate::scheme::Scheme::validate).
+    ///
+    /// An error is returned if no entries were found.
+    ///
+    /// # Panics
+    ///
+    /// The `delimiter` must not be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::{config::BasicConfig, word::WordList};
+    ///
+    /// let list = WordList::from_delimited("ice cream\nhot dog\r\napple pie\n", "\n").unwrap();
+    /// assert_eq!(&["ice cream", "hot dog", "apple pie"], list.words());
+    ///
+    /// // The default space separator collides with spaces inside entries
+    /// let config = BasicConfig::new(list.sampler());
+    /// assert_eq!(1, config.validate().len());
+    /// assert!(config.with_separator("-").validate().is_empty());
+    /// ```
+    pub fn from_delimited(contents: &str, delimiter: &str) -> Result<Self, WordListError> {
+        if delimiter.is_empty() {
+            panic!("cannot parse wordlist, given entry delimiter is empty");
+        }
+
+        let words: Vec<String> = strip_bom(contents)
+            .split(delimiter)
+            .map(str::trim)
+            .filter(|w| !w.is_empty() && !is_comment(w))
+            .map(|w| w.to_owned())
+            .collect();
+
+        Self::try_new(words)
+    }
+
+    /// Parse a wordlist from the given contents in the given format.
+    ///
+    /// The [`Plain`](WordListFormat::Plain) format is parsed as described in
+    /// [`load`](WordList::load), the other formats as described in
+    /// [`load_diced`](WordList::load_diced). An error is returned if no words were found.
+    fn parse(contents: &str, format: WordListFormat) -> Result<Self, WordListError> {
+        // Load all words, error if empty
+        let words: Vec<String> = match format {
+            WordListFormat::Plain => content_lines(contents)
+                .flat_map(str::split_whitespace)
+                .map(|w| w.to_owned())
+                .collect(),
+            WordListFormat::Diced | WordListFormat::Numbered => content_lines(contents)
+                .filter_map(|w| w.split_whitespace().last())
+                .map(|w| w.to_owned())
+                .collect(),
+        };
+
+        Self::try_new(words)
+    }
+
+    /// The names of all built-in wordlists, see [`builtin`](WordList::builtin).
+    pub const BUILTIN_NAMES: [&'static str; 6] = [
+        "eff_large",
+        "eff_short",
+        "eff_general_short",
+        "bip39_english",
+        "pgp_even",
+        "pgp_odd",
+    ];
+
+    /// Construct a built-in wordlist by name, such as `eff_large`.
+    ///
+    /// Names are matched case-insensitively. Returns `None` if there is no built-in wordlist with
+    /// the given name, or if its feature is disabled. See
+    /// [`BUILTIN_NAMES`](WordList::BUILTIN_NAMES) for all available names.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "wordlist-eff-large")]
+            "eff_large" => Some(Self::builtin_eff_large()),
+            #[cfg(feature = "wordlist-eff-short")]
+            "eff_short" => Some(Self::builtin_eff_short()),
+            #[cfg(feature = "wordlist-eff-general-short")]
+            "eff_general_short" => Some(Self::builtin_eff_general_short()),
+            "bip39_english" => Some(Self::builtin_bip39_english()),
+            "pgp_even" => Some(Self::builtin_pgp_even()),
+            "pgp_odd" => Some(Self::builtin_pgp_odd()),
+            _ => None,
+        }
+    }
+
+    /// Parse the given built-in wordlist words, one on each line.
+    fn parse_builtin(words: &str) -> Self {
+        Self::from_words(words.lines().map(|w| w.to_owned()).collect())
+    }
+
+    /// Get the words of the built-in wordlist with the given lowercase name, if enabled.
+    #[cfg(feature = "serde")]
+    fn builtin_words(name: &str) -> Option<&'static str> {
+        match name {
+            #[cfg(feature = "wordlist-eff-large")]
+            "eff_large" => Some(BUILTIN_EFF_LARGE),
+            #[cfg(feature = "wordlist-eff-short")]
+            "eff_short" => Some(BUILTIN_EFF_SHORT),
+            #[cfg(feature = "wordlist-eff-general-short")]
+            "eff_general_short" => Some(BUILTIN_EFF_GENERAL_SHORT),
+            "bip39_english" => Some(BUILTIN_BIP39_ENGLISH),
+            "pgp_even" => Some(BUILTIN_PGP_EVEN),
+            "pgp_odd" => Some(BUILTIN_PGP_ODD),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the built-in wordlist this list is equal to, if any.
+    #[cfg(feature = "serde")]
+    fn builtin_name(&self) -> Option<&'static str> {
+        Self::BUILTIN_NAMES.iter().copied().find(|name| {
+            Self::builtin_words(name)
+                .filter(|words| self.words.iter().map(String::as_str).eq(words.lines()))
+                .is_some()
+        })
+    }
+
+    /// Construct wordlist from built-in EFF large.
+    ///
+    /// Use the built-in EFF large list of words, and construct a wordlist from it.
+    /// This is based on [`BUILTIN_EFF_LARGE`](BUILTIN_EFF_LARGE).
+    ///
+    /// Only available with the `wordlist-eff-large` feature, enabled by default.
+    #[cfg(feature = "wordlist-eff-large")]
+    pub fn builtin_eff_large() -> Self {
+        CACHED_EFF_LARGE.clone()
+    }
+
+    /// Construct wordlist from built-in EFF short.
+    ///
+    /// Use the built-in EFF short list of words, and construct a wordlist from it.
+    /// This is based on [`BUILTIN_EFF_SHORT`](BUILTIN_EFF_SHORT).
+    ///
+    /// **Note:** this wordlist is considered short, as it only contains 1296 (6<sup>4</sup>)
+    /// words.
+    /// The list has an entropy of about 10.3 bits when uniformly sampling words from it.  
+    /// It is recommended to use a larger wordlist such as
+    /// [`builtin_eff_large`](WordList::builtin_eff_large).
+    ///
+    /// Only available with the `wordlist-eff-short` feature, enabled by default.
+    #[cfg(feature = "wordlist-eff-short")]
+    pub fn builtin_eff_short() -> Self {
+        CACHED_EFF_SHORT.clone()
+    }
+
+    /// Construct wordlist from built-in EFF general short.
+    ///
+    /// Use the built-in EFF general short list of words, and construct a wordlist from it.
+    /// This is based on [`BUILTIN_EFF_GENERAL_SHORT`](BUILTIN_EFF_GENERAL_SHORT).
+    ///
+    /// **Note:** this wordlist is considered short, as it only contains 1296 (6<sup>4</sup>)
+    /// words.
+    /// The list has an entropy of about 10.3 bits when uniformly sampling words from it.  
+    /// It is recommended to use a larger wordlist such as
+    /// [`builtin_eff_large`](WordList::builtin_eff_large).
+    ///
+    /// Only available with the `wordlist-eff-general-short` feature, enabled by default.
+    #[cfg(feature = "wordlist-eff-general-short")]
+    pub fn builtin_eff_general_short() -> Self {
+        CACHED_EFF_GENERAL_SHORT.clone()
+    }
+
+    /// Construct wordlist from built-in BIP39 English.
+    ///
+    /// Use the built-in BIP39 English list of words, and construct a wordlist from it.
+    /// This is based on [`BUILTIN_BIP39_ENGLISH`](BUILTIN_BIP39_ENGLISH).
+    ///
+    /// **Note:** this wordlist only contains 2048 words, providing 11 bits of entropy per word.
+    /// Words sampled from it don't form a valid seed phrase, as those include checksum bits. Use
+    /// the `bip39` module, available with the `bip39` feature to generate seed phrases.
+    pub fn builtin_bip39_english() -> Self {
+        CACHED_BIP39_ENGLISH.clone()
+    }
+
+    /// Construct wordlist from built-in PGP even words.
+    ///
+    /// Use the built-in PGP even list of words, and construct a wordlist from it.
+    /// This is based on [`BUILTIN_PGP_EVEN`](BUILTIN_PGP_EVEN).
+    ///
+    /// **Note:** this wordlist only contains 256 words, providing 8 bits of entropy per word. It
+    /// is meant for encoding bytes, see [`encode_pgp`](crate::encode::encode_pgp).
+    pub fn builtin_pgp_even() -> Self {
+        CACHED_PGP_EVEN.clone()
+    }
+
+    /// Construct wordlist from built-in PGP odd words.
+    ///
+    /// Use the built-in PGP odd list of words, and construct a wordlist from it.
+    /// This is based on [`BUILTIN_PGP_ODD`](BUILTIN_PGP_ODD).
+    ///
+    /// **Note:** this wordlist only contains 256 words, providing 8 bits of entropy per word. It
+    /// is meant for encoding bytes, see [`encode_pgp`](crate::encode::encode_pgp).
+    pub fn builtin_pgp_odd() -> Self {
+        CACHED_PGP_ODD.clone()
+    }
+
+    /// Get the words in this wordlist.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Get the index of the given word in this wordlist.
+    ///
+    /// Words are compared exactly. The index of a word is stable for the same list, which allows
+    /// mapping words to numbers and back, see the [`encode`](crate::encode) module. Returns the
+    /// first index if the word occurs more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::word::WordList;
+    ///
+    /// let list = WordList::builtin_eff_large();
+    /// assert_eq!(Some(0), list.index_of("abacus"));
+    /// assert_eq!("abacus", list.words()[0]);
+    /// assert_eq!(None, list.index_of("chbs"));
+    /// # }
+    /// ```
+    pub fn index_of(&self, word: &str) -> Option<usize> {
+        self.words.iter().position(|w| w == word)
+    }
+
+    /// Truncate this wordlist to a power of two size.
+    ///
+    /// This keeps the first words in the list, up to the largest power of two not exceeding the
+    /// current number of words. Uniformly sampling from the truncated list has an integer number
+    /// of entropy bits per word, which simplifies compliance statements and allows encoding word
+    /// indices without dice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{prelude::*, word::WordList};
+    ///
+    /// // The EFF large list has 7776 words, truncated to 2^12
+    /// let list = WordList::default().truncate_pow2();
+    /// assert_eq!(4096, list.words().len());
+    /// assert_eq!(12.0, list.sampler().entropy().bits());
+    /// # }
+    /// ```
+    pub fn truncate_pow2(self) -> Self {
+        let max = self.words.len();
+        self.truncate_pow2_max(max)
+    }
+
+    /// Truncate this wordlist to a power of two size, with at most `max` words.
+    ///
+    /// This keeps the first words in the list, up to the largest power of two not exceeding
+    /// `max` or the current number of words. See [`truncate_pow2`](WordList::truncate_pow2).
+    ///
+    /// # Panics
+    ///
+    /// `max` must be at least two, as a single word is fully predictable.
+    pub fn truncate_pow2_max(mut self, max: usize) -> Self {
+        if max < 2 {
+            panic!("cannot truncate wordlist to less than two words");
+        }
+
+        // Find the largest power of two not exceeding the limit
+        let limit = max.min(self.words.len());
+        let len = 1 << (usize::BITS - 1 - limit.leading_zeros());
+        Arc::make_mut(&mut self.words).truncate(len);
+        self
+    }
+
+    /// Securely sample a random subset of `n` distinct words from this wordlist.
+    ///
+    /// Each word has an equal chance of being included, so uniformly sampling from the subset
+    /// is as secure as uniformly sampling from any wordlist of `n` words. This may be used to
+    /// build a smaller pocket list, for example 1296 words from the EFF large list. The words
+    /// keep their original order.
+    ///
+    /// The randomness source must be cryptographically secure, such as `rand::thread_rng()`.
+    ///
+    /// # Panics
+    ///
+    /// `n` must be at least two, as a single word is fully predictable, and must not exceed the
+    /// number of words in this list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{prelude::*, word::WordList};
+    ///
+    /// let list = WordList::default().random_subset(1296, &mut rand::thread_rng());
+    /// assert_eq!(1296, list.words().len());
+    /// println!("Entropy per word: {}", list.entropy());
+    /// # }
+    /// ```
+    pub fn random_subset<R>(&self, n: usize, rng: &mut R) -> Self
+    where
+        R: RngCore + CryptoRng,
+    {
+        if n < 2 {
+            panic!("cannot sample random subset of less than two words");
+        }
+        if n > self.words.len() {
+            panic!("cannot sample random subset larger than the wordlist");
+        }
+
+        let mut indices = rand::seq::index::sample(rng, self.words.len(), n).into_vec();
+        indices.sort_unstable();
+        Self::from_words(indices.into_iter().map(|i| self.words[i].clone()).collect())
+    }
+
+    /// Derive a wordlist with only the words matching the given predicate.
+    ///
+    /// The words keep their original order. An error is returned if no words are left.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::word::WordList;
+    ///
+    /// let list = WordList::default().filter(|w| w.starts_with('a')).unwrap();
+    /// assert!(list.words().iter().all(|w| w.starts_with('a')));
+    /// # }
+    /// ```
+    pub fn filter<F>(&self, mut predicate: F) -> Result<Self, WordListError>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let words: Vec<String> = self
+            .words
+            .iter()
+            .filter(|w| predicate(w))
+            .cloned()
+            .collect();
+        Self::try_new(words)
+    }
+
+    /// Derive a wordlist with only the words having a number of characters within `lengths`.
+    ///
+    /// The words keep their original order. An error is returned if no words are left.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::word::WordList;
+    ///
+    /// // The EFF large wordlist restricted to 4 to 6 letter words
+    /// let list = WordList::default().retain_length(4..=6).unwrap();
+    /// assert!(list.words().iter().all(|w| (4..=6).contains(&w.chars().count())));
+    /// # }
+    /// ```
+    pub fn retain_length(&self, lengths: RangeInclusive<usize>) -> Result<Self, WordListError> {
+        self.filter(|w| lengths.contains(&w.chars().count()))
+    }
+
+    /// Derive a wordlist with the words of this list followed by the words of `other`.
+    ///
+    /// Words of `other` already in this list are skipped, so merging doesn't introduce
+    /// duplicates. Use [`dedup`](WordList::dedup) to remove duplicates within either list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::WordList;
+    ///
+    /// let colors = WordList::try_new(vec!["red".into(), "teal".into()]).unwrap();
+    /// let animals = WordList::try_new(vec!["otter".into(), "red".into()]).unwrap();
+    /// assert_eq!(&["red", "teal", "otter"], colors.merge(&animals).words());
+    /// ```
+    pub fn merge(&self, other: &WordList) -> Self {
+        let own: HashSet<&str> = self.words.iter().map(String::as_str).collect();
+        let mut words = self.words.to_vec();
+        words.extend(
+            other
+                .words
+                .iter()
+                .filter(|w| !own.contains(w.as_str()))
+                .cloned(),
+        );
+        // Holds at least the words of this list, which is valid
+        Self::from_words(words)
+    }
+
+    /// Derive a wordlist without duplicate words.
+    ///
+    /// The first occurrence of each word is kept, in the original order. Duplicates make
+    /// uniformly sampled words more predictable, see
+    /// [`WordListReport::entropy`](analysis::WordListReport::entropy).
+    ///
+    /// An error is returned if a single distinct word is left.
+    pub fn dedup(&self) -> Result<Self, WordListError> {
+        let mut seen = HashSet::new();
+        Self::try_new(
+            self.words
+                .iter()
+                .filter(|w| seen.insert(w.as_str()))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Ensure the first `length` characters of every word are unique.
+    ///
+    /// Words are kept in order, a word is removed if a word before it starts with the same
+    /// characters. This allows users to only type word prefixes, like the EFF short wordlist #2
+    /// where the first three characters of each word are unique.
+    ///
+    /// Returns the new wordlist along with a report of the dropped words and the entropy change.
+    /// An error is returned if no words are left. For other cleaning steps, see
+    /// [`TidyConfig`](tidy::TidyConfig).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::word::WordList;
+    ///
+    /// let (list, report) = WordList::default().unique_prefix(4).unwrap();
+    /// println!(
+    ///     "Dropped {} words, entropy change: {}",
+    ///     report.removed.len(),
+    ///     report.entropy_change(),
+    /// );
+    /// # }
+    /// ```
+    pub fn unique_prefix(
+        &self,
+        length: usize,
+    ) -> Result<(WordList, tidy::TidyReport), WordListError> {
+        tidy::TidyConfig {
+            alphabetic_only: false,
+            dedup: false,
+            remove_prefixes: false,
+            min_edit_distance: 0,
+            unique_prefix_length: length,
+        }
+        .tidy(self)
+    }
+
+    /// Get the number of dice needed to select any word in this wordlist.
+    ///
+    /// This is the number of digits in a [dice number](WordList::dice_number), the lowest number
+    /// of six sided dice rolls covering all words.
+    pub fn dice_count(&self) -> usize {
+        let mut dice = 1;
+        let mut outcomes = 6;
+        while outcomes < self.words.len() {
+            dice += 1;
+            outcomes *= 6;
+        }
+        dice
+    }
+
+    /// Get the dice number for the word at the given index.
+    ///
+    /// The dice number is the base-6 representation of the index using the digits `1` to `6`,
+    /// padded to [`dice_count`](WordList::dice_count) digits. The first word is `11111` in a
+    /// list of 7776 words.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::word::WordList;
+    ///
+    /// let list = WordList::default();
+    /// assert_eq!(Some("11111".into()), list.dice_number(0));
+    /// assert_eq!(Some("11112".into()), list.dice_number(1));
+    /// assert_eq!(Some("66666".into()), list.dice_number(7775));
+    /// # }
+    /// ```
+    pub fn dice_number(&self, index: usize) -> Option<String> {
+        if index >= self.words.len() {
+            return None;
+        }
+
+        let mut digits = vec![b'1'; self.dice_count()];
+        let mut rest = index;
+        for digit in digits.iter_mut().rev() {
+            *digit += (rest % 6) as u8;
+            rest /= 6;
+        }
+        Some(String::from_utf8(digits).unwrap())
+    }
+
+    /// Save this wordlist to a file.
+    ///
+    /// This writes all words to a file at the given path in the given format, one word per line.
+    /// See [`write`](WordList::write).
+    ///
+    /// An error is returned if saving the wordlist failed.
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    pub fn save<P>(&self, path: P, format: WordListFormat) -> Result<(), WordListError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut writer = BufWriter::new(File::create(path).map_err(WordListError::Save)?);
+        self.write(&mut writer, format)
+            .and_then(|_| writer.flush())
+            .map_err(WordListError::Save)
+    }
+
+    /// Write this wordlist to the given writer.
+    ///
+    /// This writes all words in the given format, one word per line. Wordlists written in the
+    /// [`Plain`](WordListFormat::Plain) format may be loaded again using
+    /// [`load`](WordList::load), the other formats using [`load_diced`](WordList::load_diced).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::{WordList, WordListFormat};
+    ///
+    /// let list = WordList::try_new(vec!["abacus".into(), "abdomen".into()]).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// list.write(&mut out, WordListFormat::Diced).unwrap();
+    /// assert_eq!("1\tabacus\n2\tabdomen\n", String::from_utf8(out).unwrap());
+    /// ```
+    pub fn write<W: Write>(&self, mut writer: W, format: WordListFormat) -> io::Result<()> {
+        for (i, word) in self.words.iter().enumerate() {
+            match format {
+                WordListFormat::Plain => writeln!(writer, "{}", word)?,
+                WordListFormat::Diced => {
+                    writeln!(writer, "{}\t{}", self.dice_number(i).unwrap(), word)?
+                }
+                WordListFormat::Numbered => writeln!(writer, "{}\t{}", i + 1, word)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a sampler for this wordlist.
+    ///
+    /// The word sampler may be used to pull any number of random words from the wordlist for
+    /// passphrase generation.
+    pub fn sampler(&self) -> WordSampler {
+        WordSampler::from_shared(Arc::clone(&self.words))
+    }
+
+    /// Build a sampler for this wordlist with sampling options.
+    ///
+    /// See [`WordSamplerBuilder`](WordSamplerBuilder).
+    pub fn sampler_builder(&self) -> WordSamplerBuilder {
+        WordSamplerBuilder {
+            words: Arc::clone(&self.words),
+            min_length: None,
+            max_length: None,
+            blocklist: Vec::new(),
+            rng: SamplerRng::Thread,
+            replacement: true,
+        }
+    }
+
+    /// Intern this wordlist, sharing its words with identical interned lists.
+    ///
+    /// If an identical wordlist was interned before and is still in use, the returned list
+    /// shares its words instead of holding its own copy. This saves memory when many lists or
+    /// samplers are built from the same source. See the [`intern`](intern) module.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-short")]
+    /// # {
+    /// use chbs::word::{intern, WordList};
+    ///
+    /// let first = WordList::builtin_eff_short().intern();
+    /// let second = WordList::builtin_eff_short().intern();
+    /// assert!(first.ptr_eq(&second));
+    /// assert!(intern::interned_count() >= 1);
+    /// # }
+    /// ```
+    pub fn intern(self) -> Self {
+        WordList {
+            words: intern::intern(self.words),
+        }
+    }
+
+    /// Check whether this list shares its words with the given list.
+    ///
+    /// This is the case for clones, and for identical [interned](WordList::intern) lists.
+    pub fn ptr_eq(&self, other: &WordList) -> bool {
+        Arc::ptr_eq(&self.words, &other.words)
+    }
+
+    /// Build a [`BloomFilter`](bloom::BloomFilter) over this wordlist.
+    ///
+    /// The filter allows membership checks with the given `false_positive_rate`, such as `0.001`,
+    /// without the full wordlist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `false_positive_rate` is not between `0` and `1`, exclusive.
+    pub fn bloom_filter(&self, false_positive_rate: f64) -> bloom::BloomFilter {
+        bloom::BloomFilter::from_words(&self.words, false_positive_rate)
+    }
+
+    /// Analyze this wordlist.
+    ///
+    /// Returns a [report](analysis::WordListReport) of properties relevant to vetting a custom
+    /// wordlist, such as duplicates, word lengths, whether it is prefix-free and its effective
+    /// entropy.
+    pub fn analyze(&self) -> analysis::WordListReport {
+        analysis::WordListReport::new(&self.words)
+    }
+}
+
+impl HasEntropy for WordList {
+    /// The entropy of a single word uniformly sampled from this wordlist.
+    fn entropy(&self) -> Entropy {
+        Entropy::from_real(self.words.len() as f64)
+    }
+}
+
+/// Strip a leading UTF-8 byte order mark from the given wordlist contents.
+fn strip_bom(contents: &str) -> &str {
+    contents.strip_prefix('\u{feff}').unwrap_or(contents)
+}
+
+/// Check whether the given trimmed wordlist line is a comment.
+///
+/// A `#` directly followed by a digit starts a numbered entry instead, such as
+/// `#1 (1,1,1,1,1) abacus`.
+fn is_comment(line: &str) -> bool {
+    let mut chars = line.chars();
+    chars.next() == Some('#') && chars.next().filter(char::is_ascii_digit).is_none()
+}
+
+/// Iterate over the trimmed lines of the given wordlist contents, skipping empty and comment
+/// lines.
+fn content_lines(contents: &str) -> impl Iterator<Item = &str> {
+    strip_bom(contents)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_comment(line))
+}
+
+/// Read all contents of the given reader as string.
+fn read_all<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+/// Serialize as the name of the built-in wordlist if equal to one, or as list of words otherwise.
+#[cfg(feature = "serde")]
+impl serde::Serialize for WordList {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.builtin_name() {
+            Some(name) => serializer.serialize_str(name),
+            None => self.words.serialize(serializer),
+        }
+    }
+}
+
+/// Deserialize from the name of a built-in wordlist, or from a list of words.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WordList {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Builtin(String),
+            Words(Vec<String>),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Builtin(name) => Self::builtin(&name).ok_or_else(|| {
+                D::Error::custom(format_args!("unknown built-in wordlist '{}'", name))
+            }),
+            Repr::Words(words) => Self::try_new(words).map_err(D::Error::custom),
+        }
+    }
+}
+
+#[cfg(feature = "wordlist-eff-large")]
+impl Default for WordList {
+    /// Construct a default wordlist.
+    ///
+    /// This uses the built-in EFF large wordlist, which can be constructed with
+    /// [`WordList::builtin_eff_large()`](WordList::builtin_eff_large). Only available with the
+    /// `wordlist-eff-large` feature, enabled by default.
+    fn default() -> WordList {
+        WordList::builtin_eff_large()
+    }
+}
+
+/// A [`WordList`](WordList) error.
+///
+/// Some variants only exist with the feature they belong to, so this may be extended depending
+/// on enabled features and must not be matched exhaustively.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum WordListError {
+    /// Failed to load a wordlist from a file.
+    #[error("failed to load wordlist from file")]
+    Load(#[from] std::io::Error),
+
+    /// A loaded wordlist is emtpy, which is not allowed.
+    #[error("loaded wordlist did not contain words")]
+    Empty,
+
+    /// A wordlist has a single word, so passphrases would be fully predictable.
+    #[error("wordlist has a single word, providing no entropy")]
+    SingleWord,
+
+    /// The format of a wordlist could not be detected, as it is ambiguous.
+    #[error("ambiguous wordlist format, {0}")]
+    AmbiguousFormat(&'static str),
+
+    /// A weighted wordlist has an invalid weight on the given line number.
+    #[error("invalid word weight on line {0}")]
+    InvalidWeight(usize),
+
+    /// A biased weighted sampler has less entropy per word than the required floor.
+    #[error("biased sampler has {min_entropy} per word, below the floor of {floor}")]
+    EntropyFloor {
+        /// The min-entropy per word of the biased sampler.
+        min_entropy: Entropy,
+
+        /// The required minimum entropy per word.
+        floor: Entropy,
+    },
+
+    /// Failed to save a wordlist to a file.
+    #[error("failed to save wordlist to file")]
+    Save(#[source] std::io::Error),
+
+    /// The checksum of a loaded wordlist file doesn't match the expected checksum.
+    #[cfg(feature = "verify")]
+    #[error("wordlist checksum mismatch, expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// The expected SHA-256 checksum, in hexadecimal.
+        expected: String,
+
+        /// The actual SHA-256 checksum of the file, in hexadecimal.
+        actual: String,
+    },
+
+    /// No system dictionary was found.
+    #[cfg(feature = "system-dict")]
+    #[error("no system dictionary found")]
+    NoSystemDict,
+
+    /// Failed to parse a CSV wordlist.
+    #[cfg(feature = "structured-formats")]
+    #[error("failed to parse CSV wordlist")]
+    Csv(#[from] csv::Error),
+
+    /// A CSV wordlist doesn't have the selected column.
+    #[cfg(feature = "structured-formats")]
+    #[error("CSV wordlist has no column {0}")]
+    MissingColumn(String),
+
+    /// Failed to parse a JSON wordlist.
+    #[cfg(feature = "structured-formats")]
+    #[error("failed to parse JSON wordlist")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A file format for wordlists.
+///
+/// Used to [save](WordList::save) a wordlist in a specific format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WordListFormat {
+    /// One word per line.
+    ///
+    /// ```txt
+    /// abacus
+    /// abdomen
+    /// ```
+    Plain,
+
+    /// One word per line, prefixed with its dice number separated by a tab character.
+    ///
+    /// Dice numbers are the base-6 word index using the digits `1` to `6`, see
+    /// [`WordList::dice_number`](WordList::dice_number). For physical diceware use, the
+    /// wordlist should have a power of six size such that every dice roll selects a word.
+    ///
+    /// ```txt
+    /// 11111 abacus
+    /// 11112 abdomen
+    /// ```
+    Diced,
+
+    /// One word per line, prefixed with its one-based line number separated by a tab.
+    ///
+    /// ```txt
+    /// 1 abacus
+    /// 2 abdomen
+    /// ```
+    Numbered,
+}
+
+impl WordListFormat {
+    /// Detect the format of the given wordlist contents.
+    ///
+    /// Returns [`Diced`](WordListFormat::Diced) if every non-empty line has a word prefixed with
+    /// one or more items containing digits, such as a dice number or index. Returns
+    /// [`Plain`](WordListFormat::Plain) if no line contains any digits.
+    ///
+    /// An error is returned if the format is ambiguous, or if there are no words.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::word::WordListFormat;
+    ///
+    /// let plain = "abacus\nabdomen\n";
+    /// assert_eq!(WordListFormat::Plain, WordListFormat::detect(plain).unwrap());
+    ///
+    /// let diced = "11111 abacus\n11112 abdomen\n";
+    /// assert_eq!(WordListFormat::Diced, WordListFormat::detect(diced).unwrap());
+    ///
+    /// let mixed = "11111 abacus\nabdomen\n";
+    /// assert!(WordListFormat::detect(mixed).is_err());
+    /// ```
+    pub fn detect(contents: &str) -> Result<Self, WordListError> {
+        let has_digit = |item: &str| item.chars().any(|c| c.is_ascii_digit());
+
+        let mut diced = 0;
+        let mut plain = 0;
+        for line in content_lines(contents) {
+            let items: Vec<&str> = line.split_whitespace().collect();
+            match items.split_last() {
+                None => continue,
+                Some((word, prefix))
+                    if !prefix.is_empty()
+                        && !has_digit(word)
+                        && prefix.iter().all(|i| has_digit(i)) =>
+                {
+                    diced += 1
+                }
+                Some(_) if !items.iter().any(|i| has_digit(i)) => plain += 1,
+                Some(_) => {
+                    return Err(WordListError::AmbiguousFormat(
+                        "line contains digits but is not a diced word",
+                    ))
+                }
+            }
+        }
+
+        match (diced, plain) {
+            (0, 0) => Err(WordListError::Empty),
+            (_, 0) => Ok(WordListFormat::Diced),
+            (0, _) => Ok(WordListFormat::Plain),
+            _ => Err(WordListError::AmbiguousFormat(
+                "only some lines are prefixed with dice numbers",
+            )),
+        }
+    }
+}
+
+/// Apply the given word `filter` to `words`, removing vetoed words and duplicates.
+pub(crate) fn apply_word_filter<'a, I>(words: I, filter: &dyn WordFilter) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut seen = HashSet::new();
+    words
+        .into_iter()
+        .filter_map(|word| filter.filter_word(word))
+        .map(Cow::into_owned)
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+/// An iterator uniformly sampling words.
+///
+/// This sampler uses a given wordlist of wich random words are picked for use in passphrases.
+/// The randomization is concidered cryptographically secure.
+///
+/// The iterator is infinite, as much words as needed may be pulled from this iterator.
+///
+/// To construct an instance based on a [`WordList`](WordList), use the
+/// [`sampler`](WordList::sampler) method.
+// TODO: use string references
+#[derive(Clone, Debug)]
+pub struct WordSampler {
+    /// List of words that is used for sampling, shared with the list it was built from.
+    words: Arc<Vec<String>>,
+
+    /// Random distribution used for sampling, over `u64` to sample identically on every platform.
+    distribution: Uniform<u64>,
+
+    /// The randomness source used when none is given.
+    rng: SamplerRng,
+
+    /// Whether iterating this sampler may yield the same word more than once.
+    replacement: bool,
+}
+
+impl WordSampler {
+    /// Build a new word sampler which samples the given word list.
+    ///
+    /// To configure sampling options, use a [`WordSamplerBuilder`](WordSamplerBuilder) instead.
+    pub fn new(words: Vec<String>) -> WordSampler {
+        Self::from_shared(Arc::new(words))
+    }
+
+    /// Build a new word sampler which samples the given shared word list.
+    fn from_shared(words: Arc<Vec<String>>) -> WordSampler {
+        WordSampler {
+            distribution: Uniform::new(0, words.len() as u64),
+            words,
+            rng: SamplerRng::Thread,
+            replacement: true,
+        }
+    }
+
+    /// Derive a sampler of the words of this sampler accepted by the given `filter`.
+    ///
+    /// Vetoed words are removed, and words are replaced as the filter dictates. Duplicates
+    /// resulting from replacement are removed, so words stay uniformly sampled and the entropy
+    /// reflects the number of distinct words. The derived sampler uses the same sampling options.
+    ///
+    /// Returns `None` if the filter vetoes all words.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "wordlist-eff-large")]
+    /// # {
+    /// use chbs::{component::word::WordLengthFilter, prelude::*, word::WordList};
+    ///
+    /// let sampler = WordList::default().sampler();
+    /// let filtered = sampler.apply_filter(&WordLengthFilter::new(6..=9)).unwrap();
+    /// assert!(filtered.entropy() < sampler.entropy());
+    /// assert!((6..=9).contains(&filtered.word().len()));
+    /// # }
+    /// ```
+    pub fn apply_filter(&self, filter: &dyn WordFilter) -> Option<WordSampler> {
+        let words = apply_word_filter(self.words.iter().map(String::as_str), filter);
+        if words.is_empty() {
+            return None;
+        }
+        Some(WordSampler {
+            rng: self.rng,
+            replacement: self.replacement,
+            ..WordSampler::new(words)
+        })
+    }
+
+    /// Sample `count` random words by reference, with batched randomness.
+    ///
+    /// This draws randomness for all words at once using the randomness source of this sampler,
+    /// see [`sample_batch_with_rng`](WordSampler::sample_batch_with_rng).
+    pub fn sample_batch(&self, count: usize) -> Vec<&str> {
+        match self.rng {
+            SamplerRng::Thread => self.sample_batch_with_rng(count, &mut default_rng()),
+            SamplerRng::Os => self.sample_batch_with_rng(count, &mut rand::rngs::OsRng),
+        }
+    }
+
+    /// Sample `count` random words by reference, with batched randomness from the given source.
+    ///
+    /// Words are sampled with the same uniform distribution as [`word`](WordProvider::word), but
+    /// randomness is drawn in as few draws as possible, see
+    /// [`uniform_indices`](crate::rng::uniform_indices). For the built-in wordlists, up to four
+    /// words are sampled from a single draw.
+    pub fn sample_batch_with_rng(&self, count: usize, rng: &mut dyn SecureRng) -> Vec<&str> {
+        uniform_indices(rng, &vec![self.words.len(); count])
+            .into_iter()
+            .map(|i| self.words[i].as_str())
+            .collect()
+    }
+
+    /// Sample a random word by reference.
+    ///
+    /// This returns a cryptographically secure random word by reference, which is faster than
+    /// [`word`](WordSampler::word) as it prevents cloning the chosen word.
+    fn word_ref(&self) -> &str {
+        match self.rng {
+            SamplerRng::Thread => self.word_ref_with_rng(&mut default_rng()),
+            SamplerRng::Os => self.word_ref_with_rng(&mut rand::rngs::OsRng),
+        }
+    }
+
+    /// Sample a random word by reference, using the given randomness source.
+    fn word_ref_with_rng(&self, rng: &mut dyn SecureRng) -> &str {
+        // Used instead of `rng.choose` for better performance
+        &self.words[rng.sample(self.distribution) as usize]
+    }
+}
+
+impl WordProvider for WordSampler {
+    fn word(&self) -> String {
+        self.word_ref().to_owned()
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        self.word_ref_with_rng(rng).to_owned()
+    }
+
+    fn sample_ref(&self) -> Option<&str> {
+        Some(self.word_ref())
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        Some(self.words.iter().map(String::as_str).collect())
+    }
+}
+
+impl HasEntropy for WordSampler {
+    fn entropy(&self) -> Entropy {
+        Entropy::from_real(self.words.len() as f64)
+    }
+}
+
+impl IntoIterator for WordSampler {
+    type Item = String;
+    type IntoIter = WordSamplerIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let remaining = if self.replacement {
+            None
+        } else {
+            Some((0..self.words.len()).collect())
+        };
+        WordSamplerIter {
+            sampler: self,
+            remaining,
+        }
+    }
+}
+
+/// Serialize the sampled words like a [`WordList`](WordList). Sampling options are not retained.
+#[cfg(feature = "serde")]
+impl serde::Serialize for WordSampler {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        WordList {
+            words: Arc::clone(&self.words),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialize like a [`WordList`](WordList), sampling with default options.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WordSampler {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        WordList::deserialize(deserializer).map(|list| list.sampler())
+    }
+}
+
+/// An iterator over words sampled by a [`WordSampler`](WordSampler).
+///
+/// This is infinite, unless the sampler samples without replacement. Then each word is yielded at
+/// most once, and the iterator ends when all words have been yielded.
+pub struct WordSamplerIter {
+    /// The sampler to sample words with.
+    sampler: WordSampler,
+
+    /// Indices of words not yielded yet, if sampling without replacement.
+    remaining: Option<Vec<usize>>,
+}
+
+impl Iterator for WordSamplerIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let remaining = match self.remaining {
+            Some(ref mut remaining) => remaining,
+            None => return Some(self.sampler.word()),
+        };
+        if remaining.is_empty() {
+            return None;
+        }
+
+        let i = match self.sampler.rng {
+            SamplerRng::Thread => gen_index(&mut default_rng(), remaining.len()),
+            SamplerRng::Os => gen_index(&mut rand::rngs::OsRng, remaining.len()),
+        };
+        Some(self.sampler.words[remaining.swap_remove(i)].clone())
+    }
+}
+
+/// The randomness source a [`WordSampler`](WordSampler) uses when none is given.
+///
+/// Both sources are cryptographically secure.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SamplerRng {
+    /// The thread-local generator, `rand::thread_rng()`. This is the default.
+    ///
+    /// With the `wasm` feature, this is the operating system randomness source as well.
+    Thread,
+
+    /// The operating system randomness source, `rand::rngs::OsRng`.
+    ///
+    /// This is slower, but doesn't keep generator state in process memory.
+    Os,
+}
+
+/// A builder for a [`WordSampler`](WordSampler) with sampling options.
+///
+/// This configures length filters, a blocklist, the randomness source and replacement behavior in
+/// one place. Filtered words are removed when the sampler is built, so its entropy is
+/// `log2(remaining words)`.
+///
+/// Construct it using [`WordList::sampler_builder`](WordList::sampler_builder).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{prelude::*, word::{SamplerRng, WordList}};
+///
+/// let sampler = WordList::default()
+///     .sampler_builder()
+///     .min_length(4)
+///     .max_length(6)
+///     .block(vec!["horse"])
+///     .rng(SamplerRng::Os)
+///     .build()
+///     .unwrap();
+///
+/// let word = sampler.word();
+/// assert!(word.len() >= 4 && word.len() <= 6);
+/// assert!(sampler.entropy().bits() < WordList::default().entropy().bits());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct WordSamplerBuilder {
+    /// The words to sample.
+    words: Arc<Vec<String>>,
+
+    /// The minimum word length in characters.
+    min_length: Option<usize>,
+
+    /// The maximum word length in characters.
+    max_length: Option<usize>,
+
+    /// Blocked words, in lowercase.
+    blocklist: Vec<String>,
+
+    /// The randomness source used when none is given.
+    rng: SamplerRng,
+
+    /// Whether iterating the sampler may yield the same word more than once.
+    replacement: bool,
+}
+
+impl WordSamplerBuilder {
+    /// Set the minimum length of sampled words, in characters.
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    /// Set the maximum length of sampled words, in characters.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Block the given words from being sampled.
+    ///
+    /// Words are matched exactly, ignoring case.
+    pub fn block<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.blocklist
+            .extend(words.into_iter().map(|w| w.as_ref().to_lowercase()));
+        self
+    }
+
+    /// Set the randomness source used when none is given, defaults to
+    /// [`SamplerRng::Thread`](SamplerRng::Thread).
+    pub fn rng(mut self, rng: SamplerRng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Set whether iterating the sampler may yield the same word more than once, defaults to
+    /// `true`.
+    ///
+    /// Without replacement, the [iterator](WordSamplerIter) yields each word at most once. This
+    /// only affects iteration, words sampled through [`WordProvider`](WordProvider) are always
+    /// sampled with replacement.
+    pub fn replacement(mut self, replacement: bool) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Build the sampler.
+    ///
+    /// An error is returned if less than two words remain after filtering, see
+    /// [`WordList::try_new`](WordList::try_new).
+    pub fn build(self) -> Result<WordSampler, WordListError> {
+        let filtered =
+            self.min_length.is_some() || self.max_length.is_some() || !self.blocklist.is_empty();
+        let words = if filtered {
+            let words: Vec<String> = self
+                .words
+                .iter()
+                .filter(|w| {
+                    let len = w.chars().count();
+                    !matches!(self.min_length, Some(min) if len < min)
+                        && !matches!(self.max_length, Some(max) if len > max)
+                        && !self.blocklist.contains(&w.to_lowercase())
+                })
+                .cloned()
+                .collect();
+            Arc::new(words)
+        } else {
+            self.words
+        };
+        WordList::check_len(words.len())?;
+
+        let mut sampler = WordSampler::from_shared(words);
+        sampler.rng = self.rng;
+        sampler.replacement = self.replacement;
+        Ok(sampler)
+    }
+}
+
+/// A wordlist borrowing its words from static memory.
+///
+/// This is a zero allocation variant of [`WordList`](WordList). It is backed by a
+/// `&'static [&'static str]`, so constructing it doesn't require any parsing. The
+/// [sampler](StaticWordList::sampler) provides borrowed words through
+/// [`word_ref`](StaticWordSampler::word_ref) without allocating.
+///
+/// The built-in wordlists are available through the methods prefixed with `builtin_`, the
+/// default uses [`builtin_eff_large()`](StaticWordList::builtin_eff_large).
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::word::StaticWordList;
+///
+/// let sampler = StaticWordList::default().sampler();
+/// let word: &'static str = sampler.word_ref();
+/// println!("Word: {}", word);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct StaticWordList {
+    /// A fixed set of static words.
+    words: &'static [&'static str],
+}
+
+impl StaticWordList {
+    /// Construct a new static word list with the given words.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the given set of words is empty, or if it has a single word, see
+    /// [`WordList::try_new`](WordList::try_new).
+    pub fn new(words: &'static [&'static str]) -> Self {
+        if let Err(err) = WordList::check_len(words.len()) {
+            panic!("cannot construct wordlist: {}", err);
+        }
+
+        StaticWordList { words }
+    }
+
+    /// Construct static wordlist from built-in EFF large.
+    ///
+    /// This contains the same words as [`BUILTIN_EFF_LARGE`](BUILTIN_EFF_LARGE).
+    ///
+    /// Only available with the `wordlist-eff-large` feature, enabled by default.
+    #[cfg(feature = "wordlist-eff-large")]
+    pub fn builtin_eff_large() -> Self {
+        Self::new(builtin::EFF_LARGE_WORDS)
+    }
+
+    /// Construct static wordlist from built-in EFF short.
+    ///
+    /// This contains the same words as [`BUILTIN_EFF_SHORT`](BUILTIN_EFF_SHORT).
+    ///
+    /// **Note:** this wordlist is considered short, as it only contains 1296 (6<sup>4</sup>)
+    /// words. It is recommended to use a larger wordlist such as
+    /// [`builtin_eff_large`](StaticWordList::builtin_eff_large).
+    ///
+    /// Only available with the `wordlist-eff-short` feature, enabled by default.
+    #[cfg(feature = "wordlist-eff-short")]
+    pub fn builtin_eff_short() -> Self {
+        Self::new(builtin::EFF_SHORT_WORDS)
+    }
+
+    /// Construct static wordlist from built-in EFF general short.
+    ///
+    /// This contains the same words as [`BUILTIN_EFF_GENERAL_SHORT`](BUILTIN_EFF_GENERAL_SHORT).
+    ///
+    /// **Note:** this wordlist is considered short, as it only contains 1296 (6<sup>4</sup>)
+    /// words. It is recommended to use a larger wordlist such as
+    /// [`builtin_eff_large`](StaticWordList::builtin_eff_large).
+    ///
+    /// Only available with the `wordlist-eff-general-short` feature, enabled by default.
+    #[cfg(feature = "wordlist-eff-general-short")]
+    pub fn builtin_eff_general_short() -> Self {
+        Self::new(builtin::EFF_GENERAL_SHORT_WORDS)
+    }
+
+    /// Construct static wordlist from built-in BIP39 English.
+    ///
+    /// This contains the same words as [`BUILTIN_BIP39_ENGLISH`](BUILTIN_BIP39_ENGLISH).
+    pub fn builtin_bip39_english() -> Self {
+        Self::new(builtin::BIP39_ENGLISH_WORDS)
+    }
+
+    /// Construct static wordlist from built-in PGP even words.
+    ///
+    /// This contains the same words as [`BUILTIN_PGP_EVEN`](BUILTIN_PGP_EVEN).
+    pub fn builtin_pgp_even() -> Self {
+        Self::new(builtin::PGP_EVEN_WORDS)
+    }
+
+    /// Construct static wordlist from built-in PGP odd words.
+    ///
+    /// This contains the same words as [`BUILTIN_PGP_ODD`](BUILTIN_PGP_ODD).
+    pub fn builtin_pgp_odd() -> Self {
+        Self::new(builtin::PGP_ODD_WORDS)
+    }
+
+    /// Get the words in this wordlist.
+    pub fn words(&self) -> &'static [&'static str] {
+        self.words
+    }
+
+    /// Build a sampler for this wordlist.
+    ///
+    /// Building the sampler doesn't allocate, the words stay borrowed from static memory.
+    pub fn sampler(&self) -> StaticWordSampler {
+        StaticWordSampler::new(self.words)
+    }
+}
+
+#[cfg(feature = "wordlist-eff-large")]
+impl Default for StaticWordList {
+    /// Construct a default static wordlist.
+    ///
+    /// This uses the built-in EFF large wordlist, which can be constructed with
+    /// [`StaticWordList::builtin_eff_large()`](StaticWordList::builtin_eff_large). Only
+    /// available with the `wordlist-eff-large` feature, enabled by default.
+    fn default() -> StaticWordList {
+        StaticWordList::builtin_eff_large()
+    }
+}
+
+impl From<StaticWordList> for WordList {
+    fn from(list: StaticWordList) -> WordList {
+        WordList::from_words(list.words.iter().map(|w| (*w).to_owned()).collect())
+    }
+}
+
+/// An iterator uniformly sampling words from static memory.
+///
+/// This is the zero allocation variant of [`WordSampler`](WordSampler), sampling from a
+/// [`StaticWordList`](StaticWordList). Use [`word_ref`](StaticWordSampler::word_ref) to sample
+/// words by reference.
+///
+/// To construct an instance based on a [`StaticWordList`](StaticWordList), use the
+/// [`sampler`](StaticWordList::sampler) method.
+#[derive(Clone, Debug)]
+pub struct StaticWordSampler {
+    /// List of static words that is used for sampling.
+    words: &'static [&'static str],
+
+    /// Random distribution used for sampling, over `u64` to sample identically on every platform.
+    distribution: Uniform<u64>,
+}
+
+impl StaticWordSampler {
+    /// Build a new word sampler which samples the given static word list.
+    pub fn new(words: &'static [&'static str]) -> StaticWordSampler {
+        StaticWordSampler {
+            distribution: Uniform::new(0, words.len() as u64),
+            words,
+        }
+    }
+
+    /// Sample a random word by reference.
+    ///
+    /// This returns a cryptographically secure random word borrowed from static memory, and
+    /// doesn't allocate.
+    pub fn word_ref(&self) -> &'static str {
+        self.word_ref_with_rng(&mut default_rng())
+    }
+
+    /// Sample a random word by reference, using the given randomness source.
+    pub fn word_ref_with_rng(&self, rng: &mut dyn SecureRng) -> &'static str {
+        self.words[rng.sample(self.distribution) as usize]
+    }
+}
+
+impl WordProvider for StaticWordSampler {
+    fn word(&self) -> String {
+        self.word_ref().to_owned()
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        self.word_ref_with_rng(rng).to_owned()
+    }
+
+    fn sample_ref(&self) -> Option<&str> {
+        Some(self.word_ref())
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        Some(self.words.to_vec())
+    }
+}
+
+impl HasEntropy for StaticWordSampler {
+    fn entropy(&self) -> Entropy {
+        Entropy::from_real(self.words.len() as f64)
+    }
+}
+
+impl IntoIterator for StaticWordSampler {
+    type Item = &'static str;
+    type IntoIter = StaticWordSamplerIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        StaticWordSamplerIter { sampler: self }
+    }
+}
+
+/// An infinite iterator yielding random words sampled by a [`StaticWordSampler`](StaticWordSampler).
+pub struct StaticWordSamplerIter {
+    sampler: StaticWordSampler,
+}
+
+impl Iterator for StaticWordSamplerIter {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<&'static str> {
+        Some(self.sampler.word_ref())
+    }
+}
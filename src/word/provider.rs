@@ -0,0 +1,559 @@
+//! Word provider implementations and adapters
+//!
+//! This module implements [`WordProvider`](crate::component::traits::WordProvider) for simple
+//! collections of words, so a quick custom list may be used as provider directly without
+//! constructing a [`WordList`](super::WordList) first.
+//!
+//! The following collections are supported, words are uniformly sampled from them:
+//!
+//! - `Vec<String>`
+//! - `&[&str]`
+//! - `[&str; N]` and `[String; N]`
+//!
+//! Adapters are also provided to derive a new provider from an existing one, such as
+//! [`Filter`](Filter) and [`Map`](Map). These are constructed through the
+//! [`filter`](WordProvider::filter) and [`map`](WordProvider::map) methods on any provider, so
+//! providers compose like iterators.
+//!
+//! To quickly try an experimental word source without defining a new type, wrap a closure using
+//! [`word_provider_fn`](word_provider_fn). An external infinite iterator of words, for example
+//! backed by another crate, may be used through [`IterWordProvider`](IterWordProvider).
+//!
+//! To mix words from several wordlists, such as lists in different languages, use
+//! [`MixedWordProvider`](MixedWordProvider).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chbs::{config::BasicConfig, prelude::*, probability::Probability};
+//!
+//! let config = BasicConfig::new(["correct", "horse", "battery", "staple"])
+//!     .with_words(4)
+//!     .with_separator("-")
+//!     .with_capitalize_first(Probability::Never);
+//!
+//! println!("Passphrase: {}", config.to_scheme().generate());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+use rand::{distributions::WeightedIndex, seq::SliceRandom, Rng};
+
+use crate::component::word::WordFilterError;
+use crate::entropy::Entropy;
+use crate::prelude::*;
+use crate::rng::default_rng;
+
+/// Uniformly sample a random word from the given slice of words.
+///
+/// # Panics
+///
+/// This panics if the given slice of words is empty.
+fn sample<'a, S: AsRef<str>>(words: &'a [S], rng: &mut dyn SecureRng) -> &'a str {
+    words
+        .choose(rng)
+        .expect("cannot sample word, given list of words is empty")
+        .as_ref()
+}
+
+/// Implement [`WordProvider`](WordProvider) for a collection type that dereferences to a slice.
+macro_rules! impl_collection_provider {
+    ($(impl [$($gen:tt)*] for $type_:ty;)*) => {
+        $(
+            impl<$($gen)*> HasEntropy for $type_ {
+                fn entropy(&self) -> Entropy {
+                    Entropy::from_real(self.len() as f64)
+                }
+            }
+
+            impl<$($gen)*> WordProvider for $type_ {
+                fn word(&self) -> String {
+                    self.word_with_rng(&mut default_rng())
+                }
+
+                fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+                    sample(&self[..], rng).to_owned()
+                }
+
+                fn sample_ref(&self) -> Option<&str> {
+                    Some(sample(&self[..], &mut default_rng()))
+                }
+
+                fn known_words(&self) -> Option<Vec<&str>> {
+                    Some(self.iter().map(AsRef::as_ref).collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_collection_provider! {
+    impl [] for Vec<String>;
+    impl ['a, 'b] for &'a [&'b str];
+    impl [const N: usize] for [&'static str; N];
+    impl [const N: usize] for [String; N];
+}
+
+/// The number of words sampled to estimate the acceptance rate of a [`Filter`](Filter) over a
+/// provider not knowing its words.
+const FILTER_SAMPLES: usize = 1024;
+
+/// A word provider adapter that only provides words accepted by a predicate.
+///
+/// Words rejected by the predicate are discarded, and a new word is obtained from the inner
+/// provider until one is accepted.
+///
+/// As the filter reduces the number of possible words, the entropy is lowered. If the inner
+/// provider knows its words, the entropy is exactly `log2(accepted words)`. Otherwise the
+/// acceptance rate of the predicate is measured once on construction by sampling the inner
+/// provider, the entropy is then estimated as `entropy(provider) + log2(acceptance rate)`.
+///
+/// Construct this adapter using [`WordProvider::filter`](WordProvider::filter).
+#[derive(Clone)]
+pub struct Filter<P, F> {
+    /// The inner provider to obtain words from.
+    provider: P,
+
+    /// The predicate words must match.
+    predicate: F,
+
+    /// The estimated entropy of this provider.
+    entropy: Entropy,
+}
+
+impl<P, F> Filter<P, F>
+where
+    P: WordProvider,
+    F: Fn(&str) -> bool,
+{
+    /// Construct a filtering adapter for the given provider.
+    ///
+    /// Returns [`WordFilterError::Empty`](WordFilterError::Empty) if the predicate accepts none of
+    /// the known words of the provider, or none of the words sampled to measure its acceptance
+    /// rate, as obtaining a word would likely never finish.
+    pub fn new(provider: P, predicate: F) -> Result<Self, WordFilterError> {
+        let entropy = match provider.known_words() {
+            Some(words) => {
+                let accepted = words.into_iter().filter(|w| predicate(w)).count();
+                if accepted == 0 {
+                    return Err(WordFilterError::Empty);
+                }
+                Entropy::from_real(accepted as f64)
+            }
+            None => {
+                let accepted = (0..FILTER_SAMPLES)
+                    .filter(|_| predicate(&provider.word()))
+                    .count();
+                if accepted == 0 {
+                    return Err(WordFilterError::Empty);
+                }
+                provider.entropy() + (accepted as f64 / FILTER_SAMPLES as f64).log2()
+            }
+        };
+
+        Ok(Self {
+            provider,
+            predicate,
+            entropy,
+        })
+    }
+}
+
+impl<P: Debug, F> Debug for Filter<P, F> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("provider", &self.provider)
+            .field("entropy", &self.entropy)
+            .finish()
+    }
+}
+
+impl<P, F> HasEntropy for Filter<P, F> {
+    fn entropy(&self) -> Entropy {
+        self.entropy
+    }
+}
+
+impl<P, F> WordProvider for Filter<P, F>
+where
+    P: WordProvider,
+    F: Fn(&str) -> bool + Clone + Send + Sync,
+{
+    fn word(&self) -> String {
+        self.word_with_rng(&mut default_rng())
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        loop {
+            let word = self.provider.word_with_rng(rng);
+            if (self.predicate)(&word) {
+                return word;
+            }
+        }
+    }
+
+    fn sample_ref(&self) -> Option<&str> {
+        loop {
+            let word = self.provider.sample_ref()?;
+            if (self.predicate)(word) {
+                return Some(word);
+            }
+        }
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        let words = self.provider.known_words()?;
+        Some(words.into_iter().filter(|w| (self.predicate)(w)).collect())
+    }
+}
+
+/// A word provider adapter that maps each provided word.
+///
+/// The entropy is equal to that of the inner provider. The mapping must therefore be injective,
+/// different words must never be mapped to the same result.
+///
+/// Construct this adapter using [`WordProvider::map`](WordProvider::map).
+#[derive(Clone)]
+pub struct Map<P, F> {
+    /// The inner provider to obtain words from.
+    provider: P,
+
+    /// The function to map words with.
+    f: F,
+}
+
+impl<P, F> Map<P, F>
+where
+    P: WordProvider,
+    F: Fn(String) -> String,
+{
+    /// Construct a mapping adapter for the given provider.
+    pub fn new(provider: P, f: F) -> Self {
+        Self { provider, f }
+    }
+}
+
+impl<P: Debug, F> Debug for Map<P, F> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("provider", &self.provider)
+            .finish()
+    }
+}
+
+impl<P: HasEntropy, F> HasEntropy for Map<P, F> {
+    fn entropy(&self) -> Entropy {
+        self.provider.entropy()
+    }
+}
+
+impl<P, F> WordProvider for Map<P, F>
+where
+    P: WordProvider,
+    F: Fn(String) -> String + Clone + Send + Sync,
+{
+    fn word(&self) -> String {
+        (self.f)(self.provider.word())
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        (self.f)(self.provider.word_with_rng(rng))
+    }
+}
+
+/// Construct a word provider from a closure.
+///
+/// The closure is invoked each time a word is obtained, and is given a cryptographically secure
+/// randomness source to use. The `entropy` each provided word has must be declared, as it cannot
+/// be derived from the closure.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{entropy::Entropy, prelude::*, word::provider::word_provider_fn};
+/// use rand::Rng;
+///
+/// // Provide random four digit numbers as words
+/// let provider = word_provider_fn(Entropy::from_real(10_000), |rng| {
+///     format!("{:04}", rng.gen_range(0..10_000))
+/// });
+/// assert_eq!(4, provider.word().len());
+/// ```
+pub fn word_provider_fn<F>(entropy: Entropy, f: F) -> FnWordProvider<F>
+where
+    F: Fn(&mut dyn SecureRng) -> String + Clone + Send + Sync,
+{
+    FnWordProvider { f, entropy }
+}
+
+/// A word provider backed by a closure.
+///
+/// Construct this provider using [`word_provider_fn`](word_provider_fn).
+#[derive(Clone)]
+pub struct FnWordProvider<F> {
+    /// The closure that provides words.
+    f: F,
+
+    /// The declared entropy of each provided word.
+    entropy: Entropy,
+}
+
+impl<F> Debug for FnWordProvider<F> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("FnWordProvider")
+            .field("entropy", &self.entropy)
+            .finish()
+    }
+}
+
+impl<F> HasEntropy for FnWordProvider<F> {
+    fn entropy(&self) -> Entropy {
+        self.entropy
+    }
+}
+
+impl<F> WordProvider for FnWordProvider<F>
+where
+    F: Fn(&mut dyn SecureRng) -> String + Clone + Send + Sync,
+{
+    fn word(&self) -> String {
+        (self.f)(&mut default_rng())
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        (self.f)(rng)
+    }
+}
+
+/// A word provider backed by an external iterator.
+///
+/// This adapter allows any infinite iterator of words to be used as word provider, such as a word
+/// generator from another crate. The entropy each provided word has must be declared, as it
+/// cannot be derived from the iterator. The iterator must produce cryptographically secure random
+/// words.
+///
+/// The iterator is shared between clones of this provider, words obtained through any clone
+/// advance the same iterator.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "wordlist-eff-large")]
+/// # {
+/// use chbs::{entropy::Entropy, prelude::*, word::{provider::IterWordProvider, WordList}};
+///
+/// let iter = WordList::default().sampler().into_iter();
+/// let provider = IterWordProvider::new(iter, Entropy::from_real(7776));
+/// println!("Word: {}", provider.word());
+/// # }
+/// ```
+pub struct IterWordProvider<I> {
+    /// The iterator words are obtained from.
+    iter: Arc<Mutex<I>>,
+
+    /// The declared entropy of each provided word.
+    entropy: Entropy,
+}
+
+impl<I> IterWordProvider<I>
+where
+    I: Iterator<Item = String> + Send,
+{
+    /// Construct a word provider from the given infinite iterator with the declared `entropy`.
+    pub fn new(iter: I, entropy: Entropy) -> Self {
+        Self {
+            iter: Arc::new(Mutex::new(iter)),
+            entropy,
+        }
+    }
+}
+
+impl<I> Clone for IterWordProvider<I> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            entropy: self.entropy,
+        }
+    }
+}
+
+impl<I> Debug for IterWordProvider<I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("IterWordProvider")
+            .field("entropy", &self.entropy)
+            .finish()
+    }
+}
+
+impl<I> HasEntropy for IterWordProvider<I> {
+    fn entropy(&self) -> Entropy {
+        self.entropy
+    }
+}
+
+impl<I> WordProvider for IterWordProvider<I>
+where
+    I: Iterator<Item = String> + Send,
+{
+    /// Obtain the next word from the iterator.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the iterator is depleted, as word providers must be infinite.
+    fn word(&self) -> String {
+        self.iter
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .next()
+            .expect("word provider iterator is depleted")
+    }
+}
+
+/// A word provider sampling each word from one of several providers, according to their weights.
+///
+/// This mixes words from several wordlists into a single passphrase, such as English and Spanish
+/// words for a bilingual user base. For each word a provider is picked proportional to its
+/// weight, and a word is obtained from it.
+///
+/// The entropy accounts for the combined pool of words. If all providers expose their
+/// [known words](WordProvider::known_words), and the providers share no words, picking the
+/// provider adds to the entropy of each word. Shared words, such as `no` in both English and
+/// Spanish, are accounted for exactly if the providers sample their words uniformly. Otherwise
+/// the entropy is conservatively estimated as the weighted average entropy of the providers.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{config::BasicConfig, prelude::*, word::{provider::MixedWordProvider, WordList}};
+///
+/// let english = WordList::try_new(vec!["correct".into(), "horse".into()]).unwrap();
+/// let spanish = WordList::try_new(vec!["caballo".into(), "correcto".into()]).unwrap();
+/// let provider = MixedWordProvider::new(vec![
+///     (english.sampler(), 1.0),
+///     (spanish.sampler(), 1.0),
+/// ]);
+/// assert_eq!(2.0, provider.entropy().bits());
+///
+/// println!("Passphrase: {}", BasicConfig::new(provider).to_scheme().generate());
+/// ```
+#[derive(Clone, Debug)]
+pub struct MixedWordProvider<P> {
+    /// The providers to obtain words from.
+    providers: Vec<P>,
+
+    /// Random weighted distribution used to pick a provider.
+    distribution: WeightedIndex<f64>,
+
+    /// The entropy of the combined pool of words.
+    entropy: Entropy,
+}
+
+impl<P: WordProvider> MixedWordProvider<P> {
+    /// Construct a provider mixing the given providers, each with its weight.
+    ///
+    /// Weights don't have to be normalized.
+    ///
+    /// # Panics
+    ///
+    /// This panics if no providers are given, or if any weight is not a finite positive number.
+    pub fn new(providers: Vec<(P, f64)>) -> Self {
+        if providers.is_empty() {
+            panic!("cannot construct mixed word provider, no providers given");
+        }
+        if providers.iter().any(|(_, w)| !w.is_finite() || *w <= 0.0) {
+            panic!("cannot construct mixed word provider, weights must be finite positive numbers");
+        }
+
+        let (providers, weights): (Vec<_>, Vec<_>) = providers.into_iter().unzip();
+        let total: f64 = weights.iter().sum();
+        let probabilities: Vec<_> = weights.iter().map(|w| w / total).collect();
+        Self {
+            distribution: WeightedIndex::new(&weights).expect("invalid provider weights"),
+            entropy: mixed_entropy(&providers, &probabilities),
+            providers,
+        }
+    }
+
+    /// Get the mixed providers.
+    pub fn providers(&self) -> &[P] {
+        &self.providers
+    }
+}
+
+/// Determine the entropy of mixing the given providers with the given probabilities.
+fn mixed_entropy<P: WordProvider>(providers: &[P], probabilities: &[f64]) -> Entropy {
+    let average = Entropy::from_bits(
+        providers
+            .iter()
+            .zip(probabilities)
+            .map(|(provider, p)| provider.entropy().bits() * p)
+            .sum::<f64>(),
+    );
+    let known = match providers
+        .iter()
+        .map(WordProvider::known_words)
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(known) => known,
+        None => return average,
+    };
+
+    // Without shared words, the picked provider is revealed by each word and adds to its entropy
+    let mut owners = HashMap::new();
+    let mut shared = false;
+    for (i, words) in known.iter().enumerate() {
+        for word in words {
+            shared |= *owners.entry(*word).or_insert(i) != i;
+        }
+    }
+    if !shared {
+        return Entropy::from_distribution(probabilities.iter().copied()) + average;
+    }
+
+    // With shared words, determine the exact distribution if all providers are uniform
+    let uniform = providers.iter().zip(&known).all(|(provider, words)| {
+        (provider.entropy().bits() - (words.len() as f64).log2()).abs() < 1e-9
+    });
+    if !uniform {
+        return average;
+    }
+    let mut distribution = HashMap::new();
+    for (words, p) in known.iter().zip(probabilities) {
+        for word in words {
+            *distribution.entry(*word).or_insert(0.0) += p / words.len() as f64;
+        }
+    }
+    Entropy::from_distribution(distribution.values().copied())
+}
+
+impl<P> HasEntropy for MixedWordProvider<P> {
+    fn entropy(&self) -> Entropy {
+        self.entropy
+    }
+}
+
+impl<P: WordProvider> WordProvider for MixedWordProvider<P> {
+    fn word(&self) -> String {
+        self.word_with_rng(&mut default_rng())
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        let provider = &self.providers[rng.sample(&self.distribution)];
+        provider.word_with_rng(rng)
+    }
+
+    fn sample_ref(&self) -> Option<&str> {
+        let provider = &self.providers[default_rng().sample(&self.distribution)];
+        provider.sample_ref()
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        let mut words = Vec::new();
+        for provider in &self.providers {
+            words.extend(provider.known_words()?);
+        }
+        Some(words)
+    }
+}
@@ -0,0 +1,138 @@
+//! Pluggable storage for loading wordlists
+//!
+//! The [`WordSource`](WordSource) trait abstracts where wordlist files are read from, by name.
+//! This allows loading wordlists on platforms without a regular filesystem, such as WASI,
+//! embedded flash filesystems, or asset bundles, without patching this crate.
+//!
+//! The following sources are provided:
+//!
+//! - [`FsSource`](FsSource): reads files relative to a directory on the filesystem.
+//! - [`MemorySource`](MemorySource): holds files in memory, added at runtime.
+//! - [`StaticSource`](StaticSource): holds files in static memory, such as embedded using
+//!   `include_bytes!`.
+//!
+//! Use the `_from` loaders on [`WordList`](super::WordList), such as
+//! [`load_from`](super::WordList::load_from), to load a wordlist from a source.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// A source of named wordlist files.
+pub trait WordSource {
+    /// Read the contents of the file with the given name.
+    ///
+    /// An error of kind [`NotFound`](io::ErrorKind::NotFound) should be returned if the source
+    /// has no file with the given name.
+    fn read(&self, name: &str) -> io::Result<Vec<u8>>;
+}
+
+/// Read the contents of a named file from the given source as string.
+pub(super) fn read_to_string<S: WordSource + ?Sized>(source: &S, name: &str) -> io::Result<String> {
+    String::from_utf8(source.read(name)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// The error returned for names unknown to a source.
+fn not_found(name: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no wordlist named '{}' in source", name),
+    )
+}
+
+/// A source reading files relative to a directory on the filesystem.
+#[derive(Clone, Debug)]
+pub struct FsSource {
+    /// The directory names are resolved against.
+    root: PathBuf,
+}
+
+impl FsSource {
+    /// Construct a source reading files relative to the given `root` directory.
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl WordSource for FsSource {
+    fn read(&self, name: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.root.join(name))
+    }
+}
+
+/// A source holding files in memory.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::word::{source::MemorySource, WordList};
+///
+/// let mut source = MemorySource::new();
+/// source.insert("animals.txt", b"cat\ndog\n".to_vec());
+///
+/// let list = WordList::load_from(&source, "animals.txt").unwrap();
+/// assert_eq!(2, list.words().len());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemorySource {
+    /// The files in this source, by name.
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl MemorySource {
+    /// Construct an empty in-memory source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file with the given name and contents, replacing any file with the same name.
+    pub fn insert<N: Into<String>>(&mut self, name: N, contents: Vec<u8>) {
+        self.files.insert(name.into(), contents);
+    }
+}
+
+impl WordSource for MemorySource {
+    fn read(&self, name: &str) -> io::Result<Vec<u8>> {
+        self.files.get(name).cloned().ok_or_else(|| not_found(name))
+    }
+}
+
+/// A source holding files in static memory.
+///
+/// Useful for wordlists embedded in the binary using `include_bytes!`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::word::{source::StaticSource, WordList};
+///
+/// // Files are usually embedded using include_bytes!("animals.txt")
+/// static FILES: &[(&str, &[u8])] = &[("animals.txt", b"cat\ndog\n")];
+///
+/// let list = WordList::load_from(&StaticSource::new(FILES), "animals.txt").unwrap();
+/// assert_eq!(2, list.words().len());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct StaticSource {
+    /// The files in this source, as name and contents pairs.
+    files: &'static [(&'static str, &'static [u8])],
+}
+
+impl StaticSource {
+    /// Construct a source from the given name and contents pairs.
+    pub fn new(files: &'static [(&'static str, &'static [u8])]) -> Self {
+        Self { files }
+    }
+}
+
+impl WordSource for StaticSource {
+    fn read(&self, name: &str) -> io::Result<Vec<u8>> {
+        self.files
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, contents)| contents.to_vec())
+            .ok_or_else(|| not_found(name))
+    }
+}
@@ -0,0 +1,132 @@
+//! Structured wordlist formats
+//!
+//! Many curated wordlists are distributed as CSV or JSON rather than as plain text. This module
+//! loads such files as [`WordList`](super::WordList) directly, without manual conversion:
+//!
+//! - CSV files, taking words from a selected [column](CsvColumn). This commonly holds a word
+//!   along with metadata such as its frequency or dice number.
+//! - JSON files holding an array of words.
+//!
+//! Entries are trimmed, and empty entries are omitted. A leading UTF-8 byte order mark is
+//! stripped.
+//!
+//! This module is only available with the `structured-formats` feature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chbs::word::structured::{self, CsvColumn};
+//!
+//! let csv = "rank,word\n1,abacus\n2,abdomen\n";
+//! let list = structured::from_csv(csv.as_bytes(), CsvColumn::Name("word".into())).unwrap();
+//! assert_eq!(&["abacus", "abdomen"], list.words());
+//!
+//! let list = structured::from_json(&br#"["abacus", "abdomen"]"#[..]).unwrap();
+//! assert_eq!(&["abacus", "abdomen"], list.words());
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use std::fs::File;
+use std::io::Read;
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use std::path::Path;
+
+use super::{read_all, strip_bom, WordList, WordListError};
+
+/// The column of a CSV wordlist to take words from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvColumn {
+    /// The column at the given zero-based index.
+    ///
+    /// The file must not have a header row, as it would be loaded as word.
+    Index(usize),
+
+    /// The column with the given name in the header row.
+    ///
+    /// The first row of the file must be a header row.
+    Name(String),
+}
+
+impl Display for CsvColumn {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CsvColumn::Index(index) => write!(f, "at index {}", index),
+            CsvColumn::Name(name) => write!(f, "named '{}'", name),
+        }
+    }
+}
+
+/// Load a CSV wordlist from a file, taking words from the given column.
+///
+/// See [`from_csv`](from_csv).
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub fn load_csv<P>(path: P, column: CsvColumn) -> Result<WordList, WordListError>
+where
+    P: AsRef<Path>,
+{
+    from_csv(File::open(path)?, column)
+}
+
+/// Read a CSV wordlist from the given reader, taking words from the given column.
+///
+/// Rows may have different numbers of fields. An error is returned if reading failed, if the
+/// contents aren't valid CSV, if any row doesn't have the selected column, or if there are no
+/// words.
+pub fn from_csv<R: Read>(reader: R, column: CsvColumn) -> Result<WordList, WordListError> {
+    let contents = read_all(reader)?;
+    let mut csv = csv::ReaderBuilder::new()
+        .has_headers(matches!(column, CsvColumn::Name(_)))
+        .flexible(true)
+        .from_reader(strip_bom(&contents).as_bytes());
+
+    let index = match &column {
+        CsvColumn::Index(index) => *index,
+        CsvColumn::Name(name) => csv
+            .headers()?
+            .iter()
+            .position(|header| header.trim() == name)
+            .ok_or_else(|| WordListError::MissingColumn(column.to_string()))?,
+    };
+
+    let mut words = Vec::new();
+    for record in csv.records() {
+        let word = record?
+            .get(index)
+            .ok_or_else(|| WordListError::MissingColumn(column.to_string()))?
+            .trim()
+            .to_owned();
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    WordList::try_new(words)
+}
+
+/// Load a JSON wordlist from a file.
+///
+/// See [`from_json`](from_json).
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub fn load_json<P>(path: P) -> Result<WordList, WordListError>
+where
+    P: AsRef<Path>,
+{
+    from_json(File::open(path)?)
+}
+
+/// Read a JSON wordlist from the given reader.
+///
+/// The contents must be an array of strings. An error is returned if reading failed, if the
+/// contents aren't an array of strings, or if there are no words.
+pub fn from_json<R: Read>(reader: R) -> Result<WordList, WordListError> {
+    let contents = read_all(reader)?;
+    let words: Vec<String> = serde_json::from_str(strip_bom(&contents))?;
+    WordList::try_new(
+        words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(str::to_owned)
+            .collect(),
+    )
+}
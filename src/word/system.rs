@@ -0,0 +1,81 @@
+//! Operating system dictionary wordlists
+//!
+//! Many Unix-like systems ship a dictionary of words, such as `/usr/share/dict/words`. This
+//! module allows loading such a dictionary as [`WordList`](super::WordList), so quick scripts can
+//! generate passphrases without shipping a wordlist.
+//!
+//! System dictionaries are not curated for passphrase generation, and commonly include proper
+//! nouns, possessives and very long or obscure words. Sensible filtering is therefore applied by
+//! default, see [`is_suitable`](is_suitable).
+//!
+//! This module is only available with the `system-dict` feature.
+
+use std::collections::HashSet;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use super::{WordList, WordListError};
+
+/// Known locations of system dictionaries, in order of preference.
+pub const SYSTEM_DICT_PATHS: [&str; 4] = [
+    "/usr/share/dict/words",
+    "/usr/dict/words",
+    "/usr/share/dict/web2",
+    "/usr/share/dict/american-english",
+];
+
+/// The minimum number of characters of a suitable word.
+const MIN_LENGTH: usize = 3;
+
+/// The maximum number of characters of a suitable word.
+const MAX_LENGTH: usize = 9;
+
+/// Find the path of the system dictionary.
+///
+/// Returns the first existing path in [`SYSTEM_DICT_PATHS`](SYSTEM_DICT_PATHS), or `None` if no
+/// system dictionary is available.
+pub fn find() -> Option<&'static Path> {
+    SYSTEM_DICT_PATHS
+        .iter()
+        .map(Path::new)
+        .find(|path| path.is_file())
+}
+
+/// Load the system dictionary as wordlist.
+///
+/// The dictionary is filtered to only keep [suitable](is_suitable) words, and duplicates are
+/// removed. Use [`WordList::load`](WordList::load) with the path returned by [`find`](find) to
+/// load the dictionary unfiltered.
+///
+/// An error is returned if no system dictionary was found, if loading it failed, or if it didn't
+/// contain any suitable words.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use chbs::word::system;
+///
+/// let sampler = system::load().expect("no system dictionary").sampler();
+/// ```
+pub fn load() -> Result<WordList, WordListError> {
+    let path = find().ok_or(WordListError::NoSystemDict)?;
+
+    let mut seen = HashSet::new();
+    let words: Vec<String> = read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|w| is_suitable(w))
+        .filter(|w| seen.insert(*w))
+        .map(str::to_owned)
+        .collect();
+
+    WordList::try_new(words)
+}
+
+/// Check whether the given dictionary word is suitable for passphrase generation.
+///
+/// Suitable words consist of 3 to 9 lowercase ASCII letters. This excludes proper nouns,
+/// possessives, abbreviations and long words that are hard to type.
+pub fn is_suitable(word: &str) -> bool {
+    (MIN_LENGTH..=MAX_LENGTH).contains(&word.len()) && word.bytes().all(|b| b.is_ascii_lowercase())
+}
@@ -0,0 +1,77 @@
+//! Themed wordlists for memorable identifiers
+//!
+//! This module provides small built-in wordlists of a single theme, such as colors or animals.
+//! Combining a word from several themes produces memorable identifiers like `brave-teal-otter`,
+//! see [`ThemedConfig`](crate::config::ThemedConfig).
+//!
+//! Each built-in theme contains 64 (2<sup>6</sup>) words, providing 6 bits of entropy per word.
+//! That is much less than a general wordlist, so themed identifiers should not be used as
+//! passphrases protecting anything valuable without adding more slots.
+
+use super::{builtin, StaticWordList, WordList};
+use crate::prelude::*;
+
+/// A theme for a passphrase word, selecting the list the word is sampled from.
+///
+/// With the `serde` feature, built-in themes are serialized by [name](Theme::NAMES).
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Theme {
+    /// Adjectives, such as `brave` or `quiet`.
+    Adjective,
+
+    /// Animals, such as `otter` or `panda`.
+    Animal,
+
+    /// Colors, such as `teal` or `amber`.
+    Color,
+
+    /// Verbs, such as `glide` or `wander`.
+    Verb,
+
+    /// Numbers from `0` to `99`.
+    Number,
+
+    /// A custom wordlist.
+    Custom(WordList),
+}
+
+impl Theme {
+    /// The names of all built-in themes, see [`from_name`](Theme::from_name).
+    pub const NAMES: [&'static str; 5] = ["adjective", "animal", "color", "verb", "number"];
+
+    /// Get a built-in theme by name, such as `animal`.
+    ///
+    /// Names are matched case-insensitively. Returns `None` if there is no built-in theme with the
+    /// given name. See [`NAMES`](Theme::NAMES) for all available names.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "adjective" => Some(Theme::Adjective),
+            "animal" => Some(Theme::Animal),
+            "color" => Some(Theme::Color),
+            "verb" => Some(Theme::Verb),
+            "number" => Some(Theme::Number),
+            _ => None,
+        }
+    }
+
+    /// Build a word provider sampling words of this theme.
+    pub fn provider(&self) -> Box<dyn DynWordProvider> {
+        match self {
+            Theme::Adjective => {
+                Box::new(StaticWordList::new(builtin::THEME_ADJECTIVE_WORDS).sampler())
+            }
+            Theme::Animal => Box::new(StaticWordList::new(builtin::THEME_ANIMAL_WORDS).sampler()),
+            Theme::Color => Box::new(StaticWordList::new(builtin::THEME_COLOR_WORDS).sampler()),
+            Theme::Verb => Box::new(StaticWordList::new(builtin::THEME_VERB_WORDS).sampler()),
+            Theme::Number => {
+                Box::new(WordList::from_words((0..100).map(|n| n.to_string()).collect()).sampler())
+            }
+            Theme::Custom(list) => Box::new(list.sampler()),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Wordlist cleaning pipeline
+//!
+//! This module provides [`TidyConfig`](TidyConfig), a configurable pipeline to clean up a
+//! [`WordList`](super::WordList), similar to the [tidy] tool. It produces a cleaned wordlist
+//! along with a [report](TidyReport) of all removed words.
+//!
+//! The following steps are available, and are applied in this order:
+//!
+//! 1. Remove words containing non-alphabetic characters
+//! 2. Remove duplicate words
+//! 3. Remove words that are a prefix of another word, making the list a prefix code
+//! 4. Enforce a minimum edit distance between all words
+//! 5. Ensure the first characters of every word are unique
+//!
+//! [tidy]: https://github.com/sts10/tidy
+
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use super::{WordList, WordListError};
+use crate::entropy::Entropy;
+
+/// A configuration for cleaning a wordlist.
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::word::{tidy::{TidyConfig, TidyReason}, WordList};
+///
+/// let words = vec!["abacus", "abacus", "ab", "t-shirt", "robust"];
+/// let list = WordList::try_new(words.into_iter().map(Into::into).collect()).unwrap();
+///
+/// let (list, report) = TidyConfig::default().tidy(&list).unwrap();
+/// assert_eq!(&["abacus", "robust"], list.words());
+/// assert_eq!(1, report.count(TidyReason::Duplicate));
+/// assert_eq!(1, report.count(TidyReason::Prefix));
+/// assert_eq!(1, report.count(TidyReason::NonAlphabetic));
+/// ```
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into))]
+#[non_exhaustive]
+pub struct TidyConfig {
+    /// Whether to remove words containing non-alphabetic characters.
+    pub alphabetic_only: bool,
+
+    /// Whether to remove duplicate words.
+    pub dedup: bool,
+
+    /// Whether to remove words that are a prefix of another word.
+    ///
+    /// This makes the wordlist a prefix code, so words may be concatenated without separator
+    /// while still being unambiguous.
+    pub remove_prefixes: bool,
+
+    /// The minimum edit distance between any two words, `0` to disable.
+    ///
+    /// Words are kept in order, a word is removed if its
+    /// [Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance) to any word
+    /// kept before it is lower than this.
+    pub min_edit_distance: usize,
+
+    /// The number of leading characters that must be unique across all words, `0` to disable.
+    ///
+    /// Words are kept in order, a word is removed if a word kept before it starts with the same
+    /// characters. This allows users to only type word prefixes, like the EFF short wordlist #2.
+    pub unique_prefix_length: usize,
+}
+
+impl TidyConfig {
+    /// Set whether to remove words containing non-alphabetic characters.
+    pub fn with_alphabetic_only(mut self, alphabetic_only: bool) -> Self {
+        self.alphabetic_only = alphabetic_only;
+        self
+    }
+
+    /// Set whether to remove duplicate words.
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set whether to remove words that are a prefix of another word.
+    pub fn with_remove_prefixes(mut self, remove_prefixes: bool) -> Self {
+        self.remove_prefixes = remove_prefixes;
+        self
+    }
+
+    /// Set the minimum edit distance between any two words, `0` to disable.
+    pub fn with_min_edit_distance(mut self, min_edit_distance: usize) -> Self {
+        self.min_edit_distance = min_edit_distance;
+        self
+    }
+
+    /// Set the number of leading characters that must be unique across all words, `0` to
+    /// disable.
+    pub fn with_unique_prefix_length(mut self, unique_prefix_length: usize) -> Self {
+        self.unique_prefix_length = unique_prefix_length;
+        self
+    }
+
+    /// Clean the given wordlist.
+    ///
+    /// Returns the cleaned wordlist, in the original order, along with a report of the removed
+    /// words. An error is returned if fewer than two words are left.
+    pub fn tidy(&self, list: &WordList) -> Result<(WordList, TidyReport), WordListError> {
+        let mut words: Vec<String> = list.words().to_vec();
+        let mut report = TidyReport {
+            removed: Vec::new(),
+            words_before: words.len(),
+        };
+
+        if self.alphabetic_only {
+            words = report.retain(words, TidyReason::NonAlphabetic, |w| {
+                w.chars().all(char::is_alphabetic)
+            });
+        }
+
+        if self.dedup {
+            let mut seen = HashSet::new();
+            words = report.retain(words, TidyReason::Duplicate, |w| seen.insert(w.to_owned()));
+        }
+
+        if self.remove_prefixes {
+            // A word is a prefix of another word if it is a prefix of its sorted successor
+            let mut sorted: Vec<&str> = words.iter().map(String::as_str).collect();
+            sorted.sort_unstable();
+            sorted.dedup();
+            let prefixes: HashSet<String> = sorted
+                .windows(2)
+                .filter(|pair| pair[1].starts_with(pair[0]))
+                .map(|pair| pair[0].to_owned())
+                .collect();
+            words = report.retain(words, TidyReason::Prefix, |w| !prefixes.contains(w));
+        }
+
+        if self.min_edit_distance > 0 {
+            let mut kept: Vec<Vec<char>> = Vec::new();
+            words = report.retain(words, TidyReason::EditDistance, |w| {
+                let chars: Vec<char> = w.chars().collect();
+                let far = kept.iter().all(|other| {
+                    // Length difference is a lower bound, skip calculating the distance
+                    (chars.len() as isize - other.len() as isize).unsigned_abs()
+                        >= self.min_edit_distance
+                        || edit_distance(&chars, other) >= self.min_edit_distance
+                });
+                if far {
+                    kept.push(chars);
+                }
+                far
+            });
+        }
+
+        if self.unique_prefix_length > 0 {
+            let mut seen = HashSet::new();
+            words = report.retain(words, TidyReason::UniquePrefix, |w| {
+                seen.insert(
+                    w.chars()
+                        .take(self.unique_prefix_length)
+                        .collect::<String>(),
+                )
+            });
+        }
+
+        Ok((WordList::try_new(words)?, report))
+    }
+}
+
+impl Default for TidyConfig {
+    /// Build a default tidy configuration.
+    ///
+    /// This removes non-alphabetic words, duplicates and prefix words. A minimum edit distance
+    /// and unique prefixes are not enforced.
+    fn default() -> TidyConfig {
+        TidyConfig {
+            alphabetic_only: true,
+            dedup: true,
+            remove_prefixes: true,
+            min_edit_distance: 0,
+            unique_prefix_length: 0,
+        }
+    }
+}
+
+/// A report of words removed while cleaning a wordlist.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TidyReport {
+    /// All removed words with the reason for removal, in order of removal.
+    pub removed: Vec<(String, TidyReason)>,
+
+    /// The number of words before cleaning.
+    pub words_before: usize,
+}
+
+impl TidyReport {
+    /// Get the number of words after cleaning.
+    pub fn words_after(&self) -> usize {
+        self.words_before - self.removed.len()
+    }
+
+    /// Get the change in entropy per uniformly sampled word caused by cleaning.
+    ///
+    /// This is zero or negative, as cleaning only removes words.
+    pub fn entropy_change(&self) -> Entropy {
+        Entropy::from_real(self.words_after() as f64) - Entropy::from_real(self.words_before as f64)
+    }
+
+    /// Get the number of words removed for the given reason.
+    pub fn count(&self, reason: TidyReason) -> usize {
+        self.removed.iter().filter(|(_, r)| *r == reason).count()
+    }
+
+    /// Retain words matching the predicate, and report all other words for the given reason.
+    fn retain<F>(&mut self, words: Vec<String>, reason: TidyReason, mut predicate: F) -> Vec<String>
+    where
+        F: FnMut(&str) -> bool,
+    {
+        let (keep, remove): (Vec<String>, Vec<String>) =
+            words.into_iter().partition(|w| predicate(w));
+        self.removed
+            .extend(remove.into_iter().map(|word| (word, reason)));
+        keep
+    }
+}
+
+/// The reason a word was removed while cleaning a wordlist.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TidyReason {
+    /// The word contained non-alphabetic characters.
+    NonAlphabetic,
+
+    /// The word was a duplicate of an earlier word.
+    Duplicate,
+
+    /// The word was a prefix of another word.
+    Prefix,
+
+    /// The word was too similar to an earlier word.
+    EditDistance,
+
+    /// The word started with the same characters as an earlier word.
+    UniquePrefix,
+}
+
+impl Display for TidyReason {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TidyReason::NonAlphabetic => write!(f, "non-alphabetic"),
+            TidyReason::Duplicate => write!(f, "duplicate"),
+            TidyReason::Prefix => write!(f, "prefix of another word"),
+            TidyReason::EditDistance => write!(f, "edit distance too small"),
+            TidyReason::UniquePrefix => write!(f, "prefix not unique"),
+        }
+    }
+}
+
+/// Calculate the Levenshtein edit distance between two words.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
+    }
+    row[b.len()]
+}
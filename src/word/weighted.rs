@@ -0,0 +1,341 @@
+//! Weighted wordlists with non-uniform sampling
+//!
+//! This module provides [`WeightedWordList`](WeightedWordList), a wordlist where each word has a
+//! weight, such as its frequency in a corpus. A [`WeightedWordSampler`](WeightedWordSampler)
+//! samples words proportional to their weight.
+//!
+//! As words are not sampled uniformly, the entropy is not `log2(words)`. The entropy of a
+//! weighted sampler is the Shannon entropy of its distribution instead, which is always lower
+//! than that of a uniform sampler over the same words.
+//!
+//! To favor memorable words without giving up a known amount of entropy, a sampler may be biased
+//! toward the most common words while enforcing a minimum entropy per word, see
+//! [`WeightedWordList::common_sampler`](WeightedWordList::common_sampler).
+
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use rand::{distributions::WeightedIndex, prelude::*};
+
+use super::{is_comment, strip_bom, WordListError};
+use crate::entropy::Entropy;
+use crate::prelude::*;
+use crate::rng::default_rng;
+
+/// The number of steps to search the highest bias meeting an entropy floor.
+const BIAS_SEARCH_STEPS: usize = 32;
+
+/// A wordlist with a weight for each word.
+///
+/// Construct a weighted wordlist from words and weights using [`new`](WeightedWordList::new), or
+/// load it from a file using [`load`](WeightedWordList::load).
+///
+/// # Examples
+///
+/// ```rust
+/// use chbs::{prelude::*, word::weighted::WeightedWordList};
+///
+/// let list = WeightedWordList::new(
+///     vec!["correct".into(), "horse".into()],
+///     vec![3.0, 1.0],
+/// )
+/// .unwrap();
+///
+/// let sampler = list.sampler();
+/// assert!(sampler.entropy().bits() < 1.0);
+/// println!("Word: {}", sampler.word());
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeightedWordList {
+    /// A fixed set of words.
+    words: Vec<String>,
+
+    /// The weight of each word.
+    weights: Vec<f64>,
+}
+
+impl WeightedWordList {
+    /// Construct a new weighted word list with the given words and weights.
+    ///
+    /// An error is returned if the list of words is empty, if the number of weights doesn't
+    /// match the number of words, or if any weight is not a finite positive number.
+    pub fn new(words: Vec<String>, weights: Vec<f64>) -> Result<Self, WordListError> {
+        if words.is_empty() {
+            return Err(WordListError::Empty);
+        }
+        if words.len() != weights.len() {
+            return Err(WordListError::InvalidWeight(
+                weights.len().min(words.len()) + 1,
+            ));
+        }
+        if let Some(i) = weights.iter().position(|w| !w.is_finite() || *w <= 0.0) {
+            return Err(WordListError::InvalidWeight(i + 1));
+        }
+
+        Ok(Self { words, weights })
+    }
+
+    /// Load a weighted wordlist from a file.
+    ///
+    /// This loads a weighted wordlist from a file at the given path, having a word and its weight
+    /// on each line, separated by a tab. This is the format commonly produced by corpus frequency
+    /// tools.
+    ///
+    /// - The weight is the last item on each line, separated by a tab or other whitespace
+    /// - Weights must be finite positive numbers, and don't have to be normalized
+    /// - Empty lines and [comments](super::WordList::load#comments) are omitted
+    ///
+    /// An error is returned if loading the wordlist failed, if any line doesn't have a valid
+    /// weight, or if the loaded file didn't contain any words.
+    ///
+    /// # File examples
+    /// ```txt
+    /// the 23135851162
+    /// of 13151942776
+    /// and 12997637966
+    /// ```
+    pub fn load<P>(path: P) -> Result<Self, WordListError>
+    where
+        P: AsRef<Path>,
+    {
+        let mut words = Vec::new();
+        let mut weights = Vec::new();
+        for (i, line) in strip_bom(&read_to_string(path)?).lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || is_comment(line) {
+                continue;
+            }
+
+            let (word, weight) = line
+                .rsplit_once(|c: char| c.is_whitespace())
+                .ok_or(WordListError::InvalidWeight(i + 1))?;
+            let weight: f64 = weight
+                .parse()
+                .map_err(|_| WordListError::InvalidWeight(i + 1))?;
+            if !weight.is_finite() || weight <= 0.0 {
+                return Err(WordListError::InvalidWeight(i + 1));
+            }
+
+            words.push(word.trim().to_owned());
+            weights.push(weight);
+        }
+
+        Self::new(words, weights)
+    }
+
+    /// Get the words in this wordlist.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Get the weights of the words in this wordlist.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Build a sampler for this wordlist.
+    ///
+    /// The sampler picks words proportional to their weight.
+    pub fn sampler(&self) -> WeightedWordSampler {
+        WeightedWordSampler::new(self.words.clone(), &self.weights)
+    }
+
+    /// Build a sampler biased toward common words, with a minimum entropy per word.
+    ///
+    /// Words are sampled proportional to their weight raised to the power `bias`. A bias of `0`
+    /// samples all words uniformly, a bias of `1` samples proportional to the weight like
+    /// [`sampler`](WeightedWordList::sampler). Higher values favor common words even more.
+    ///
+    /// The [min-entropy](WeightedWordSampler::min_entropy) of the sampler must be at least
+    /// `floor`, otherwise an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `bias` is negative or not finite.
+    pub fn biased_sampler(
+        &self,
+        bias: f64,
+        floor: Entropy,
+    ) -> Result<WeightedWordSampler, WordListError> {
+        assert!(
+            bias.is_finite() && bias >= 0.0,
+            "cannot construct biased sampler, bias must be a finite non-negative number",
+        );
+
+        let min_entropy = self.biased_min_entropy(bias);
+        if min_entropy < floor {
+            return Err(WordListError::EntropyFloor { min_entropy, floor });
+        }
+        Ok(WeightedWordSampler::new(
+            self.words.clone(),
+            &self.biased_weights(bias),
+        ))
+    }
+
+    /// Build a sampler biased toward common words as far as the given entropy floor allows.
+    ///
+    /// This finds the highest [bias](WeightedWordList::biased_sampler) up to `1` for which the
+    /// min-entropy per word is still at least `floor`. Common words are sampled as often as
+    /// possible, while the entropy traded for memorability stays within a known bound.
+    ///
+    /// An error is returned if even sampling all words uniformly doesn't reach `floor`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chbs::{entropy::Entropy, prelude::*, word::weighted::WeightedWordList};
+    ///
+    /// let list = WeightedWordList::new(
+    ///     vec!["the".into(), "of".into(), "and".into(), "to".into()],
+    ///     vec![23.0, 13.0, 12.0, 1.0],
+    /// )
+    /// .unwrap();
+    ///
+    /// let sampler = list.common_sampler(Entropy::from_bits(1.5)).unwrap();
+    /// assert!(sampler.min_entropy().bits() >= 1.5);
+    /// assert!(list.common_sampler(Entropy::from_bits(3.0)).is_err());
+    /// ```
+    pub fn common_sampler(&self, floor: Entropy) -> Result<WeightedWordSampler, WordListError> {
+        if self.biased_min_entropy(1.0) >= floor {
+            return self.biased_sampler(1.0, floor);
+        }
+
+        // The min-entropy decreases as the bias increases, search the highest allowed bias
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..BIAS_SEARCH_STEPS {
+            let bias = (low + high) / 2.0;
+            if self.biased_min_entropy(bias) >= floor {
+                low = bias;
+            } else {
+                high = bias;
+            }
+        }
+        self.biased_sampler(low, floor)
+    }
+
+    /// Get the word weights raised to the power `bias`, relative to the highest weight.
+    fn biased_weights(&self, bias: f64) -> Vec<f64> {
+        let max = self.weights.iter().cloned().fold(0.0, f64::max);
+        self.weights.iter().map(|w| (w / max).powf(bias)).collect()
+    }
+
+    /// Get the min-entropy of sampling with the given bias.
+    ///
+    /// This matches the [min-entropy](WeightedWordSampler::min_entropy) of the sampler built with
+    /// this bias, the weights of a word listed more than once are summed.
+    fn biased_min_entropy(&self, bias: f64) -> Entropy {
+        min_entropy(&distinct_probabilities(
+            &self.words,
+            &self.biased_weights(bias),
+        ))
+    }
+}
+
+/// A word provider sampling words proportional to their weight.
+///
+/// To construct an instance based on a [`WeightedWordList`](WeightedWordList), use the
+/// [`sampler`](WeightedWordList::sampler) method.
+///
+/// A word listed more than once is sampled with the sum of its weights. The entropy is based on
+/// the probability of each distinct word, as repeating a word doesn't make it harder to guess.
+#[derive(Clone, Debug)]
+pub struct WeightedWordSampler {
+    /// List of words that is used for sampling.
+    words: Vec<String>,
+
+    /// Random weighted distribution used for sampling.
+    distribution: WeightedIndex<f64>,
+
+    /// The Shannon entropy of the distribution.
+    entropy: Entropy,
+
+    /// The min-entropy of the distribution.
+    min_entropy: Entropy,
+}
+
+impl WeightedWordSampler {
+    /// Build a new weighted word sampler which samples the given words with the given weights.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the words and weights are not valid, see
+    /// [`WeightedWordList::new`](WeightedWordList::new).
+    pub fn new(words: Vec<String>, weights: &[f64]) -> Self {
+        assert_eq!(
+            words.len(),
+            weights.len(),
+            "cannot construct weighted sampler, number of words and weights differs",
+        );
+
+        let distribution = WeightedIndex::new(weights).expect("invalid word weights");
+        let probabilities = distinct_probabilities(&words, weights);
+        Self {
+            distribution,
+            entropy: Entropy::from_distribution(probabilities.iter().cloned()),
+            min_entropy: min_entropy(&probabilities),
+            words,
+        }
+    }
+
+    /// Sample a random word by reference.
+    fn word_ref(&self) -> &str {
+        self.word_ref_with_rng(&mut default_rng())
+    }
+
+    /// Sample a random word by reference, using the given randomness source.
+    fn word_ref_with_rng(&self, rng: &mut dyn SecureRng) -> &str {
+        &self.words[rng.sample(&self.distribution)]
+    }
+
+    /// Get the min-entropy of this sampler.
+    ///
+    /// This is the entropy of the most likely word, `-log2(p_max)`. It is a conservative measure
+    /// for how hard a word is to guess, as an attacker will guess the most likely words first.
+    /// The [entropy](HasEntropy::entropy) of this sampler is the Shannon entropy, which is never
+    /// lower than this.
+    pub fn min_entropy(&self) -> Entropy {
+        self.min_entropy
+    }
+}
+
+impl WordProvider for WeightedWordSampler {
+    fn word(&self) -> String {
+        self.word_ref().to_owned()
+    }
+
+    fn word_with_rng(&self, rng: &mut dyn SecureRng) -> String {
+        self.word_ref_with_rng(rng).to_owned()
+    }
+
+    fn sample_ref(&self) -> Option<&str> {
+        Some(self.word_ref())
+    }
+
+    fn known_words(&self) -> Option<Vec<&str>> {
+        Some(self.words.iter().map(String::as_str).collect())
+    }
+}
+
+impl HasEntropy for WeightedWordSampler {
+    /// The Shannon entropy of the weighted distribution.
+    fn entropy(&self) -> Entropy {
+        self.entropy
+    }
+}
+
+/// Get the min-entropy of the given word probabilities, the entropy of the most likely word.
+fn min_entropy(probabilities: &[f64]) -> Entropy {
+    let max = probabilities.iter().cloned().fold(0.0, f64::max);
+    Entropy::from_bits(-max.log2())
+}
+
+/// Get the probability of sampling each distinct word with the given weights.
+fn distinct_probabilities(words: &[String], weights: &[f64]) -> Vec<f64> {
+    let total: f64 = weights.iter().sum();
+    let mut probabilities: HashMap<&str, f64> = HashMap::with_capacity(words.len());
+    for (word, weight) in words.iter().zip(weights) {
+        *probabilities.entry(word).or_insert(0.0) += weight / total;
+    }
+    probabilities.into_values().collect()
+}